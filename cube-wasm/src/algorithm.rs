@@ -0,0 +1,363 @@
+// Algorithm notation
+// Algorithms are written like scrambles but may also use lowercase wide moves (r = Rw),
+// slice moves, rotations, primes on doubles (R2'), parenthesised groups with an
+// optional repeat count: "F (R U R' U')2 F'", and commutators and conjugates in
+// square brackets: "[R U R', D]" is R U R' D R U' R' D', "[R: U]" is R U R'.
+use crate::cubie_cube::CubieCube;
+use crate::optimized_cube::{MoveCode, OptimizedCube};
+
+/// Largest repeat count of a group or move, e.g. "(R U R' U')6"
+const MAX_COUNT: usize = 99;
+
+/// Deepest nesting of parentheses and brackets
+const MAX_DEPTH: usize = 16;
+
+/// Most moves an algorithm may expand to
+const MAX_MOVES: usize = 1000;
+
+/// Parse algorithm notation into move codes (0-53)
+pub fn parse_algorithm(algorithm: &str) -> Result<Vec<u8>, String> {
+    let chars: Vec<char> = algorithm.chars().collect();
+    let mut position = 0;
+    let moves = parse_sequence(&chars, &mut position, 0)?;
+    if position < chars.len() {
+        return Err(format!(
+            "Unmatched '{}' in algorithm: {}",
+            chars[position], algorithm
+        ));
+    }
+    Ok(moves)
+}
+
+/// Parse moves until the end of input, a closing bracket or a commutator separator
+fn parse_sequence(chars: &[char], position: &mut usize, depth: usize) -> Result<Vec<u8>, String> {
+    if depth > MAX_DEPTH {
+        return Err(format!("Algorithm nests deeper than {} groups", MAX_DEPTH));
+    }
+    let mut moves = Vec::new();
+    while *position < chars.len() {
+        let c = chars[*position];
+        if c.is_whitespace() {
+            *position += 1;
+        } else if c == '(' {
+            *position += 1;
+            let group = parse_sequence(chars, position, depth + 1)?;
+            if chars.get(*position) != Some(&')') {
+                return Err("Missing ')' in algorithm".to_string());
+            }
+            *position += 1;
+            let repeat = parse_count(chars, position)?.unwrap_or(1);
+            check_length(moves.len() + group.len() * repeat)?;
+            for _ in 0..repeat {
+                moves.extend_from_slice(&group);
+            }
+        } else if c == '[' {
+            *position += 1;
+            let first = parse_sequence(chars, position, depth + 1)?;
+            let separator = chars.get(*position).copied();
+            if !matches!(separator, Some(',') | Some(':')) {
+                return Err("Missing ',' or ':' in commutator".to_string());
+            }
+            *position += 1;
+            let second = parse_sequence(chars, position, depth + 1)?;
+            if chars.get(*position) != Some(&']') {
+                return Err("Missing ']' in algorithm".to_string());
+            }
+            *position += 1;
+            // A commutator also undoes the second part, a conjugate only the first
+            let second_count = if separator == Some(',') { 2 } else { 1 };
+            check_length(moves.len() + first.len() * 2 + second.len() * second_count)?;
+            let first_inverse = invert_algorithm(&first);
+            moves.extend_from_slice(&first);
+            moves.extend_from_slice(&second);
+            moves.extend(first_inverse);
+            if separator == Some(',') {
+                moves.extend(invert_algorithm(&second));
+            }
+        } else if matches!(c, ')' | ']' | ',' | ':') {
+            if depth == 0 {
+                return Err(format!("Unmatched '{}' in algorithm", c));
+            }
+            break;
+        } else {
+            moves.push(parse_move(chars, position)?);
+            check_length(moves.len())?;
+        }
+    }
+    Ok(moves)
+}
+
+/// Parse a single move: a letter, an optional `w`, then an amount and/or a prime
+fn parse_move(chars: &[char], position: &mut usize) -> Result<u8, String> {
+    let letter = chars[*position];
+    *position += 1;
+    let (base, wide) = match letter {
+        'U' | 'D' | 'F' | 'B' | 'R' | 'L' if chars.get(*position) == Some(&'w') => {
+            *position += 1;
+            (letter.to_string(), true)
+        }
+        'U' | 'D' | 'F' | 'B' | 'R' | 'L' | 'M' | 'E' | 'S' | 'x' | 'y' | 'z' => {
+            (letter.to_string(), false)
+        }
+        'u' | 'd' | 'f' | 'b' | 'r' | 'l' => (letter.to_ascii_uppercase().to_string(), true),
+        _ => return Err(format!("Invalid move: {}", letter)),
+    };
+
+    let amount = parse_count(chars, position)?.unwrap_or(1);
+    let mut prime = false;
+    while matches!(chars.get(*position), Some('\'') | Some('’')) {
+        prime = !prime;
+        *position += 1;
+    }
+    // "R'2" is written by some people for R2
+    let amount = parse_count(chars, position)?
+        .map_or(Some(amount), |count| amount.checked_mul(count))
+        .ok_or("Move count is too large")?;
+    let quarter_turns = if prime {
+        (4 - amount % 4) % 4
+    } else {
+        amount % 4
+    };
+
+    let name = if wide { format!("{}w", base) } else { base };
+    let suffix = match quarter_turns {
+        0 => return Err(format!("Move {} does not turn anything", name)),
+        1 => "",
+        2 => "2",
+        _ => "'",
+    };
+    MoveCode::from_str(&format!("{}{}", name, suffix)).map(|code| code as u8)
+}
+
+/// Parse an optional decimal count at the current position, from 1 to `MAX_COUNT`
+fn parse_count(chars: &[char], position: &mut usize) -> Result<Option<usize>, String> {
+    let start = *position;
+    while chars.get(*position).is_some_and(|c| c.is_ascii_digit()) {
+        *position += 1;
+    }
+    if start == *position {
+        return Ok(None);
+    }
+    let digits: String = chars[start..*position].iter().collect();
+    match digits.parse::<usize>() {
+        Ok(count) if (1..=MAX_COUNT).contains(&count) => Ok(Some(count)),
+        _ => Err(format!(
+            "Count {} must be between 1 and {}",
+            digits, MAX_COUNT
+        )),
+    }
+}
+
+/// Error if an algorithm would expand to more than `MAX_MOVES` moves
+fn check_length(length: usize) -> Result<(), String> {
+    if length > MAX_MOVES {
+        Err(format!(
+            "Algorithm expands to more than {} moves",
+            MAX_MOVES
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Moves that undo an algorithm: reversed, each move inverted
+pub fn invert_algorithm(moves: &[u8]) -> Vec<u8> {
+    moves
+        .iter()
+        .rev()
+        .filter_map(|&code| MoveCode::from_u8(code).ok())
+        .map(|move_code| move_code.inverse() as u8)
+        .collect()
+}
+
+/// Moves with turns of the same face merged, also across turns of the opposite face
+/// ("R L R" is "R2 L"), and cancelled turns removed
+/// Only face moves (codes 0-17) are merged; other moves are kept as they are.
+pub fn cancel_moves(moves: &[u8]) -> Vec<u8> {
+    // Quarter turns of a face move code: U = 1, U' = 3, U2 = 2
+    let quarter_turns = |code: u8| [1, 3, 2][code as usize / 6];
+    let mut result: Vec<u8> = Vec::with_capacity(moves.len());
+    for &code in moves {
+        if code >= 18 {
+            result.push(code);
+            continue;
+        }
+        // Look back through the moves on the same axis for the same face
+        let axis = code % 6 / 2;
+        let same_face = result
+            .iter()
+            .rev()
+            .take_while(|&&last| last < 18 && last % 6 / 2 == axis)
+            .position(|&last| last % 6 == code % 6)
+            .map(|back| result.len() - 1 - back);
+        match same_face {
+            Some(index) => {
+                let turns = (quarter_turns(result[index]) + quarter_turns(code)) % 4;
+                if turns == 0 {
+                    result.remove(index);
+                } else {
+                    result[index] = code % 6 + [0, 0, 12, 6][turns];
+                }
+            }
+            None => result.push(code),
+        }
+    }
+    result
+}
+
+/// The same algorithm performed from another side: each face is replaced by the one it
+/// moves to under `turns` y rotations (F to L, L to B, B to R, R to F)
+/// Slice moves and rotations follow their axis: M becomes S', S becomes M, x becomes z
+/// and z becomes x'; E and y are kept.
+pub fn y_rotated(moves: &[u8], turns: usize) -> Vec<u8> {
+    moves
+        .iter()
+        .map(|&code| (0..turns % 4).fold(code, |code, _| y_rotated_move(code)))
+        .collect()
+}
+
+/// A move relabelled under one y rotation
+fn y_rotated_move(code: u8) -> u8 {
+    // Face each face (U, D, F, B, R, L) moves to under one y rotation
+    const Y_FACES: [u8; 6] = [0, 1, 5, 4, 2, 3];
+    if code < MoveCode::OUTER_COUNT {
+        return code - code % 6 + Y_FACES[code as usize % 6];
+    }
+    // Slices M, E, S and rotations x, y, z: three axes, then clockwise, prime and double
+    let (first, inverted_axis) = if code < MoveCode::X as u8 {
+        (MoveCode::M as u8, 0)
+    } else {
+        (MoveCode::X as u8, 2)
+    };
+    let (amount, axis) = ((code - first) / 3, (code - first) % 3);
+    // The M/x axis and the S/z axis swap; one of them turns the other way round
+    let new_axis = [2, 1, 0][axis as usize];
+    let amount = if axis == inverted_axis {
+        [1, 0, 2][amount as usize]
+    } else {
+        amount
+    };
+    first + amount * 3 + new_axis
+}
+
+/// Cube state reached by applying `moves` to a solved cube
+/// Pieces are read relative to the centers, so rotations and slice moves only
+/// change how the state is held, not which state it is.
+pub fn state_after(moves: &[u8]) -> Result<CubieCube, String> {
+    let mut cube = OptimizedCube::solved();
+    for &code in moves {
+        cube.apply_move_code(MoveCode::from_u8(code)?);
+    }
+    CubieCube::from_optimized(&cube)
+}
+
+/// State an algorithm solves: the state reached by its inverse
+pub fn case_state(moves: &[u8]) -> Result<CubieCube, String> {
+    state_after(&invert_algorithm(moves))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_algorithm_notation() {
+        let expected = OptimizedCube::parse_scramble("F R U R' U' R U R' U' F'").unwrap();
+        assert_eq!(parse_algorithm("F (R U R' U')2 F'").unwrap(), expected);
+        assert_eq!(parse_algorithm("F(RUR'U')(RUR'U')F'").unwrap(), expected);
+
+        let parsed = parse_algorithm("r U R' U' M2 x' R2' y2 U3 Lw'").unwrap();
+        let expected = OptimizedCube::parse_scramble("Rw U R' U' M2 x' R2 y2 U' Lw'").unwrap();
+        assert_eq!(parsed, expected);
+
+        assert!(parse_algorithm("R U (R'").is_err());
+        assert!(parse_algorithm("R U) R'").is_err());
+        assert!(parse_algorithm("R Q").is_err());
+        assert!(parse_algorithm("R4").is_err());
+
+        // Counts and nesting are bounded
+        assert!(parse_algorithm("(R U)0").is_err());
+        assert!(parse_algorithm("R0").is_err());
+        assert!(parse_algorithm("(R U)1000000000").is_err());
+        assert!(parse_algorithm("R99999999999999999999").is_err());
+        assert!(parse_algorithm("R'99999999999999999999").is_err());
+        assert!(parse_algorithm("((((R U)99)99)99)").is_err());
+        assert!(parse_algorithm(&format!("{}R{}", "(".repeat(10000), ")".repeat(10000))).is_err());
+        assert_eq!(parse_algorithm("(R U)99").unwrap().len(), 198);
+        assert!(parse_algorithm("[[[[[[[[[[R U, D], F], B], L], R], U], D], F], B], L]").is_err());
+
+        let expected = parse_algorithm("R U R' D R U' R' D'").unwrap();
+        assert_eq!(parse_algorithm("[R U R', D]").unwrap(), expected);
+        let expected = parse_algorithm("D R U R' D R U' R' D' D'").unwrap();
+        assert_eq!(parse_algorithm("[D: [R U R', D]]").unwrap(), expected);
+        assert!(parse_algorithm("[R U R' D]").is_err());
+        assert!(parse_algorithm("[R, U").is_err());
+        assert!(parse_algorithm("R, U").is_err());
+        assert!(parse_algorithm("(R, U)").is_err());
+        assert_eq!(parse_algorithm("  ").unwrap(), Vec::<u8>::new());
+    }
+
+    #[test]
+    fn test_case_state_is_solved_by_algorithm() {
+        // Algorithms with rotations and slice moves still describe a plain case
+        for algorithm in [
+            "R U R' U' R' F R2 U' R' U' R U R' F'",
+            "M2 U M2 U2 M2 U M2",
+            "y R U R' U R U2 R' y'",
+            "x R' U R' D2 R U' R' D2 R2 x'",
+            "r U R' U' r' F R F'",
+        ] {
+            let moves = parse_algorithm(algorithm).unwrap();
+            let state = case_state(&moves).unwrap();
+            assert!(!state.is_solved(), "{}", algorithm);
+            // Scrambling the state with face moves and applying the algorithm solves it
+            let mut cube = state.to_optimized();
+            for &code in &moves {
+                cube.apply_move_code(MoveCode::from_u8(code).unwrap());
+            }
+            assert!(
+                CubieCube::from_optimized(&cube).unwrap().is_solved(),
+                "{}",
+                algorithm
+            );
+        }
+        let sune = parse_algorithm("R U R' U R U2 R'").unwrap();
+        assert_eq!(
+            y_rotated(&parse_algorithm("R U R' F' M").unwrap(), 1),
+            parse_algorithm("F U F' L' S'").unwrap()
+        );
+        assert_eq!(
+            y_rotated(&parse_algorithm("x S2 E' z' M'").unwrap(), 1),
+            parse_algorithm("z M2 E' x S").unwrap()
+        );
+        assert_eq!(y_rotated(&sune, 4), sune);
+        // Performing the relabelled algorithm is performing the algorithm after a y turn
+        let moves = parse_algorithm("r U M' x S' E2 z y' R2 Fw").unwrap();
+        for turns in 0..4 {
+            let mut rotated = OptimizedCube::solved();
+            rotated.apply_moves(&y_rotated(&moves, turns));
+            let mut conjugated = OptimizedCube::solved();
+            let y_turns = vec![MoveCode::Y as u8; turns];
+            conjugated.apply_moves(&invert_algorithm(&y_turns));
+            conjugated.apply_moves(&moves);
+            conjugated.apply_moves(&y_turns);
+            assert_eq!(rotated, conjugated, "{} y turns", turns);
+        }
+        assert_eq!(
+            cancel_moves(&parse_algorithm("R U R' R U2 R' R' F F'").unwrap()),
+            parse_algorithm("R U' R2").unwrap()
+        );
+        assert_eq!(
+            cancel_moves(&parse_algorithm("R L R U D' U' D F B2 F' B2").unwrap()),
+            parse_algorithm("R2 L").unwrap()
+        );
+        assert_eq!(
+            cancel_moves(&parse_algorithm("R L R' M R").unwrap()),
+            parse_algorithm("L M R").unwrap()
+        );
+        assert_eq!(
+            invert_algorithm(&sune),
+            parse_algorithm("R U2' R' U' R U' R'").unwrap()
+        );
+    }
+}
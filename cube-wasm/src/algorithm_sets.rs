@@ -0,0 +1,1377 @@
+// Algorithm databases for the COLL, CMLL, ZBLL and OLLCP last-layer sets, with recognition
+// The algorithms are the usual speedsolving ones where a standard algorithm exists
+// (Sune, Niklas, the OCLL and PLL algorithms and their relatives), otherwise the most
+// comfortable two-gen or three-gen (RU, RUF, RUD, RUL) algorithm by the ergonomics score.
+// Each starts with the U turn that takes its case from the side it is recognised from,
+// and the tests below check that it keeps the first two layers (both Roux blocks for
+// CMLL) and solves its case up to a U turn. Cases are told apart by what the set solves,
+// up to a U turn before and after the algorithm, and are recognised from the table
+// algorithms like OLL and PLL cases.
+// Case ids follow the community naming: COLL, CMLL and ZBLL cases are named after their
+// corner orientation shape (H, Pi, U, T, L, S, AS, and O for oriented corners) and
+// OLLCP cases after their OLL case ("27a" to "27f"). The shape is held the way the OLL
+// 21-27 algorithms solve it, and cases within a shape are numbered in recognition order:
+// corners permuted, diagonal swap, then a swap of the front, right, back or left
+// corners (with ZBLL cases further ordered by their edge permutation).
+use crate::algorithm;
+use crate::cubie_cube::CubieCube;
+use crate::last_layer::{after_auf, auf_moves};
+use crate::optimized_cube::{MoveCode, OptimizedCube};
+use crate::subset_scrambler::is_f2l_solved;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// A set of last-layer algorithms with its own case recognition
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlgorithmSet {
+    /// Corners of the last layer with edges oriented, keeping edge orientation
+    Coll,
+    /// Corners of the last layer with both Roux blocks solved
+    Cmll,
+    /// Whole last layer with edges oriented
+    Zbll,
+    /// Orientation and corner permutation of the last layer
+    Ollcp,
+}
+
+impl AlgorithmSet {
+    pub const ALL: [AlgorithmSet; 4] = [
+        AlgorithmSet::Coll,
+        AlgorithmSet::Cmll,
+        AlgorithmSet::Zbll,
+        AlgorithmSet::Ollcp,
+    ];
+
+    /// Parse a set name, case-insensitive: "COLL", "CMLL", "ZBLL", "OLLCP"
+    pub fn from_name(name: &str) -> Result<Self, String> {
+        match name.to_ascii_uppercase().as_str() {
+            "COLL" => Ok(AlgorithmSet::Coll),
+            "CMLL" => Ok(AlgorithmSet::Cmll),
+            "ZBLL" => Ok(AlgorithmSet::Zbll),
+            "OLLCP" => Ok(AlgorithmSet::Ollcp),
+            _ => Err(format!("Unknown algorithm set: {}", name)),
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            AlgorithmSet::Coll => "COLL",
+            AlgorithmSet::Cmll => "CMLL",
+            AlgorithmSet::Zbll => "ZBLL",
+            AlgorithmSet::Ollcp => "OLLCP",
+        }
+    }
+
+    /// (case id, algorithm) of every case
+    pub fn cases(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            AlgorithmSet::Coll => &COLL_ALGORITHMS,
+            AlgorithmSet::Cmll => &CMLL_ALGORITHMS,
+            AlgorithmSet::Zbll => &ZBLL_ALGORITHMS,
+            AlgorithmSet::Ollcp => &OLLCP_ALGORITHMS,
+        }
+    }
+
+    /// Algorithm of a case id such as "H1" or "27c" (case-insensitive)
+    pub fn algorithm(self, id: &str) -> Option<&'static str> {
+        self.cases()
+            .iter()
+            .find(|(case, _)| case.eq_ignore_ascii_case(id.trim()))
+            .map(|&(_, alg)| alg)
+    }
+
+    /// The part of the last layer the set's algorithms solve: corners for COLL and
+    /// CMLL, everything for ZBLL, corners and edge orientation for OLLCP
+    fn pattern(self, cube: &CubieCube) -> Vec<u8> {
+        let mut pattern = cube.cp[..4].to_vec();
+        pattern.extend_from_slice(&cube.co[..4]);
+        match self {
+            AlgorithmSet::Coll | AlgorithmSet::Cmll => {}
+            AlgorithmSet::Zbll => {
+                pattern.extend_from_slice(&cube.ep[..4]);
+                pattern.extend_from_slice(&cube.eo[..4]);
+            }
+            AlgorithmSet::Ollcp => pattern.extend_from_slice(&cube.eo[..4]),
+        }
+        pattern
+    }
+
+    /// Whether the cube is a state the set's algorithms start from (or a skip)
+    fn check(self, cube: &CubieCube) -> Result<(), String> {
+        let edges_oriented = cube.eo[..4].iter().all(|&o| o == 0);
+        let corners_oriented = cube.co[..4].iter().all(|&o| o == 0);
+        match self {
+            AlgorithmSet::Cmll => {
+                let blocks_solved = (4..8).all(|i| cube.cp[i] == i as u8 && cube.co[i] == 0)
+                    && ROUX_BLOCK_EDGES
+                        .iter()
+                        .all(|&i| cube.ep[i] == i as u8 && cube.eo[i] == 0);
+                if !blocks_solved {
+                    return Err("Roux blocks are not solved".to_string());
+                }
+            }
+            _ if !is_f2l_solved(cube) => {
+                return Err("First two layers are not solved".to_string());
+            }
+            AlgorithmSet::Coll if !edges_oriented => {
+                return Err("Last layer edges are not oriented".to_string());
+            }
+            AlgorithmSet::Zbll if !edges_oriented => {
+                return Err("Last layer edges are not oriented".to_string());
+            }
+            AlgorithmSet::Zbll if corners_oriented && !self.is_skip(cube) => {
+                return Err("Last layer corners are oriented, the case is a PLL".to_string());
+            }
+            AlgorithmSet::Ollcp if corners_oriented && edges_oriented && !self.is_skip(cube) => {
+                return Err("Last layer is oriented, the case is a PLL".to_string());
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Whether only a U turn is left for the set to solve
+    fn is_skip(self, cube: &CubieCube) -> bool {
+        solving_auf(self, cube).is_some()
+    }
+}
+
+/// Edges of the Roux blocks: DR, DL, FR, FL, BL, BR
+const ROUX_BLOCK_EDGES: [usize; 6] = [4, 6, 8, 9, 10, 11];
+
+/// Recognised case of a set, with the U turns before and after its algorithm
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SetCase {
+    pub set: AlgorithmSet,
+    /// Index into `set.cases()`, None for a skip
+    pub index: Option<usize>,
+    /// Quarter turns of U before the algorithm
+    pub pre_auf: u8,
+    /// Quarter turns of U after the algorithm
+    pub post_auf: u8,
+}
+
+impl SetCase {
+    /// Case id within the set ("Skip" when nothing but a U turn is left)
+    pub fn id(&self) -> &'static str {
+        self.index.map_or("Skip", |index| self.set.cases()[index].0)
+    }
+
+    pub fn algorithm(&self) -> Option<&'static str> {
+        self.index.map(|index| self.set.cases()[index].1)
+    }
+
+    /// Pre-AUF, algorithm and post-AUF as move codes
+    pub fn solution(&self) -> Vec<u8> {
+        let mut moves = auf_moves(self.pre_auf);
+        if let Some(alg) = self.algorithm() {
+            moves.extend(algorithm::parse_algorithm(alg).expect("valid set algorithm"));
+        }
+        moves.extend(auf_moves(self.post_auf));
+        moves
+    }
+}
+
+/// `cube` after `turns` quarter turns of U done before it
+fn before_auf(cube: &CubieCube, turns: u8) -> CubieCube {
+    after_auf(&CubieCube::solved(), turns).multiply(cube)
+}
+
+/// Quarter turns of U after which the set has nothing left to solve, if any
+fn solving_auf(set: AlgorithmSet, cube: &CubieCube) -> Option<u8> {
+    let solved = set.pattern(&CubieCube::solved());
+    (0..4).find(|&turns| set.pattern(&after_auf(cube, turns)) == solved)
+}
+
+/// Case index and U turns before it, by the pattern they make
+type CasePatterns = HashMap<Vec<u8>, (usize, u8)>;
+
+/// Pattern of each case of a set after every U turn done before it
+fn case_patterns(set: AlgorithmSet) -> &'static CasePatterns {
+    static PATTERNS: OnceLock<Vec<CasePatterns>> = OnceLock::new();
+    let patterns = PATTERNS.get_or_init(|| {
+        AlgorithmSet::ALL
+            .iter()
+            .map(|&set| {
+                let mut patterns = HashMap::new();
+                for (index, (_, alg)) in set.cases().iter().enumerate() {
+                    let moves = algorithm::parse_algorithm(alg).expect("valid set algorithm");
+                    let case = algorithm::case_state(&moves).expect("valid set case");
+                    for turns in 0..4 {
+                        patterns
+                            .entry(set.pattern(&before_auf(&case, turns)))
+                            .or_insert((index, turns));
+                    }
+                }
+                patterns
+            })
+            .collect()
+    });
+    &patterns[set as usize]
+}
+
+/// Cubie state of a cube, with the M slice turned back into place for CMLL
+fn set_state(set: AlgorithmSet, cube: &OptimizedCube) -> Result<CubieCube, String> {
+    if set != AlgorithmSet::Cmll {
+        return CubieCube::from_optimized(cube);
+    }
+    let mut turned = cube.clone();
+    for _ in 0..4 {
+        let state = CubieCube::from_optimized(&turned)?;
+        if set.check(&state).is_ok() {
+            return Ok(state);
+        }
+        turned.apply_move_code(MoveCode::M);
+    }
+    Err("Roux blocks are not solved".to_string())
+}
+
+/// Case of the set showing on a cube
+pub fn recognize(set: AlgorithmSet, cube: &OptimizedCube) -> Result<SetCase, String> {
+    let state = set_state(set, cube)?;
+    set.check(&state)?;
+    if let Some(post_auf) = solving_auf(set, &state) {
+        return Ok(SetCase {
+            set,
+            index: None,
+            pre_auf: 0,
+            post_auf,
+        });
+    }
+    for pre_auf in 0..4 {
+        let pattern = set.pattern(&after_auf(&state, pre_auf));
+        if let Some(&(index, turns)) = case_patterns(set).get(&pattern) {
+            return Ok(SetCase {
+                set,
+                index: Some(index),
+                pre_auf,
+                post_auf: (4 - turns) % 4,
+            });
+        }
+    }
+    Err(format!("Unknown {} case", set.name()))
+}
+
+/// COLL algorithms: corners solved, edges kept oriented
+pub const COLL_ALGORITHMS: [(&str, &str); 42] = [
+    ("H1", "R U2 R' U' R U R' U' R U' R'"),
+    ("H2", "F R U R' U' R U R' U' R U R' U' F'"),
+    ("H3", "U' R U R' U R U L' U R' U' L"),
+    ("H4", "U' F R U' R' U R U2 R' U' R U R' U' F'"),
+    ("Pi1", "R U2 R2 U' R2 U' R2 U2 R"),
+    ("Pi2", "U2 R U R' U L' U R U L U L' U R' L"),
+    ("Pi3", "U' R' U2 R U R' U R2 U' L' U R' U' L"),
+    ("Pi4", "U R L' U R' U' L U2 R U L' U R' U' L"),
+    ("Pi5", "U F U R U' R' U R U' R2 F' R U R U' R'"),
+    ("Pi6", "U2 L U' R' U L' U R U R' U R"),
+    ("U1", "U2 R U R' U R U2 R2 U' R U' R' U2 R"),
+    ("U2", "R' U2 R F U' R' U' R U F'"),
+    ("U3", "R' U2 R U F U' R' U2 R U F'"),
+    ("U4", "U2 R2 D R' U2 R D' R' U2 R'"),
+    ("U5", "U2 R U L' U R' U2 L U' R L' U R' U2 L"),
+    ("U6", "U2 R U' L U' R' U L' U2 R U' R'"),
+    ("T1", "U R' U R U2 R' U' R U2 R' U' R U' R' U R"),
+    ("T2", "U R U2 R' U L U' R U L2 U R' U' L"),
+    ("T3", "r U R' U' r' F R F'"),
+    ("T4", "U' F U' R' U2 R U F' U' R' U2 R"),
+    ("T5", "R L' U R U' L U R' U' R'"),
+    ("T6", "U' R U' R' U2 R L U' R' U L'"),
+    ("L1", "U R U R' U R U' R' U R U' R' U R U2 R'"),
+    ("L2", "U2 F R U R' U' R U' R' U2 R U2 R' U' F'"),
+    ("L3", "U' F R' F' r U R U' r'"),
+    ("L4", "U' R' U' R U2 L' U R' U' L U' R"),
+    ("L5", "U2 R U R' U2 L U' R U L' U R'"),
+    ("L6", "U F R U' R' U' R U2 R' U' F'"),
+    ("S1", "R U R' U R U2 R'"),
+    ("S2", "U2 R U R' U L' U R U' L U2 R'"),
+    ("S3", "U2 R L U2 R' U' R U2 L2 U R' U' L"),
+    ("S4", "R L' U R' U' L U2 R U2 R'"),
+    ("S5", "U2 R' U2 R U2 L U' R' U R L'"),
+    ("S6", "R U' L' U R' U' L"),
+    ("AS1", "R U2 R' U' R U' R'"),
+    ("AS2", "U' R U2 L' U R' U' L U' R U' R'"),
+    ("AS3", "U' R' L U' R U L' U2 R' U2 R"),
+    ("AS4", "U R2 D R' U R D' R' U R' U' R U' R'"),
+    ("AS5", "U' R' U L U' R U L'"),
+    ("AS6", "U R U2 R' U2 L' U R U' R' L"),
+    ("O1", "R U' L U2 R' U R L' U' L U2 R' U L'"),
+    ("O2", "U2 R' U L' U2 R U' R' U2 R L"),
+];
+
+/// CMLL algorithms: corners solved, Roux blocks kept
+pub const CMLL_ALGORITHMS: [(&str, &str); 42] = [
+    ("H1", "R U2 R' U' R U R' U' R U' R'"),
+    ("H2", "F R U R' U' R U R' U' R U R' U' F'"),
+    ("H3", "U' R U R' U R U L' U R' U' L"),
+    ("H4", "U' F R U' R' U R U2 R' U' R U R' U' F'"),
+    ("Pi1", "F R U R' U' R U R' U' F'"),
+    ("Pi2", "U' F U F' U F U' R U' R' F'"),
+    ("Pi3", "R U2 R' U' R U R' U2 R' F R F'"),
+    ("Pi4", "F R' F' U2 R U R' U R2 U2 R'"),
+    ("Pi5", "U F U R U' R' U R U' R2 F' R U R U' R'"),
+    ("Pi6", "U2 L U' R' U L' U R U R' U R"),
+    ("U1", "U2 R U R' U R U2 R2 U' R U' R' U2 R"),
+    ("U2", "U' F R U R' U' F'"),
+    ("U3", "U2 F U R' U2 R2 U R2 U R F'"),
+    ("U4", "U2 R2 D R' U2 R D' R' U2 R'"),
+    ("U5", "U' F R2 D R' U R D' R2 U' F'"),
+    ("U6", "U2 R U' L U' R' U L' U2 R U' R'"),
+    ("T1", "U R' U R U2 R' U' R U2 R' U' R U' R' U R"),
+    ("T2", "F R' F' R F' U2 F U R U R'"),
+    ("T3", "R U R' U' R' F R F'"),
+    ("T4", "U F R' U' R2 U' R2 U2 R U' F'"),
+    ("T5", "R L' U R U' L U R' U' R'"),
+    ("T6", "U' R U' R' U2 R L U' R' U L'"),
+    ("L1", "U R U R' U R U' R' U R U' R' U R U2 R'"),
+    ("L2", "U' R U2 R2 F R F' R U2 R'"),
+    ("L3", "U' F R' F' R U R U' R'"),
+    ("L4", "U' R' U' R U2 L' U R' U' L U' R"),
+    ("L5", "U2 R U R' U2 L U' R U L' U R'"),
+    ("L6", "U F R U' R' U' R U2 R' U' F'"),
+    ("S1", "R U R' U R U2 R'"),
+    ("S2", "U2 R U R' U R' F R F' R U2 R'"),
+    ("S3", "U2 R L U2 R' U' R U2 L2 U R' U' L"),
+    ("S4", "F R' F' R U2 R U2 R'"),
+    ("S5", "L' U2 L U2 L F' L' F"),
+    ("S6", "R U' L' U R' U' L"),
+    ("AS1", "R U2 R' U' R U' R'"),
+    ("AS2", "U' R U2 L' U R' U' L U' R U' R'"),
+    ("AS3", "U' R' L U' R U L' U2 R' U2 R"),
+    ("AS4", "U R2 D R' U R D' R' U R' U' R U' R'"),
+    ("AS5", "U' R' U L U' R U L'"),
+    ("AS6", "U R U2 R' U2 L' U R U' R' L"),
+    ("O1", "R U' L U2 R' U R L' U' L U2 R' U L'"),
+    ("O2", "U2 R' U L' U2 R U' R' U2 R L"),
+];
+
+/// ZBLL algorithms: the whole last layer, edges oriented and corners not
+pub const ZBLL_ALGORITHMS: [(&str, &str); 472] = [
+    ("H1", "R F' U R F2 U2 R' U2 F' R U' F' R2 U2 F"),
+    ("H2", "U R U R' U R U' R' U R U2 R'"),
+    ("H3", "U' R' U' R U' R' U R U' R' U2 R"),
+    ("H4", "R' U2 R U R' U R U R U R' U R U2 R'"),
+    ("H5", "R' U2 R U R' U' R U R' U R"),
+    ("H6", "U2 R U2 R' U' R U R' U' R U' R'"),
+    ("H7", "R U' R2 L' U L2 U' R2 U R' L2 U' L"),
+    ("H8", "R U2 R' U' R U' R' U' R' U' R U' R' U2 R"),
+    ("H9", "U2 R U R' U R U2 R2 U' R L U2 R' U' R U2 L'"),
+    ("H10", "U R' U2 L U' R U' R' U2 L' U' L U' R U2 L'"),
+    ("H11", "U' F R' F' R2 U' R U2 R U2 R' U2 F R2 F' R'"),
+    ("H12", "U' R U' L' U R' U' R L U' L' U R' U' L"),
+    ("H13", "U F' U' F U' R U' R' U2 F R' F' R2 U' R'"),
+    ("H14", "U' R' U2 R U R' U F' R U2 F U F' U R' F R"),
+    ("H15", "U' R U2 L' U R' U R U2 L U L' U R' U2 L"),
+    ("H16", "F R U R' U' R U R' U' R U R' U' F'"),
+    ("H17", "U R U R' U R' U' R2 U' R2 U' L U' R U L'"),
+    ("H18", "U' R' U' R U' R' U' L U' R U L'"),
+    ("H19", "U2 L U' R U L' U2 R' U R U R' U' R U R'"),
+    ("H20", "R U R' U R U2 R' F R U' R' U' R U2 R' U' F'"),
+    ("H21", "U' R U R' U R U L' U R' U' L"),
+    ("H22", "R U' L' U R2 U' R L U2 R' U' R"),
+    ("H23", "U R U R' U R U2 L' U R' U' L U2 R U2 R'"),
+    ("H24", "U2 R' U L U' R2 U R' L' U2 R U R'"),
+    ("H25", "U R' U' R U' R U R2 U R2 U L' U R' U' L"),
+    ("H26", "U R' U' R U' R' U2 L U' R U L' U2 R' U2 R"),
+    ("H27", "L' U R' U' L U2 R U' R' U' R U R' U' R"),
+    ("H28", "R U R' U' L' U2 R U2 R' U L U' L' U2 L"),
+    ("H29", "U2 R' U2 R U' L U2 R' U2 R U2 R' L' U R"),
+    ("H30", "U' R U' L' U R' U L U2 R U' L' U R' L"),
+    ("H31", "R' U2 R L U2 R' U R U2 L' U R' U R"),
+    ("H32", "F R U R' U' R' F' U2 R U R' U R2 U2 R'"),
+    ("H33", "U R' L U' R U' R' U R U2 L' U R' U2 R"),
+    ("H34", "U' R L' U R' U R U' R' U2 L U' R U2 R'"),
+    ("H35", "R U2 R' L' U2 R U' R' U2 L U' R U' R'"),
+    ("H36", "U R' U L U' R U' L' U2 R' U L U' R L'"),
+    ("H37", "U2 R U2 R' U L' U2 R U2 R' U2 R L U' R'"),
+    ("H38", "U R' U2 R2 U R2 U R U L U' R U L' U R'"),
+    ("H39", "U' R U2 R2 U' R2 U' R' U' L' U R' U' L U' R"),
+    ("H40", "U' F R U' R' U R U2 R' U' R U R' U' F'"),
+    ("Pi1", "R U R2 U' R2 U' R2 U2 R2 U' R' U R U2 R'"),
+    ("Pi2", "U' R U2 R' L' U R U' R' U R U' L U2 R'"),
+    ("Pi3", "U R' U2 R L U' R' U R U' R' U L' U2 R"),
+    ("Pi4", "R' U' R U R U2 R' U' R U' R2 U2 R"),
+    ("Pi5", "U R U2 R' U' R U' R2 U' R U' R' U2 R"),
+    ("Pi6", "U' R' U2 L U' R U R' U' R U R' L' U2 R"),
+    ("Pi7", "R' U2 R2 U R2 U R2 U2 R'"),
+    ("Pi8", "R U2 R2 U' R2 U' R2 U2 R"),
+    ("Pi9", "U R U' L' U R' U' L2 U' R' U L' U' R"),
+    ("Pi10", "U R U2 L' U R' U' R U R' U' R L U2 R'"),
+    ("Pi11", "U' R' U2 R U R' U R2 U R' U R U2 R'"),
+    ("Pi12", "R U R' U' R' U2 R U R' U R2 U2 R'"),
+    ("Pi13", "U R U2 R2 U' R2 U' R' L U' R' U L' U2 R"),
+    ("Pi14", "U2 R' U R U F U R' U' R F' U' R' U' R"),
+    ("Pi15", "U2 R U R' U L' U R U L U L' U R' L"),
+    ("Pi16", "R U L U2 R2 U' R U' R' U2 R2 U2 L' U' R'"),
+    ("Pi17", "U' R U R' U L' U R U' L U' R' U R U2 R'"),
+    ("Pi18", "U2 R U L U2 R2 U2 R U R' U R2 U2 L' U' R'"),
+    ("Pi19", "U' R' U2 R2 U R2 U R L' U R U' L U2 R'"),
+    ("Pi20", "U2 R L' U R' U R U L U R' U L' U L"),
+    ("Pi21", "U2 R U' R' U2 R L' U R' U' L U2 R U R'"),
+    ("Pi22", "U' R U R2 U L' U R2 U' L U2 R' U' R' U2 R"),
+    ("Pi23", "R U' R' U2 L' U R U' R' L U2 R U R'"),
+    ("Pi24", "R' U R U F R' U R U' F' U' R' U' R"),
+    ("Pi25", "U R U2 R' L' U' R U2 R' U2 R' U L U' R2 U' R'"),
+    ("Pi26", "U' L U' R' U L' U' R2 U R' U R U2 R'"),
+    ("Pi27", "U' R' L U' R U L' U2 R' U R U' R' U2 R"),
+    ("Pi28", "R2 U2 R' L U' R' U L' U R' U' R U2 R U' R"),
+    ("Pi29", "U R U' L' U R' U' L U R' U2 R U R' U R"),
+    ("Pi30", "R U R' U R U2 R' U L U' R' U L' U' R"),
+    ("Pi31", "R' L U2 R2 U R2 U R U L' U' R U2 R'"),
+    ("Pi32", "U' R' U2 R U R' U R2 U' L' U R' U' L"),
+    ("Pi33", "R' U L U' R U L' U R' U L U' R U L'"),
+    ("Pi34", "U R' U2 R U' L' U R U R2 U R2 U2 R' L"),
+    ("Pi35", "R U2 R' U' R U R' U2 L' U R U' R' L"),
+    ("Pi36", "R U R' U' R' L' U2 R U R' U2 L U' R2 U2 R'"),
+    ("Pi37", "U' R U R' L' U2 R U2 R' U2 L U' R U2 R'"),
+    ("Pi38", "U R L' U' L U R' U2 L' U' R U' L U R'"),
+    ("Pi39", "R U' L U' R' U L' U' R' U' R2 U' R2 U2 R"),
+    ("Pi40", "U2 R2 U R2 F' R' U2 R U R' U R U F R2 U' R2"),
+    ("Pi41", "R' U2 R U' L U2 R' U' R U R' U R L'"),
+    ("Pi42", "R U2 R' U L' U2 R U R' U' R U' R' L"),
+    ("Pi43", "R' U L' U R U' L U R U R2 U R2 U2 R'"),
+    ("Pi44", "U R L' U R' U' L U2 R U L' U R' U' L"),
+    ("Pi45", "U R' U' R L U2 R' U2 R U2 L' U R' U2 R"),
+    ("Pi46", "R' U' R U' L U2 R' U' R U2 R' L' U2 R"),
+    ("Pi47", "R U R' U L' U2 R U R' U2 R L U2 R'"),
+    ("Pi48", "U F U R U' R' U R U2 R' U' R U R' F'"),
+    ("Pi49", "R' U2 R U R' U' R U2 L U' R' U R L'"),
+    ("Pi50", "U' R U2 R' U L U' R' U' R2 U' R2 U2 R L'"),
+    ("Pi51", "U' R U2 R2 U L' U2 R U' R' U2 R L U R U' R'"),
+    ("Pi52", "R L' U2 R2 U' R2 U' R' U' L U R' U2 R"),
+    ("Pi53", "R U' L' U R' U' L U L U' R' U L' U' R"),
+    ("Pi54", "U R U2 R' U' R U' R2 U L U' R U L'"),
+    ("Pi55", "U' R' U L U' R U L' U' R U2 R' U' R U' R'"),
+    ("Pi56", "U2 L2 U2 R' L U L U' R U' L U L' U2 L' U L'"),
+    ("Pi57", "R' U' R U' R' U2 R U R' U L U' R U L'"),
+    ("Pi58", "U F U R U' R' U R U' R2 F' R U R U' R'"),
+    ("Pi59", "U R L' U R' U' L U2 R U' R' U R U2 R'"),
+    ("Pi60", "U L' U R U' L U R2 U' R U' R' U2 R"),
+    ("Pi61", "U2 R' U R U' R' U R U R' U2 L' U R U' L"),
+    ("Pi62", "U2 R U' R' U R U' R' U' R U2 L U' R' U L'"),
+    ("Pi63", "U2 R U R' U' R' U' R U' R' U2 R2 L U' R' U L'"),
+    ("Pi64", "U R U2 R' U2 L' U R U' L U2 R' U' R U' R'"),
+    ("Pi65", "U2 L' U R U' L U' R2 U' R2 U' R' U R' U R"),
+    ("Pi66", "U2 R U' R' U2 R L U' R2 U L' U' R"),
+    ("Pi67", "U2 L' U R U' L U' R' U' R U' R'"),
+    ("Pi68", "U2 R U2 R' U' R U L' U2 L U2 R' U' L' U L"),
+    ("Pi69", "U2 L U' R' U L' U R U R' U R"),
+    ("Pi70", "U2 L U' R' U L' U R2 U R2 U R U' R U' R'"),
+    ("Pi71", "U' R' U2 R U2 L U' R' U L' U2 R U R' U R"),
+    ("Pi72", "U2 R' U R U2 R' L' U R2 U' L U R'"),
+    ("U1", "U R L U2 R' U' R U' R' L' U2 L U L'"),
+    ("U2", "U R' U2 R U R' U R U R' U' R U' R' U2 R"),
+    ("U3", "U R L' U R' U' L U' L U' R U L' U R'"),
+    ("U4", "R' U' R U' R' U2 R2 U R' U R U2 R'"),
+    ("U5", "R L U' R' U L' U L' U R U' L U' R'"),
+    ("U6", "U' R L' U' L U R' U R' U L' U' R U' L"),
+    ("U7", "U2 R' L' U R U' L U' L U' R' U L' U R"),
+    ("U8", "U2 R U R' U R U2 R' U R U2 R' U' R U' R'"),
+    ("U9", "U' R L' U2 R2 U L U' R2 U2 R' U' L' U L"),
+    ("U10", "U R U2 R' U' R U' R' U' R U R' U R U2 R'"),
+    ("U11", "R' U' R U' R' U2 R U' R' U2 R U R' U R"),
+    ("U12", "U2 R U R' U R U2 R2 U' R U' R' U2 R"),
+    ("U13", "U2 R L' U R' U R U R' U2 R L U' R'"),
+    ("U14", "U2 R U2 R' U' L' U2 R U R' U2 L"),
+    ("U15", "U R' U' R U' R' L U' R U R' L' U2 R"),
+    ("U16", "U2 R' U L U' R2 U R' U L' U' R U2 L U2 R' L'"),
+    ("U17", "U2 R U' L' U R' U' R' L U L U' R U L'"),
+    ("U18", "R U R' L' U2 R U' R' U' R U' R' L"),
+    ("U19", "R U' L' U R2 U' R U' L U R' U2 L' U2 R L"),
+    ("U20", "U R U R' U R L' U R' U' R L U2 R'"),
+    ("U21", "U2 L' U2 R U' R' U2 L U R U2 R'"),
+    ("U22", "F U2 R U R' U2 R U R U R2 U R2 U2 R2 F'"),
+    ("U23", "U' F U' R' U R U F' R' U2 R"),
+    ("U24", "R' U2 R F U' R' U' R U F'"),
+    ("U25", "U2 R U2 R' U2 L' U2 R U2 R' U2 L"),
+    ("U26", "U2 R' L' U R U L U' R' U2 L' U L U R"),
+    ("U27", "U' R' U2 R U R' U' R2 U' L' U R2 U' L U' R"),
+    ("U28", "U' F U2 R U2 R' U R U R' U F' U2 F U' F'"),
+    ("U29", "R L U' R' U' L' U R U2 L U' L' U' R'"),
+    ("U30", "U' L U2 R' U' R U2 L2 U R' U' L U' R"),
+    ("U31", "R' U' R F U' R' U' R U F' U R' U R"),
+    ("U32", "U R U R' U R U' R U' L' U R' U' R' L"),
+    ("U33", "R' U2 R U F U' R' U2 R U F'"),
+    ("U34", "U2 R U R' U' L' U2 R U R' U2 R L U' R'"),
+    ("U35", "U' R U2 R' U' R U R2 U L U' R2 U L' U R'"),
+    ("U36", "F R U' R' U R U R' U R U' R' F'"),
+    ("U37", "U2 R2 D R' U2 R D' R' U2 R'"),
+    ("U38", "R' U L' U R U' L U2 R' U R"),
+    ("U39", "U2 R U L' U' L U R' U' L' U' R U R' U2 L"),
+    ("U40", "U R U' L U' R' U' L' U2 R U L U' L' U R'"),
+    ("U41", "R' U' R U' R' U2 R2 U' L' U R' U' L"),
+    ("U42", "U2 R U' L' U R' U R U2 R' U' R U' R' L"),
+    ("U43", "U' R' U' R U' L U' R' U2 L' U2 R U' L U2 L'"),
+    ("U44", "U2 R U L U L' U2 R U' L U R' U R' L'"),
+    ("U45", "U R U' R' U2 R' L U L' U' R2 U2 L U2 R' L'"),
+    ("U46", "R U2 R' L' U R U R2 L U L' U' R2 U2 R' L"),
+    ("U47", "U R' U' L U' R U L' U R' U' R U' R' U R"),
+    ("U48", "U R' U L' U' L U R U2 L' U2 L U R' U2 R"),
+    ("U49", "U R U2 R' U L' U' L U R U' L' U2 R' U' L"),
+    ("U50", "U R' U L' U' L U' R U L' U2 R' U2 R L"),
+    ("U51", "U2 R U L' U R' U2 L U' R L' U R' U2 L"),
+    ("U52", "U R U' L U L' U R' U' L U2 R U2 R' L'"),
+    ("U53", "U2 R U R' U R' F2 U' R2 U' R2 U F2 U R"),
+    ("U54", "U2 R L' U' L2 U2 R' U R U2 R' L2 U2 L"),
+    ("U55", "U' R' U2 R2 L U2 L' U' L U2 R2 U R L'"),
+    ("U56", "R U' R' U L' U2 L U2 R U' L' U2 R' U' L"),
+    ("U57", "U2 R L' U R2 U2 L U' L' U2 R2 L U2 R'"),
+    ("U58", "U' R' U' F2 U' R2 U R2 U F2 R U' R U' R'"),
+    ("U59", "U R U' L' U2 R' U' L U R U' R' U L' U2 L"),
+    ("U60", "U' R U2 R2 L' U2 L U L' U2 R2 U' R' L"),
+    ("U61", "U2 R U' L U' R' U L' U2 R U' R'"),
+    ("U62", "R' U L' U L U' R U2 L' U R' U R L"),
+    ("U63", "U R U R' U' L' U2 R U L U R' U L' U L"),
+    ("U64", "R' U L U' R U R L' U R' U R U2 R'"),
+    ("U65", "R2 D' R U2 R' D R U2 R"),
+    ("U66", "R U R' L' U2 R U' R' L2 U' R U L2 U2 R' L"),
+    ("U67", "R' U' L U L' U' R U L U R' U' R U2 L'"),
+    ("U68", "R U' R2 U' R2 F' R U R' U' R' F U2 R'"),
+    ("U69", "U' R U R' U L' U R U2 L U2 R' U L' U2 L"),
+    ("U70", "U R' U R U2 R L' U' L U R2 U2 L' U2 R L"),
+    ("U71", "U2 R U R' U R U2 R2 U L U' R U L'"),
+    ("U72", "U R U L' U R' U' L U' R U R' U R U' R'"),
+    ("T1", "U' R U' R' U2 R L U L' U L U2 R' L'"),
+    ("T2", "U' R U' R' U2 R U R' U2 R U R' U R U' R'"),
+    ("T3", "U R' U R U2 R' U' R U2 R' U' R U' R' U R"),
+    ("T4", "U' R' U2 R U R' U R2 U2 R' U' R U' R'"),
+    ("T5", "U2 R U' L U' R' U L' U L' U R U' R' L"),
+    ("T6", "U' R' U' L U' R U L' U L' U R' U' R L"),
+    ("T7", "U' R' U2 R U R' U R U' R' U' R U' R' U2 R"),
+    ("T8", "U R U2 R' U' R U' R' U R U R' U R U2 R'"),
+    ("T9", "R U R' U' L' U2 L2 U' R U L2 U2 R' L"),
+    ("T10", "U R U L' U R' U' L U' L U' R U R' L'"),
+    ("T11", "U2 R' U L' U R U' L U' L U' R' U R L'"),
+    ("T12", "U R U2 R' U' R U' R2 U2 R U R' U R"),
+    ("T13", "U R U2 L' U R' U L U L' U L U2 R U2 R'"),
+    ("T14", "U2 R' U2 R L U' R' U L' U R U' R' U2 R"),
+    ("T15", "U' R' U' R U' R' U R L U2 R' U' R U2 L'"),
+    ("T16", "U F U' R2 U R' U R U2 R2 U' R U2 R' F'"),
+    ("T17", "U R U2 R' U L U' R U L2 U R' U' L"),
+    ("T18", "U F R U2 R' U R2 U2 R' U' R U' R2 U F'"),
+    ("T19", "U' R U2 R' U2 L' U' L U' L' U' R U' L U2 R'"),
+    ("T20", "U R U R' U R U' R' L' U2 R U R' U2 L"),
+    ("T21", "U2 R U2 R' L' U R U' L U' R' U R U2 R'"),
+    ("T22", "U2 F' U' F2 R' F' R2 U R' F R' F' R U' R U' R'"),
+    ("T23", "R U2 R' U' R U L' U R' U' R L U2 R'"),
+    ("T24", "R' U2 R U R' U' L U' R U R' L' U2 R"),
+    ("T25", "r U R' U' r' F R F'"),
+    ("T26", "U' L' U2 R U2 R' U2 L U R U' R'"),
+    ("T27", "U2 R' L' U2 R U R' U' R U' L U2 R' U R"),
+    ("T28", "U2 R' U L' U' L U' R U' R' U2 R U' L' U L"),
+    ("T29", "U' R' U R U' R' L U' R U L' U R' U2 R"),
+    ("T30", "U' R L' U R' U' L U R U R' U' R U' R'"),
+    ("T31", "U' F R U R' U' R' F' U2 R U R U' R2 U2 R"),
+    ("T32", "R U2 R' U2 R' F R U R U' R' F'"),
+    ("T33", "R' U2 R U2 R' U R U' L U' R' U L' U R"),
+    ("T34", "U2 R U R' U R U R' U2 R L U' R' U L'"),
+    ("T35", "U2 R L U2 R' U' L' U2 R U R' U2 L U' L'"),
+    ("T36", "R U R' U' R U' R' L U' R U R' L'"),
+    ("T37", "L' U2 R U2 R' U2 L U2 R U2 R'"),
+    ("T38", "U' R' U L' U R2 U' L U R2 U R U' R' U2 R"),
+    ("T39", "U R' U' L' U' L U2 R U L' U' R' U' R L"),
+    ("T40", "U' F U F' U2 F U' R U' R' U' R U2 R' U2 F'"),
+    ("T41", "U2 R' U' R U' F U' R' U R U F' R' U R"),
+    ("T42", "U2 R U R' L' U2 R U' R' U2 L U R U' R'"),
+    ("T43", "U' R U L U L' U2 R' U' L U R U R' L'"),
+    ("T44", "U R U' L U' R2 U L' U' R2 U' R' U R U2 R'"),
+    ("T45", "U' F U' R' U2 R U F' U' R' U2 R"),
+    ("T46", "U' R L' U' L' U R' U' L U' L U L' U L"),
+    ("T47", "U' R L' U R U' L U R' U R' U' R U' R'"),
+    ("T48", "U' F R U R' U' R U' R' U' R U R' F'"),
+    ("T49", "U2 R' L' U2 R U L U2 R' U' R U2 L' U L"),
+    ("T50", "U2 R' U' R U' R' U' R U2 R' L' U R U' L"),
+    ("T51", "R' U' R U R' U R L' U R' U' R L"),
+    ("T52", "R L' U' L U R2 U2 R U L' U2 R' U2 L U R"),
+    ("T53", "R U2 R' U2 R U' R' U L' U R U' L U' R'"),
+    ("T54", "R' U2 R U2 R B' R' U' R' U R B"),
+    ("T55", "U R U' R' U R L' U R' U' L U' R U2 R'"),
+    ("T56", "U R' U2 R F U' R' U R U F' R' U R"),
+    ("T57", "U R' L U' R U L' U' R' U' R U R' U R"),
+    ("T58", "R L' U R U' L U R' U' R'"),
+    ("T59", "U2 R L U2 R' U' R U R' U L' U2 R U' R'"),
+    ("T60", "U2 F U R U2 R' U R U R' F'"),
+    ("T61", "U' R U' R' U2 R L U' R' U L'"),
+    ("T62", "U R' U R U2 R' L' U R U' L"),
+    ("T63", "U R U R' U' R' U L' U2 R U' R' U2 R2 L U' R'"),
+    ("T64", "R U2 R' U' R2 U' L' U R' U' R' L"),
+    ("T65", "U R2 L' U R' U' R' L U2 R' U' R2 U R' U' R2"),
+    ("T66", "U R U R' U R U' R' U' L' U2 R U2 R' U2 L"),
+    ("T67", "U2 L' U2 R U2 R' U2 L U R U R' U' R U' R'"),
+    ("T68", "U2 R U2 L' U R' U R U' L U L' U R' U2 L"),
+    ("T69", "U F U' R' U2 R U F' R' U' R U R' U R"),
+    ("T70", "U' R2 U2 R' U' R' U R2 U2 R2 U R L U' R' U L'"),
+    ("T71", "U R L' U' L' U R' U' L2 U' L' U2 L"),
+    ("T72", "U' R' U' R U' R' U R F U' R' U2 R U F'"),
+    ("L1", "U2 R U' R' L' U2 L U L' U R L U2 R'"),
+    ("L2", "U R2 U R' U R' U' R U' R' U' R U R U' R2"),
+    ("L3", "U' R U2 R' U' R U' R' U R' U2 R U R' U R"),
+    ("L4", "U' R U R' U R U2 R' L U2 L' U' L U' L'"),
+    ("L5", "U2 R' U2 R U R' U R U' R U2 R' U' R U' R'"),
+    ("L6", "U2 R' U' R U' R' U2 R U' R U R' U R U2 R'"),
+    ("L7", "U2 R U' R2 L U L' U' R2 U R' U2 L U L'"),
+    ("L8", "U R U' R' U2 L U' L2 U R U' R' L2 U L'"),
+    ("L9", "U R U R' U R U' R' U R U' R' U R U2 R'"),
+    ("L10", "U2 R2 U R' U' R' U R U R' U R U' R U' R2"),
+    ("L11", "U' R U R' U R U2 R' U R' U' R U' R' U2 R"),
+    ("L12", "U' R U2 R' U' R U' R' U2 R U R' U R U2 R'"),
+    ("L13", "U R' U' R U' R' L U' L' U2 R U L U2 L'"),
+    ("L14", "U' R U2 R2 L' U2 R U R U' R2 U2 R2 L U2 R'"),
+    ("L15", "U2 R U R' U' R2 U' L' U R' U' R' L U' R U R'"),
+    ("L16", "R U R' U R L' U L U2 R' U' L' U2 L"),
+    ("L17", "U R L' U2 R2 U' R2 U' R' U' L2 U2 R' U R U2 L'"),
+    ("L18", "U2 F R U R' U' R U' R' U2 R U2 R' U' F'"),
+    ("L19", "R U2 R U2 R' L' U R' U' R2 L U2 R' U2 R'"),
+    ("L20", "R U R' U' L U2 L' U2 R U L U2 R' U L'"),
+    ("L21", "R U R' L' U2 R U R' U2 L"),
+    ("L22", "R' L U2 R2 U R2 U R U L2 U2 R U' R' U2 L"),
+    ("L23", "U2 R U2 R2 L' U2 R2 U R' U' R' U2 R2 L U2 R'"),
+    ("L24", "U' R U2 R' L' U R U' L U2 R'"),
+    ("L25", "R' U' L U' R U L' U R' U' R U2 R' U2 R"),
+    ("L26", "U2 R U R' U R U' R' U' L' U R U' R' L"),
+    ("L27", "U' R' U' R U2 L' U R' U R U' R' U2 R L"),
+    ("L28", "U' R' U' R U L' U2 L U L' U R' U R U' L"),
+    ("L29", "U' F R U R' U' R' F' R U2 R U2 R'"),
+    ("L30", "R U R' U' L' U2 R U2 R' U2 L"),
+    ("L31", "U' R U L U2 R' U2 L' U R U2 R2 U L U' R L'"),
+    ("L32", "U R' U2 R U' L U' R' U R L' U R' U' R"),
+    ("L33", "U' F R' F' r U R U' r'"),
+    ("L34", "U2 L U' R U R' L' U2 R U' R' U' R U' R'"),
+    ("L35", "R U R' U2 L U' L' U2 R U L U2 R' L'"),
+    ("L36", "U2 R L U' R' U R L' U R' U R U' R'"),
+    ("L37", "U R L U2 L' U2 R2 U L U' R L' U2 R U R'"),
+    ("L38", "U' R' U' R U2 L' U R' U' L U' R"),
+    ("L39", "U R L' U2 R2 U L U' R2 L' U' R' U' R L U2 R'"),
+    ("L40", "U2 R U R' U L' U R U L U2 R' U' L' U L"),
+    ("L41", "L' U R U' L U R2 U2 R U R' U R"),
+    ("L42", "R L' U R' U R U2 R' U' R U' L U R'"),
+    ("L43", "U' R' U' R U R' U R U' L U' R' U L' U R"),
+    ("L44", "R L U R' U L' U2 R U' L U L' U R'"),
+    ("L45", "U' R' U L' U R2 U' L U R2 U2 R"),
+    ("L46", "U' R' U2 L U' L' U R U L U' R' U R U' L'"),
+    ("L47", "U2 R U2 R' U L' U2 R U2 L U R' U L' U L"),
+    ("L48", "U2 R' U2 R U' L' U2 L U2 R' U' L' U L U' R"),
+    ("L49", "U' R' L' U R U' L U2 R' U L' U R U' L"),
+    ("L50", "U' R U2 R' U' R U' R' L U' R' U L' U' R"),
+    ("L51", "U' R U2 R' U L U2 L' U2 R U L U' L' U R'"),
+    ("L52", "U L U' R' U L' U' R2 U2 R' U' R U' R'"),
+    ("L53", "R' L' U2 L U2 R2 U' L' U R' L U2 R' U' R"),
+    ("L54", "U2 R U R' U' R U' R' U L' U R U' L U' R'"),
+    ("L55", "U' R' U2 R U' L U2 R' U2 L' U' R U' L U' L'"),
+    ("L56", "U' R' U L' U' L U R U2 L' U' R' U' L U' R"),
+    ("L57", "U2 R L' U2 L2 U' R' U R L2 U R' U2 R L U' R'"),
+    ("L58", "U2 R U' L U' R2 U L' U' R2 U2 R'"),
+    ("L59", "U2 R U R' U2 L U' R U L' U R'"),
+    ("L60", "U2 R U2 L' U L U' R' U' L' U R U' R' U L"),
+    ("L61", "U2 R U R' U2 L U' R U' R' U R U2 R' L'"),
+    ("L62", "U R' U' R U2 L' U L U2 R' U' L' U2 R L"),
+    ("L63", "U' R' U' R F U' R' U' R U F' R' U2 R"),
+    ("L64", "U' L' U R' U' R L U2 R' U R U R' U R"),
+    ("L65", "F' r U R' U' r' F R"),
+    ("L66", "U' R' U' R U' R' U R U L U' R' U R L'"),
+    ("L67", "U F R U' R' U' R U2 R' U' F'"),
+    ("L68", "U' R' L' U R U' R' L U' R U' R' U R"),
+    ("L69", "R U2 R' U L' U R U' R' L U' R U R'"),
+    ("L70", "U R U L' U R' U' L U' R U R' U2 R U2 R'"),
+    ("L71", "U' R U' L U2 R' U2 L' U' R U2 R2 U L U' R L'"),
+    ("L72", "R L' U' L U2 R' U2 L' U L U R U' R'"),
+    ("S1", "U R' U' R U' L U2 R' U2 L' U' L U' R U2 L'"),
+    ("S2", "U' R U R' U' R' U2 R U R' U R U' R U' R'"),
+    ("S3", "U' R2 U' R2 U' R U2 R U' R' U' R U R2"),
+    ("S4", "U' R' U' R U R U R' U' R' U R U R U' R'"),
+    ("S5", "U' R2 U R' U' R' U' R U2 R' U' R2 U' R2"),
+    ("S6", "R U R' U R U2 R'"),
+    ("S7", "R2 U' R2 U' R U R2 U' R2 U R' U R2"),
+    ("S8", "U' R' U2 R U R' U R"),
+    ("S9", "U2 R' U' R U' R U R2 U R2 U2 R'"),
+    ("S10", "R' U2 R2 U2 R2 U' R2 U' R2 U R"),
+    ("S11", "R U R2 U' R2 U' R2 U2 R2 U2 R'"),
+    ("S12", "U' R2 U' R' U' R2 U R U R2 U R' U R"),
+    ("S13", "U' R' L' U2 R U L U2 R U' L' U R2 U' R U' L"),
+    ("S14", "U R U2 R' L' U2 R U2 R' U2 L U' R U' R'"),
+    ("S15", "U' R U2 L' U R' U' R L U' R' U R U2 R'"),
+    ("S16", "U' R2 U R' U R' L' U R2 L U2 R' U' L' U2 R' L"),
+    ("S17", "R U R' U' L' U R U' L U' L' U R' U' L"),
+    ("S18", "U' R U' L U' L2 U R' U' R L2 U L' U R'"),
+    ("S19", "U2 R2 U R' U R' L' U R U' L U' R U' R2"),
+    ("S20", "U R' U2 R U R' U' R L U' R' U L' U2 R"),
+    ("S21", "R' U2 L U' R U L' U R' U R"),
+    ("S22", "R' U2 R U R' U' L U' L' U2 R U L U2 L'"),
+    ("S23", "U' R' U' R U' L U2 R' U2 R U2 R' L' U2 R"),
+    ("S24", "U2 R U R' U L' U R U' L U2 R'"),
+    ("S25", "U R U2 R' L' U2 R U R' U2 R L U2 R'"),
+    ("S26", "U' R U2 L' U R' U' R L U L' U R' U' L"),
+    ("S27", "U' R' U2 R U' L U' R2 U L' U' R2 U' R' U R"),
+    ("S28", "U F R2 F' U2 R' U2 R2 U2 R U2 R F R F' R"),
+    ("S29", "U2 R L U2 R' U' R U2 L2 U R' U' L"),
+    ("S30", "U2 L U' R' U L2 U2 R U' R' U2 R L"),
+    ("S31", "U2 R U2 R' U L' U2 R U R' U2 L U R U' R'"),
+    ("S32", "U' R U' L' U R' U R L U' L' U R' U2 L"),
+    ("S33", "U R L U R2 U L' U' R2 U L U2 R' L'"),
+    ("S34", "U R' U' R F U' R' U R U F' U2 R' U2 R"),
+    ("S35", "R U R' U' R2 U' L' U R2 U' L U' R U2 R'"),
+    ("S36", "U' R' U' R U' L U2 R' U' L' U2 R U' L U' L'"),
+    ("S37", "U' R' U' F U' R2 U R2 U F' R U' R U' R'"),
+    ("S38", "U' R L U' R' U R L' U R' U2 R U2 R'"),
+    ("S39", "U R' U R U' R' L U' R U L2 U R' U' L U' R"),
+    ("S40", "U' R' U' L U' R2 U R L' U' R2 U2 R' U' R2"),
+    ("S41", "R' L' U2 R U R' U2 L U R U R' U R"),
+    ("S42", "R L' U R' U' L U2 R U2 R'"),
+    ("S43", "R' U2 R U' R' U' L' U R U' L U' R' U R"),
+    ("S44", "U R' U R U2 L U' R' U L2 U R U' L"),
+    ("S45", "U R U' L' U R' U2 L U R U' L' U R' L"),
+    ("S46", "U' R U R U' L' U R2 U' L U2 R U2 R'"),
+    ("S47", "U F R2 U' R2 U' R U2 R' U' R U' R U R2 F'"),
+    ("S48", "R U2 R' U L' U2 R U' R' L U L' U L"),
+    ("S49", "U2 L U' R U L2 U R' U' L U2 R U R'"),
+    ("S50", "U2 R' U2 R U2 L U' R' U R L'"),
+    ("S51", "U2 R U R' U R L' U' L U2 R' U L' U2 L"),
+    ("S52", "R U R' U R U L U2 R' U R U2 R' L'"),
+    ("S53", "R L' U R' U' L U R U2 L' U R' U' L"),
+    ("S54", "U2 R U R' U R2 D R' U2 R D' R2"),
+    ("S55", "R U R' U' L U' R U L' U' R' U' R U2 R'"),
+    ("S56", "U' R2 U' R' U2 R2 U' R L' U R2 U' L U' R'"),
+    ("S57", "F R' U' R2 U' R2 U2 R2 U' R' F'"),
+    ("S58", "U' R' U' R U2 R D R' U R U D' R2 U R2 U2 R'"),
+    ("S59", "U2 R' U2 R U2 R' U R L' U R' U' R L"),
+    ("S60", "U2 R U' L U' R' U L2 U R U' R' L U' R U R'"),
+    ("S61", "U R' U' R U F U' R' U' R U F' R' U R"),
+    ("S62", "R' U2 L U' R U' R' U R U2 R' L' U2 R"),
+    ("S63", "U2 R' F' R U' F U' F' R U2 R' F U' R U' R'"),
+    ("S64", "U' R' U2 R U L U L' U' R' U L U' L' U R"),
+    ("S65", "R U' L' U R' U' L"),
+    ("S66", "U2 R U' L' U R' U R U' R' U2 L U R U2 R'"),
+    ("S67", "U R L U R2 U L' U' R2 L U2 R' U L'"),
+    ("S68", "U2 R' U' R U' R' U' L U' R U' L' U' L U' L'"),
+    ("S69", "U2 R' U2 R U L U2 R' U' R U R' U L' U' R"),
+    ("S70", "U2 R L U2 R' U' R' U' R2 U2 L' U R2 U2 R2 U2 R'"),
+    ("S71", "U' F U2 R' U' R F' R' U R F U2 F'"),
+    ("S72", "U2 R' U L' U2 R L2 U' R' U L2 U R L"),
+    ("AS1", "U2 R U R' U L' U2 R U2 L U L' U R' U2 L"),
+    ("AS2", "U' R' U' R2 U R2 U R2 U2 R2 U2 R"),
+    ("AS3", "R U2 R' U' R U' R'"),
+    ("AS4", "R U R' U' R' U' R U R U' R' U' R' U R"),
+    ("AS5", "U' R U2 R2 U2 R2 U R2 U R2 U' R'"),
+    ("AS6", "R2 U' R' U R U R' U2 R' U R2 U R2"),
+    ("AS7", "U' R' U' R U' R' U2 R"),
+    ("AS8", "U2 L2 U L2 U L' U2 L' U L U L' U' L2"),
+    ("AS9", "U R U R' U R' U' R2 U' R2 U2 R"),
+    ("AS10", "U R U R' U R' U' R U' R' U2 R U R U' R'"),
+    ("AS11", "R2 U R2 U R U2 R' U R U R U' R2"),
+    ("AS12", "U' R' U' R U' R2 U' R' U' R2 U R U R2"),
+    ("AS13", "R L U2 R' U' L' U2 R' U L U' R2 U R' U L'"),
+    ("AS14", "R U R' U L' U2 R U2 R' U2 R L U2 R'"),
+    ("AS15", "U2 R U2 R' U' R U R' L' U R U' L U2 R'"),
+    ("AS16", "U2 F R U R' U' R U R' F R' F' R U' F'"),
+    ("AS17", "U' R U2 R' U' R U L' U L U2 R' U' L' U2 L"),
+    ("AS18", "U' R2 U R' U L' U R' U' R L U' R U' R2"),
+    ("AS19", "U2 R U' L U' R' L2 U R U' L2 U L' U R'"),
+    ("AS20", "R' U2 L U' R U R' L' U R U' R' U2 R"),
+    ("AS21", "U' R U2 L' U R' U' L U' R U' R'"),
+    ("AS22", "R' U L U' R U R' U L' U' R U L U' L'"),
+    ("AS23", "U2 R' U2 R L U2 R' U2 R U2 L' U R' U R"),
+    ("AS24", "U R' U' R U' L U' R' U L' U2 R"),
+    ("AS25", "R U2 R2 U' R2 U' D R' U' R D' R' U2 R' U R"),
+    ("AS26", "U R2 D R' U2 R D' R2 U' R U' R'"),
+    ("AS27", "U2 R U' R' U2 L' U R U' L2 U' R' U L'"),
+    ("AS28", "U2 L U R' U L2 U' R L' U L2 U2 L U L2"),
+    ("AS29", "F R U R2 U2 R2 U R2 U R F'"),
+    ("AS30", "U' R U2 R' U R U L U' R' U L' U R U' R'"),
+    ("AS31", "U' R' L U' R U L' U2 R' U2 R"),
+    ("AS32", "U2 R U' R' U R L' U R' U' L2 U' R U L' U R'"),
+    ("AS33", "U2 R' U L U' R U2 L' U' R' U L U' R L'"),
+    ("AS34", "U' R L U2 R' U' R U2 L' U' R' U' R U' R'"),
+    ("AS35", "R' L' U R U' R' L U' R U2 R' U2 R"),
+    ("AS36", "U R L' U' L U2 R' U L' U2 L U' R U' R'"),
+    ("AS37", "R U2 R' L' U2 R U' R' U2 R L U2 R'"),
+    ("AS38", "U' R' U' R U R2 U L U' R2 U L' U R' U2 R"),
+    ("AS39", "R' U L U' R U' R' L' U L U' R U2 L'"),
+    ("AS40", "U2 F' U' F U F U R2 U' F' R2 F R2 F' R2 U R2"),
+    ("AS41", "U2 R U R' U' L' U2 R U' R' U2 L U' R U2 R'"),
+    ("AS42", "U R' U2 R U2 F U' R' U' R U F' R' U R"),
+    ("AS43", "U R2 D R' U R D' R' U R' U' R U' R'"),
+    ("AS44", "R U2 R' U L' U R2 U' L U R2 U R U' R'"),
+    ("AS45", "U R L U2 R' U' L2 U R U' L2 U' R' L'"),
+    ("AS46", "U R' L' U2 R U R' U2 L2 U' R U L'"),
+    ("AS47", "R' U2 L U' R U R' L' U' L U' R U L'"),
+    ("AS48", "R U R' U L' U2 R U L U2 R' U L' U L"),
+    ("AS49", "U R' U2 R L U2 R' U' R U R' U L' U2 R"),
+    ("AS50", "U R' U' L U L' U' R U L U' L' U' R' U2 R"),
+    ("AS51", "U R U R' U R U L' U R' U L U L' U L"),
+    ("AS52", "U F U2 F' R' U' R F R' U R U2 F'"),
+    ("AS53", "U F R' U R U2 R2 U R2 U F' U R U R' U' R'"),
+    ("AS54", "U2 R' L' U' R2 U' L U R2 L' U2 R U' L"),
+    ("AS55", "U R' U L U' R U' R' U R U2 L' U' R' U2 R"),
+    ("AS56", "U2 L2 U' L U' L2 U L' U' R U' L' U R' L U2 L2"),
+    ("AS57", "U R U2 R' U' L' U2 R U R' U' R U' L U R'"),
+    ("AS58", "U' R' U L U' R U L'"),
+    ("AS59", "U' R' U' R F U' R' U R U F' U' R' U R"),
+    ("AS60", "U R U' L U2 R' L2 U R U' L2 U' R' L'"),
+    ("AS61", "U L' U R' U' L2 U' R U L' U2 R' U' R"),
+    ("AS62", "U R U2 R' U2 R U' R' L U' R U R' L'"),
+    ("AS63", "U2 L2 U L U2 L2 U R L' U' L2 U R' U L"),
+    ("AS64", "U' R' U' R U' R' U' L' U2 R U' R' U2 R L"),
+    ("AS65", "U' F R U R' F R' F' U2 R2 U R2 U R F'"),
+    ("AS66", "U' R' U' R U L' U R' U' L U R U R' U2 R"),
+    ("AS67", "U F R2 U' R' U R' U R U2 R' U R2 U R2 F'"),
+    ("AS68", "U R' U' R U' R' L U L' U2 R U' L U2 L'"),
+    ("AS69", "U2 R U2 R' U2 L' U R2 U' L U R' U' R'"),
+    ("AS70", "U R L' U' L U R' U' L' U2 R U' L U R'"),
+    ("AS71", "U R U2 R' U2 L' U R U' R' L"),
+    ("AS72", "U R' U L' U R U' L2 U' R' U R L' U R' U' R"),
+];
+
+/// OLLCP algorithms: last layer oriented and corners permuted
+pub const OLLCP_ALGORITHMS: [(&str, &str); 329] = [
+    ("1a", "R U' R' U F2 U2 R' F' R U2 F2 U' R U R'"),
+    ("1b", "F U R U2 R' F R' F' R2 U R' F' U' F U2 F'"),
+    ("1c", "U F' U2 F2 R' F' R U2 F R' F' R"),
+    ("1d", "U' F U F R' F' R2 U R' U' F' U2 F U F'"),
+    ("2a", "F R U R' U' F' U2 F U R U' R' F'"),
+    ("2b", "U2 F U F R' F' R2 U2 R' U2 R U R' F'"),
+    ("2c", "U2 F R' F' R2 U R' F' U2 F U' R U' R'"),
+    ("2d", "U2 F R' F' R U2 F R' F' R2 U2 R'"),
+    ("2e", "U2 F U R U2 R' F U R U' R2 F' R U F'"),
+    ("2f", "U2 F R' F' R U' F' U' F2 R' F' R U' R U' R'"),
+    ("3a", "U2 F U R U' R' F' U F R U R' U' F'"),
+    ("3b", "U2 F R U R2 U' F' U R U' F' U' F U' R U' R'"),
+    ("3c", "F R' F' R U R U' R' F' U' F U F R' F' R"),
+    ("3d", "U F U R2 U2 R F' R' U2 R F R2 U' R' F'"),
+    ("3e", "U F U2 F R' F' R U R U R' U F'"),
+    ("3f", "U' R' U F' U F U F R' F' R U2 R"),
+    ("4a", "U2 F U R U' R' F' U' F R U R' U' F'"),
+    ("4b", "F U F R U R2 U' F' R U' R' U2 R U2 F'"),
+    ("4c", "U2 F U R U2 R' F R U R2 U' F' U R U F'"),
+    ("4d", "R' F2 R2 U2 R' F' R U2 R2 F2 R"),
+    ("4e", "F U R' U' F' U R2 U R' F' U' F U' R U2 R'"),
+    ("4f", "U2 F U' R U' R' F' U' F R U' R' U' F'"),
+    ("5a", "U' F' U' F2 R' F' R2 U' R'"),
+    ("5b", "U2 R U R' U R U2 R' U2 F R U R' U' F'"),
+    ("5c", "U' R U' R' U' F' U R U R' U' R' F R"),
+    ("5d", "F U R' U' F' U R F' U2 F U R U R'"),
+    ("5e", "U R' F' R F' U2 F U R U R' U F"),
+    ("5f", "U2 R U R' U' R U R' F' U' F U2 R U' R'"),
+    ("6a", "U' F U F R' F' R2 U' R' F'"),
+    ("6b", "U' R U2 R' U' R U' R' U' F U R U' R' F'"),
+    ("6c", "R' U' R F R' U R2 U' R' F'"),
+    ("6d", "U R' U' R U' R' U2 R2 U R' F' U F R U' R'"),
+    ("6e", "U' F R2 U R' U' R' U F R F' U' R' F'"),
+    ("6f", "U R U R' F' U' R' U' R F R' U R2 U2 R'"),
+    ("7a", "U F U R U2 R' U2 R U R' F'"),
+    ("7b", "U2 F' U2 F U' R U R' U F' U F"),
+    ("7c", "U2 R' U2 R U R' U R2 U R' F' U' F R U' R'"),
+    ("7d", "F R' F' R U2 R U2 R'"),
+    ("7e", "L' U2 L U2 L F' L' F"),
+    ("7f", "R U' R' U' F' U2 F U2 R U2 R'"),
+    ("8a", "U F R U R' F R' F' R U' F'"),
+    ("8b", "U' R U2 R' U F' U' F U' R U' R'"),
+    ("8c", "U2 R U2 R' U' F' U' F R U' R'"),
+    ("8d", "U2 F' U' F R U' R' U' F' U2 F"),
+    ("8e", "U F R' F' R U R U' R2 U' R U' R' U2 R"),
+    ("8f", "U' R U2 R' U2 F' U2 F U R U R'"),
+    ("9a", "U F R' U' R2 U' R2 U R F' R' U R"),
+    ("9b", "U R' U' R U' R' U R' F R F' U R"),
+    ("9c", "U' R' U' R U' F R U R2 U' R F' U' R' U' R"),
+    ("9d", "R' U' R F R' F' U F R F'"),
+    ("9e", "R U R' U' R' F R2 U R' U' F'"),
+    ("9f", "U F R U R' U' R U' R' U R U2 R' U' F'"),
+    ("10a", "U F R U R2 U' F' U R U R U' R'"),
+    ("10b", "R U R' U R' F R F' R U2 R'"),
+    ("10c", "U R U' R' F' U F R U R' U2 R U2 R'"),
+    ("10d", "U F U R U' R2 U R U' F' U R' U' R"),
+    ("10e", "R' U R U F R' U R2 U' R' F' U R' U R"),
+    ("10f", "R' U' R U F R U R' U' F' R' U R"),
+    ("11a", "U F R U' R2 U2 R2 U R2 U R U' F'"),
+    ("11b", "U F U R U' R' U R U2 R' U2 R U R' F'"),
+    ("11c", "F U' R' U' R U R U R' U' R' U R U' F'"),
+    ("11d", "U' R U' R' U' R U' R' U2 F' U F U' R U R'"),
+    ("11e", "U' R U2 R' F R' U' R2 U' R2 U F' U R"),
+    ("11f", "U F U' R U R' U' R U2 R' U' R U2 R' U F'"),
+    ("12a", "U F R U R' U' F' U F R U R' U' F'"),
+    ("12b", "R U2 R' U' F' R' U' R F R' U R2 U2 R'"),
+    ("12c", "F U' R U2 R' U R U2 R' U R U' R' U F'"),
+    ("12d", "U' F U R U2 R' U R U R' U R U' R' F'"),
+    ("12e", "U' R U' R' U' F' U2 F U2 R U' R' U R U2 R'"),
+    ("12f", "R U' R' U F' U' F U2 R U R' U R U R'"),
+    ("13a", "U2 F R U' R' U2 R U R' U R U' R' F'"),
+    ("13b", "R' U2 R U R' U R U F U R U' R' F'"),
+    ("13c", "U F R' F' R U2 F' U F U2 R U' R'"),
+    ("13d", "F U R U' R2 F' R U R U' R'"),
+    ("13e", "F U R U2 R' U' R U R' F'"),
+    ("13f", "U' F R' F' U' F R F' R' U R"),
+    ("14a", "U F R' U' R F' R' U R U F R U' R' F'"),
+    ("14b", "U R' U' R U' R' U2 R F U R U' R' F'"),
+    ("14c", "U2 R U R' U2 F' U' F U' F' U' F R U' R'"),
+    ("14d", "R' U R U' F U R' U' R2 U R' U' F'"),
+    ("14e", "U' R' U' R F U R U' R' F' U' R' U R"),
+    ("14f", "R U2 R' U2 R U' R' F' U' F R U R'"),
+    ("15a", "U2 R U' R' F' U2 F U R U' R' U F' U F"),
+    ("15b", "U2 F R U R' U' F' R' U2 R U R' U R"),
+    ("15c", "U2 F U F U R U' R' F' R U R' U2 F'"),
+    ("15d", "U' R U2 R' U' R U R' U2 F' U2 F U R U R'"),
+    ("15e", "U2 R U R' F' U' F U R U' R' U R U2 R'"),
+    ("15f", "U' R' U' R F R' U R F R' F' R U' F'"),
+    ("16a", "U' R' U' R F U R U' R' U' R' U R U F'"),
+    ("16b", "U2 F R U R' U' F' R U2 R' U' R U' R'"),
+    ("16c", "U2 R' F R U R' U' F' R U' R' U2 R"),
+    ("16d", "U2 R U2 R' U' R U' R' F R' F' R U R U' R'"),
+    ("16e", "U' R' F' R U R U' R' U' F U R U R'"),
+    ("16f", "U R U2 R' U' R U R' U' F' U F R U' R'"),
+    ("17a", "U F U' R U R' U2 F' U F U R U2 R' U F'"),
+    ("17b", "F U2 R' U' R F R' F' U R U2 R U R' U' F'"),
+    ("17c", "U F U' R U R' F' U' F U' R U' R' F'"),
+    ("17d", "F' U' F U' F R' F' R U2 F R' F' R"),
+    ("17e", "U F U R U' R' F2 U' F U F R' F' R"),
+    ("17f", "U R' F' U2 F2 U R U' R' F' U2 R"),
+    ("18a", "U2 F R U' R' F' U' F2 R' F' R2 U R' U' F'"),
+    ("18b", "U2 F U R U2 R' F R U R' U' F' R U R' F'"),
+    ("18c", "U2 F R' F' R2 U R' F' U2 F R U2 R'"),
+    ("18d", "U' F R U' R' U' R U R' F' U' F R U R' U' F'"),
+    ("18e", "F U' F U R U' R' U R U' R' F' R U2 R' F'"),
+    ("18f", "U2 F R U R' U F' U F R U' R' U F'"),
+    ("19a", "U2 F U2 R U' R' U' F' U2 F U' R U' R' U F'"),
+    ("19b", "F' U2 R U2 R' F2 U' F U R U R2 F' R U' F'"),
+    ("19c", "U2 F U R' U' F' U R2 U' R' F' U' F R U R'"),
+    ("19d", "R' U2 F R U R' U' F2 U2 F R"),
+    ("19e", "U2 F R U2 R' F R U R' U' R U R' U' F' U F'"),
+    ("19f", "U F R U' R' F R' F' R U R U R' U' F'"),
+    ("20a", "F U R U' R' F' U' F U F R' F' R U' F'"),
+    ("20b", "F' U' F2 U' F2 U2 F' R' F' R U2 R U' R' U F'"),
+    ("20c", "U2 R U R' U F R U R' U' F2 U2 F U R U' R'"),
+    ("21a", "R U2 R' U' R U R' U' R U' R'"),
+    ("21b", "F R U R' U' R U R' U' R U R' U' F'"),
+    ("21c", "U' R U R' U R U L' U R' U' L"),
+    ("21d", "U' F R U' R' U R U2 R' U' R U R' U' F'"),
+    ("22a", "R U2 R2 U' R2 U' R2 U2 R"),
+    ("22b", "U2 R U R' U L' U R U L U L' U R' L"),
+    ("22c", "U' R' U2 R U R' U R2 U' L' U R' U' L"),
+    ("22d", "U R L' U R' U' L U2 R U L' U R' U' L"),
+    ("22e", "U F U R U' R' U R U' R2 F' R U R U' R'"),
+    ("22f", "U2 L U' R' U L' U R U R' U R"),
+    ("23a", "U2 R U R' U R U2 R2 U' R U' R' U2 R"),
+    ("23b", "R' U2 R F U' R' U' R U F'"),
+    ("23c", "R' U2 R U F U' R' U2 R U F'"),
+    ("23d", "U2 R2 D R' U2 R D' R' U2 R'"),
+    ("23e", "U2 R U L' U R' U2 L U' R L' U R' U2 L"),
+    ("23f", "U2 R U' L U' R' U L' U2 R U' R'"),
+    ("24a", "U R' U R U2 R' U' R U2 R' U' R U' R' U R"),
+    ("24b", "U R U2 R' U L U' R U L2 U R' U' L"),
+    ("24c", "r U R' U' r' F R F'"),
+    ("24d", "U' F U' R' U2 R U F' U' R' U2 R"),
+    ("24e", "R L' U R U' L U R' U' R'"),
+    ("24f", "U' R U' R' U2 R L U' R' U L'"),
+    ("25a", "U R U R' U R U' R' U R U' R' U R U2 R'"),
+    ("25b", "U2 F R U R' U' R U' R' U2 R U2 R' U' F'"),
+    ("25c", "U' F R' F' r U R U' r'"),
+    ("25d", "U' R' U' R U2 L' U R' U' L U' R"),
+    ("25e", "U2 R U R' U2 L U' R U L' U R'"),
+    ("25f", "U F R U' R' U' R U2 R' U' F'"),
+    ("26a", "R U2 R' U' R U' R'"),
+    ("26b", "U' R U2 L' U R' U' L U' R U' R'"),
+    ("26c", "U' R' L U' R U L' U2 R' U2 R"),
+    ("26d", "U R2 D R' U R D' R' U R' U' R U' R'"),
+    ("26e", "U' R' U L U' R U L'"),
+    ("26f", "U R U2 R' U2 L' U R U' R' L"),
+    ("27a", "R U R' U R U2 R'"),
+    ("27b", "U2 R U R' U L' U R U' L U2 R'"),
+    ("27c", "U2 R L U2 R' U' R U2 L2 U R' U' L"),
+    ("27d", "R L' U R' U' L U2 R U2 R'"),
+    ("27e", "U2 R' U2 R U2 L U' R' U R L'"),
+    ("27f", "R U' L' U R' U' L"),
+    ("28a", "U' F U F R' F' U' F' U R U R U' R'"),
+    ("28b", "U' R2 U R2 F' U F R U' R' F R F' R'"),
+    ("28c", "R U2 R U R' U' R' U F R F' U R'"),
+    ("28d", "F R' F' U R U2 R U2 R' U R' U2 R"),
+    ("28e", "F R' U' R U' F U R' U' F' U2 R F'"),
+    ("28f", "U F2 U' F' R' F' R2 U' R2 F' R F"),
+    ("29a", "U F' U' F2 R' F' R2 U R2 U' R2 U' R2 U2 R"),
+    ("29b", "U2 F' U' R' U' R F R' U R2 U' R' F' U F"),
+    ("29c", "R' U2 R2 U R' U' R' U2 F R F'"),
+    ("29d", "U F R' U' R2 U' R2 U2 R U' F'"),
+    ("29e", "R' U2 R U F R' F' U R U R U' R'"),
+    ("29f", "U' R U' R' U' F U R U' R2 F' R2 U2 R'"),
+    ("30a", "R U' R' F R' F' R F' U F U2 R U2 R'"),
+    ("30b", "U' F R' F' R F' U2 F U R U R'"),
+    ("30c", "F U R U2 R' U' R U2 R' U' F'"),
+    ("30d", "R U R2 F' R U R U' R' U' F R U' R'"),
+    ("30e", "U' F R' F' R U' F' U' F U' R U' R'"),
+    ("30f", "F U R2 U' R2 F' R' U R U' F R F'"),
+    ("31a", "U2 F U R U' R2 F' R F' U2 F U R U R'"),
+    ("31b", "U2 R U2 R' U' R U R' U' F' U2 F U R U2 R'"),
+    ("31c", "U' F R' F' R U R U R' U' R U' R'"),
+    ("31d", "U R U2 R' U' F' U F U R U2 R'"),
+    ("31e", "U F' U2 F U R U' R' F' U F"),
+    ("31f", "R' U2 R U R' U R F R' F' R U R U' R'"),
+    ("32a", "U' R U2 R' U F' U' F2 R' F' R2 U2 R'"),
+    ("32b", "U2 R U2 R' U' R U R' F R' F' R2 U2 R'"),
+    ("32c", "U2 R U' R' U2 R U2 R' F R' F' R2 U R'"),
+    ("32d", "U' F U2 F' U' F U' R U' R' U' R U R' F'"),
+    ("32e", "U2 R U2 R' U' F' U F R U' R'"),
+    ("32f", "U F U R U R' U' F' U2 F U F'"),
+    ("33a", "F R' U R2 U' R F' R2 U R2 U R2 U' R2"),
+    ("33b", "U F' U F U R U2 R' F R' F' R"),
+    ("33c", "R U R' U' R' F R F'"),
+    ("33d", "U' R' U2 R U F R' U R2 U' R' F'"),
+    ("33e", "F R U' R' U R U R' F'"),
+    ("33f", "U' F' U' F2 R U R' U' R' F' R F' U2 F"),
+    ("34a", "U2 F R U R' U2 R' U R U F' U R' U R"),
+    ("34b", "U2 R U' R' U2 F' U F R U R' U R U R'"),
+    ("34c", "F' U2 F2 U R U' R2 F' R2 U' R'"),
+    ("34d", "U R' U' R F R' F' U R U2 R U2 R'"),
+    ("34e", "U' F' U' F U' R U' R' U' F R' F' R"),
+    ("34f", "U2 R U2 R' F R U R' U' F' U R U R'"),
+    ("35a", "F R U R U R' U' R' U2 F R F' U R' U F'"),
+    ("35b", "R U2 R2 F R F' R U2 R'"),
+    ("35c", "U2 F U R' U' R2 U' R2 U2 R U R U R' F'"),
+    ("35d", "U' F U R U' R2 U' R2 U' R2 U2 R U' F'"),
+    ("35e", "U' R' U2 R U F R' F' U F R F'"),
+    ("35f", "U R2 U' R2 U F R U R' U' F' R2 U R2"),
+    ("36a", "F' U' F2 R' F' R2 U R' U' R U' R'"),
+    ("36b", "U2 R U R' U2 F' U' F U' R U' R'"),
+    ("36c", "U2 R U R' U' F' U2 F U R U R'"),
+    ("36d", "U' F R U' R' U R' U2 R2 U R2 U R U' F'"),
+    ("36e", "R' F' U' F2 U R U' R' F' R"),
+    ("36f", "U R U' R' U' F U R U' R' F' R U2 R'"),
+    ("37a", "U F' U' F R U' R2 U' R F' R' U R U F"),
+    ("37b", "U' R U2 R' F R' F' R2 U2 R'"),
+    ("37c", "F R U' R' U' R U R' F'"),
+    ("37d", "F R' F' R U R U' R'"),
+    ("37e", "U R U R' U R U R' U' F' U2 F U R U' R'"),
+    ("37f", "U' R U' R' F R' F' R F' U' F U R U R'"),
+    ("38a", "U' F' U' R' U' R F R' U R U F R' F' R"),
+    ("38b", "U2 R U R' U F' U F U2 R U' R'"),
+    ("38c", "U F' U' F U R U2 R' U' F' U' F"),
+    ("38d", "U R' U' R F U' R' U2 R U R U R' U' F'"),
+    ("38e", "R U R' U R U' R' F' U' F R U' R'"),
+    ("38f", "U2 R U R2 U R U F' R' U2 R U' F U R U' R'"),
+    ("39a", "U2 F U F' U F U' F R' F' R2 U' R' F'"),
+    ("39b", "R U2 R' U' R U' R' F U R U' R' F'"),
+    ("39c", "U F' R' F' R F' U' F R U R' U F2"),
+    ("39d", "U R' U' R F R' F' U R U R U' R'"),
+    ("39e", "U2 R U R' F' U' F U R U2 R'"),
+    ("39f", "U2 F R U R' U2 R' U' R U F' U' R' U R"),
+    ("40a", "U2 F' U' F U' F' U F2 R' F' R2 U' R'"),
+    ("40b", "U2 F R U R' U' F' R U R' U R U2 R'"),
+    ("40c", "R U R' F' U' F R U R' U' R U' R'"),
+    ("40d", "U2 R U' R' U R U R' U' F' U F U' R U R'"),
+    ("40e", "U F' U' F R U R' U' F' U2 F"),
+    ("40f", "U2 R' U2 R F U R' U' F' U2 R U R U' R'"),
+    ("41a", "U' F' U2 F U2 R U R' F R' F' R F' U' F"),
+    ("41b", "R U R' U R U2 R' F R U R' U' F'"),
+    ("41c", "U' F U' F' U2 F U R U' R' U' F'"),
+    ("41d", "F R' F' U2 R U R U' R' U R' U R"),
+    ("41e", "F U2 R' U' R F' R' U R U' F R U' R' F'"),
+    ("41f", "U R U2 R' U' F' U' F U R U' R' U R U2 R'"),
+    ("42a", "U2 F' U' F2 R' F' R U R' U' R2 U' R2 U2 R"),
+    ("42b", "R' U' R U' R' U2 R F R U R' U' F'"),
+    ("42c", "R' U' R U F R U R' U' R' U R U' F'"),
+    ("42d", "U2 R U' R' U F' U' F U R U' R' U' R U R'"),
+    ("42e", "U F R' F' R U2 R' U' R2 U' R2 U2 R"),
+    ("42f", "U R' U' R U F U' R2 U' R2 U R2 F' R' U R"),
+    ("43a", "U R2 U R2 U' R2 U' R2 F R' U R2 U' R F'"),
+    ("43b", "U' R' U' F' U F R"),
+    ("43c", "U R U' R' U R U' R' U' F R' F' R2 U R'"),
+    ("43d", "U' F U R' U2 R2 U R2 U R F'"),
+    ("43e", "U' R U' R' U' F' U2 F U R U' R'"),
+    ("43f", "F U' F R2 F' R' U' R' U F' U2 R' U R"),
+    ("44a", "F R U R' F R' F' R F' U F U2 F'"),
+    ("44b", "F U R U' R' F'"),
+    ("44c", "U' R' U' R U F U' R' U R U2 R U' R' F'"),
+    ("44d", "R' U R U' F U R' U' R U R U' R' F'"),
+    ("44e", "F' U F U R U2 R' U' F' U F"),
+    ("44f", "U R U2 R' U' F' U' F U R U2 R'"),
+    ("45a", "F2 R U' R' U R U R2 F' R F'"),
+    ("45b", "F R U R' U' F'"),
+    ("45c", "F U R' U' R2 U' R2 U2 R U' R U R' F'"),
+    ("45d", "U R' U' R F R' U R U' R U R' U' F'"),
+    ("45e", "R' U2 R U2 F R U R' U' R' U R U' F'"),
+    ("45f", "F R2 D R' U R D' R2 U' F'"),
+    ("46a", "R U R' U2 F' U' F2 R' F' R U' R U' R'"),
+    ("46b", "U F U F R' F' R U' F'"),
+    ("46c", "R U R' U R U' R' F' U F R U' R'"),
+    ("46d", "U F R U R' U' R' U R U' F' U R' U' R"),
+    ("46e", "F R U' R' F U R U' R2 F' R U F'"),
+    ("46f", "U' F R U R2 U' R F' U' R' U2 R"),
+    ("47a", "U' R' F' U' F U F' U' F U R"),
+    ("47b", "U' F U R U' R' F' R U R' U R U2 R'"),
+    ("47c", "U' F R' F' R U2 R U' R' U R U2 R'"),
+    ("47d", "U2 F' U' F U2 R U' R' F' U F2 R' F' R"),
+    ("47e", "U2 R' U' R U F R U R' U2 R' U2 R U F'"),
+    ("47f", "U2 F' U2 F U' R U R' U2 F R' F' R"),
+    ("48a", "F R U R' U' R U R' U' F'"),
+    ("48b", "U2 R U R' F' U2 F R U R' U' R U' R'"),
+    ("48c", "R U2 R' U' R U R' U2 R' F R F'"),
+    ("48d", "F R' F' U2 R U R' U R2 U2 R'"),
+    ("48e", "U F U' R' U2 R U2 R U' R' F' U' R' U R"),
+    ("48f", "U R' U' R U' R' U R F R' F' U R U2 R U2 R'"),
+    ("49a", "U' F R' F2 R U2 R U2 R' F"),
+    ("49b", "U2 R U R' U R U' R' F' U2 F R U' R'"),
+    ("49c", "U' R U R2 U' R F R' U R2 U' R2 F' R"),
+    ("49d", "U R' U' R F U' R' U2 R U2 R U' R' F'"),
+    ("49e", "U2 F U F' U F U' R U2 R' U' R U R' F'"),
+    ("49f", "U F' U' F2 R' F' R F' U' F R U' R'"),
+    ("50a", "U' F' R U2 R' U2 R' F2 R F'"),
+    ("50b", "U' R U2 R' U' R U' R' F R U R' U' F'"),
+    ("50c", "U2 R2 U R' U' R' F R' F' R U2 R U' R U' R2"),
+    ("50d", "U' F R U' R' U' R U R' U R U' R' F'"),
+    ("50e", "R U R' F R U' R' U R U R' F2 U F"),
+    ("50f", "R U R' U F2 U' F R U R' F' U F2"),
+    ("51a", "F U R U' R' U R U' R' F'"),
+    ("51b", "U2 F U R' U' F' U R U2 R U2 R'"),
+    ("51c", "U F R' U' R2 U' R2 U F' U F R F'"),
+    ("51d", "F' U2 F2 R U R' F' R U' R' U F U' F'"),
+    ("51e", "U2 R U2 R' U2 R' F R2 U R' U' F'"),
+    ("51f", "U2 R' U2 R U2 F R U R' U' F' R' U R"),
+    ("52a", "U2 R' U2 R U F R' U' R2 U R2 U R F'"),
+    ("52b", "U F U F' U F U' R U' R' F'"),
+    ("52c", "U' F U R U' R2 F' R U2 R U2 R'"),
+    ("52d", "U R U' R' F' U' F U2 R U R' U' R U R'"),
+    ("52e", "U2 F R' F' U' R U F' U' F R' U R2 U2 R'"),
+    ("52f", "U2 R U R' U F R' U' R2 U' R2 U F' U R"),
+    ("53a", "U2 F R U' R' U2 R U2 R' U F' U' F U' F'"),
+    ("53b", "U R' U2 R2 U R2 U R2 U2 R' F U R U' R' F'"),
+    ("53c", "U2 F U R U' R' U R U2 R' U R U R' F'"),
+    ("53d", "U F U F' U' R U R' F R U' R' F2 U2 F"),
+    ("53e", "U2 F R U R' U' R U' R' U R U R' F'"),
+    ("53f", "F' U' F R U' R' F R' F' R2 U' R'"),
+    ("54a", "U' F R U R2 U' F' U R U2 R U2 R'"),
+    ("54b", "U R U2 R' U F' U' F R' U' R2 U' R2 U2 R"),
+    ("54c", "F U' R U R2 U' R2 U' R' U R' U R U' F'"),
+    ("54d", "U' F U2 F2 U' F2 R U' R2 F' R2 U' R'"),
+    ("54e", "R U2 R' U' F' U' F R U R' U' R U' R'"),
+    ("54f", "U' F R U' R' U' R U R' U F' U F U2 F'"),
+    ("55a", "R' U R U' F U R' U' R2 U' R' U R U R' F'"),
+    ("55b", "F U R U2 R' U R U' R' U' F' U2 F U F'"),
+    ("55c", "F R' F' R2 U R' F' U' F U2 R U' R'"),
+    ("55d", "U' R' U' R F R' U R2 U' R' U R U' R' F'"),
+    ("56a", "U' R U2 R2 U' R F' U' R' U2 R F U R U2 R'"),
+    ("56b", "U' R U2 R2 U' R2 U' R2 U2 R F U R U' R' F'"),
+    ("56c", "U R' U' R F U R U' R' F' U2 R' U2 R"),
+    ("56d", "U' R' U' R F U R U2 R' U2 R' U2 R2 U R' F'"),
+    ("57a", "U' F U R U' R' F' U2 F U R U' R' F'"),
+    ("57b", "F R U2 R' U F' U' F2 R' F' R U2 F'"),
+    ("57c", "F R' F' R2 U' R' F' U' F R U R'"),
+    ("57d", "R U2 R' F U R' U' F' U R U' R U2 R'"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Rng;
+    use crate::subset_scrambler::{self, Subset};
+    use std::collections::HashSet;
+
+    /// Pattern up to a U turn before and after (smallest of the sixteen)
+    fn pattern_class(set: AlgorithmSet, cube: &CubieCube) -> Vec<u8> {
+        (0..16)
+            .map(|turns| set.pattern(&after_auf(&before_auf(cube, turns / 4), turns % 4)))
+            .min()
+            .unwrap()
+    }
+
+    /// Corner shapes in table order, each with the OLL case whose table algorithm
+    /// shows it in its usual orientation (0 for oriented corners)
+    const SHAPES: [(&str, usize); 8] = [
+        ("H", 21),
+        ("Pi", 22),
+        ("U", 23),
+        ("T", 24),
+        ("L", 25),
+        ("S", 27),
+        ("AS", 26),
+        ("O", 0),
+    ];
+
+    /// Orientation of the U corners, and of the U edges when `edges` is set
+    fn orientation(cube: &CubieCube, edges: bool) -> Vec<u8> {
+        let mut orientation = cube.co[..4].to_vec();
+        if edges {
+            orientation.extend_from_slice(&cube.eo[..4]);
+        }
+        orientation
+    }
+
+    /// Orientation of OLL case `number` as its table algorithm solves it
+    fn oll_orientation(number: usize, edges: bool) -> Vec<u8> {
+        if number == 0 {
+            return vec![0; if edges { 8 } else { 4 }];
+        }
+        let alg = crate::last_layer::OLL_ALGORITHMS[number - 1];
+        let moves = algorithm::parse_algorithm(alg).unwrap();
+        orientation(&algorithm::case_state(&moves).unwrap(), edges)
+    }
+
+    /// Corner permutation seen from one side: 0 when the corners are permuted, 1 for a
+    /// diagonal swap, 2 to 5 for a swap of the front, right, back or left corners,
+    /// told by the one pair of neighbouring corners that belong next to each other
+    fn corner_class(cube: &CubieCube) -> usize {
+        let matched: Vec<usize> = (0..4)
+            .filter(|&p| (cube.cp[(p + 1) % 4] + 4 - cube.cp[p]) % 4 == 1)
+            .collect();
+        match matched[..] {
+            [_, _, _, _] => 0,
+            [] => 1,
+            // The swapped pair is opposite the matched one
+            [p] => 2 + [2, 1, 0, 3][p],
+            _ => unreachable!("corner permutation class"),
+        }
+    }
+
+    /// Lexicographic rank of the U edge permutation, read with the corners turned so
+    /// that the URF corner is home
+    fn edge_rank(cube: &CubieCube) -> usize {
+        let home = (0..4)
+            .map(|turns| before_auf(cube, turns))
+            .find(|turned| turned.cp[0] == 0)
+            .unwrap();
+        let edges = &home.ep[..4];
+        (0..4)
+            .map(|i| {
+                let smaller = edges[i + 1..].iter().filter(|&&e| e < edges[i]).count();
+                smaller * [6, 2, 1, 1][i]
+            })
+            .sum()
+    }
+
+    /// Shape (or OLL number) of a case and its place within it: the smallest corner
+    /// class (and edge rank for ZBLL) over the sides that show the usual orientation
+    fn recognition(set: AlgorithmSet, case: &CubieCube) -> (String, usize, Vec<usize>) {
+        let (shape, order, view) = match set {
+            AlgorithmSet::Ollcp => {
+                let number = crate::last_layer::recognize_oll(case).unwrap().number as usize;
+                (number.to_string(), number, oll_orientation(number, true))
+            }
+            _ => SHAPES
+                .iter()
+                .enumerate()
+                .map(|(order, &(name, number))| {
+                    (name.to_string(), order, oll_orientation(number, false))
+                })
+                .find(|(_, _, view)| {
+                    (0..4).any(|turns| orientation(&after_auf(case, turns), false) == *view)
+                })
+                .unwrap(),
+        };
+        let key = (0..4)
+            .map(|turns| after_auf(case, turns))
+            .filter(|seen| orientation(seen, set == AlgorithmSet::Ollcp) == view)
+            .map(|seen| match set {
+                AlgorithmSet::Zbll => vec![corner_class(&seen), edge_rank(&seen)],
+                _ => vec![corner_class(&seen)],
+            })
+            .min()
+            .unwrap();
+        (shape, order, key)
+    }
+
+    #[test]
+    fn test_set_tables_cover_every_case_once() {
+        let sizes = [42, 42, 472, 329];
+        for (set, size) in AlgorithmSet::ALL.into_iter().zip(sizes) {
+            assert_eq!(set.cases().len(), size);
+            let mut classes = HashSet::new();
+            let mut ids = HashSet::new();
+            for (id, alg) in set.cases() {
+                assert!(ids.insert(id), "{} {} repeats an id", set.name(), id);
+                let moves = algorithm::parse_algorithm(alg).unwrap();
+                let case = algorithm::case_state(&moves).unwrap();
+                assert!(is_f2l_solved(&case), "{} {} breaks F2L", set.name(), id);
+                assert_eq!(set.check(&case), Ok(()), "{} {}", set.name(), id);
+                assert!(!set.is_skip(&case), "{} {} is a skip", set.name(), id);
+                assert!(
+                    classes.insert(pattern_class(set, &case)),
+                    "{} {} repeats a case",
+                    set.name(),
+                    id
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_set_ids_follow_recognition_order() {
+        for set in AlgorithmSet::ALL {
+            let mut previous: Option<(String, usize, Vec<usize>)> = None;
+            let mut rank = 0;
+            for (id, alg) in set.cases() {
+                let moves = algorithm::parse_algorithm(alg).unwrap();
+                let case = algorithm::case_state(&moves).unwrap();
+                let (shape, order, key) = recognition(set, &case);
+                match &previous {
+                    Some((_, last_order, last_key)) if *last_order == order => {
+                        assert!(key > *last_key, "{} {} is out of order", set.name(), id);
+                        rank += 1;
+                    }
+                    Some((_, last_order, _)) => {
+                        assert!(order > *last_order, "{} {} is out of order", set.name(), id);
+                        rank = 1;
+                    }
+                    None => rank = 1,
+                }
+                let expected = match set {
+                    AlgorithmSet::Ollcp => format!("{}{}", shape, (b'a' + rank - 1) as char),
+                    _ => format!("{}{}", shape, rank),
+                };
+                assert_eq!(*id, expected, "{} {}", set.name(), alg);
+                previous = Some((shape, order, key));
+            }
+        }
+    }
+
+    #[test]
+    fn test_recognize_random_cases() {
+        let mut rng = Rng::new(42);
+        let subsets = [
+            Subset::Coll,
+            Subset::FirstTwoBlocks,
+            Subset::Zbll,
+            Subset::LastLayer,
+        ];
+        for (set, subset) in AlgorithmSet::ALL.into_iter().zip(subsets) {
+            for _ in 0..40 {
+                let state = subset_scrambler::random_subset_state(&subset.spec(), &mut rng);
+                let mut cube = state.to_optimized();
+                let Ok(case) = recognize(set, &cube) else {
+                    // Only PLLs fall outside ZBLL and OLLCP
+                    assert!(state.co[..4].iter().all(|&o| o == 0));
+                    continue;
+                };
+                cube.apply_moves(&case.solution());
+                let solved = CubieCube::from_optimized(&cube).unwrap();
+                assert_eq!(solving_auf(set, &solved), Some(0), "{:?}", case);
+                assert_eq!(set.check(&solved), Ok(()));
+            }
+        }
+    }
+
+    #[test]
+    fn test_recognize_named_cases() {
+        let set = AlgorithmSet::from_name("coll").unwrap();
+        assert_eq!(set, AlgorithmSet::Coll);
+        assert!(AlgorithmSet::from_name("ELL").is_err());
+        let alg = set.algorithm("s1").unwrap();
+        let mut cube = OptimizedCube::solved();
+        cube.apply_moves(&algorithm::invert_algorithm(
+            &algorithm::parse_algorithm(alg).unwrap(),
+        ));
+        let case = recognize(set, &cube).unwrap();
+        assert_eq!((case.id(), case.pre_auf, case.post_auf), ("S1", 0, 0));
+
+        // CMLL is recognised with the M slice turned
+        cube.apply_move_code(MoveCode::M);
+        assert!(recognize(AlgorithmSet::Coll, &cube).is_err());
+        let case = recognize(AlgorithmSet::Cmll, &cube).unwrap();
+        let cmll = case.algorithm().unwrap();
+        cube.apply_moves(&case.solution());
+        cube.apply_move_code(MoveCode::Mp);
+        let state = CubieCube::from_optimized(&cube).unwrap();
+        assert!(state.cp.iter().enumerate().all(|(i, &p)| p == i as u8));
+        assert!(state.co.iter().all(|&o| o == 0), "{}", cmll);
+
+        let skip = recognize(AlgorithmSet::Zbll, &OptimizedCube::solved()).unwrap();
+        assert_eq!((skip.id(), skip.solution()), ("Skip", Vec::new()));
+        let mut pll = OptimizedCube::solved();
+        pll.apply_scramble("R U R' U' R' F R2 U' R' U' R U R' F'")
+            .unwrap();
+        assert!(recognize(AlgorithmSet::Zbll, &pll).is_err());
+    }
+}
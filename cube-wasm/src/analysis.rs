@@ -0,0 +1,99 @@
+// Scramble analysis helpers shared by the scramble filters and solvers
+// Edge orientation is measured on the F/B axis (F and B quarter turns flip edges),
+// F2L pairs are those of the D cross, so rotate the cube first for other colors.
+use crate::cubie_cube::{CubieCube, CORNER_FACELETS, CORNER_FACES, EDGE_FACELETS, EDGE_FACES};
+
+/// Corner and edge cubies of each F2L pair: DFR+FR, DLF+FL, DBL+BL, DRB+BR
+pub const F2L_PAIRS: [(u8, u8); 4] = [(4, 8), (5, 9), (6, 10), (7, 11)];
+
+/// Number of edges that are misoriented for ZZ (F/B axis)
+pub fn misoriented_edges(cube: &CubieCube) -> usize {
+    cube.eo.iter().filter(|&&flip| flip == 1).count()
+}
+
+/// Whether a corner and an edge cubie are next to each other and connected the way
+/// they are on a solved cube, wherever they are
+pub fn is_pair_connected(cube: &CubieCube, corner: u8, edge: u8) -> bool {
+    let stickers = cube.to_optimized();
+    let stickers = stickers.stickers();
+    let corner_position = cube.cp.iter().position(|&p| p == corner);
+    let edge_position = cube.ep.iter().position(|&p| p == edge);
+    let (Some(corner_position), Some(edge_position)) = (corner_position, edge_position) else {
+        return false;
+    };
+    // Both edge stickers must lie on faces of the corner and match its sticker there
+    EDGE_FACES[edge_position]
+        .iter()
+        .zip(EDGE_FACELETS[edge_position])
+        .all(|(face, edge_facelet)| {
+            CORNER_FACES[corner_position]
+                .iter()
+                .position(|corner_face| corner_face == face)
+                .is_some_and(|k| {
+                    stickers[CORNER_FACELETS[corner_position][k]] == stickers[edge_facelet]
+                })
+        })
+}
+
+/// Number of F2L pairs already formed (including pairs solved in their slot)
+pub fn connected_pairs(cube: &CubieCube) -> usize {
+    F2L_PAIRS
+        .iter()
+        .filter(|&&(corner, edge)| is_pair_connected(cube, corner, edge))
+        .count()
+}
+
+/// Whether a corner and an edge cubie are both solved in their slot
+pub fn is_pair_solved(cube: &CubieCube, corner: u8, edge: u8) -> bool {
+    let (corner, edge) = (corner as usize, edge as usize);
+    cube.cp[corner] == corner as u8
+        && cube.co[corner] == 0
+        && cube.ep[edge] == edge as u8
+        && cube.eo[edge] == 0
+}
+
+/// Number of F2L pairs formed outside their solved slot, ready to be inserted
+pub fn free_pairs(cube: &CubieCube) -> usize {
+    F2L_PAIRS
+        .iter()
+        .filter(|&&(corner, edge)| {
+            is_pair_connected(cube, corner, edge) && !is_pair_solved(cube, corner, edge)
+        })
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::optimized_cube::OptimizedCube;
+
+    fn state(scramble: &str) -> CubieCube {
+        CubieCube::from_moves(&OptimizedCube::parse_scramble(scramble).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_misoriented_edges() {
+        assert_eq!(misoriented_edges(&CubieCube::solved()), 0);
+        assert_eq!(misoriented_edges(&state("F")), 4);
+        assert_eq!(misoriented_edges(&state("F B")), 8);
+        assert_eq!(misoriented_edges(&state("F2 R U L2 D")), 0);
+    }
+
+    #[test]
+    fn test_connected_pairs() {
+        assert_eq!(connected_pairs(&CubieCube::solved()), 4);
+        // R U R' takes the front right pair out together
+        assert!(is_pair_connected(&state("R U R'"), 4, 8));
+        // R U' R' splits it
+        assert!(!is_pair_connected(&state("R U' R'"), 4, 8));
+        assert_eq!(connected_pairs(&state("R U' R'")), 3);
+        // A single turn moves every pair it touches as a block
+        assert_eq!(connected_pairs(&state("R")), 4);
+
+        // Solved pairs are connected but not free
+        assert_eq!(free_pairs(&CubieCube::solved()), 0);
+        assert_eq!(free_pairs(&state("R U R'")), 1);
+        assert_eq!(free_pairs(&state("R U' R'")), 0);
+        assert_eq!(free_pairs(&state("R")), 2);
+    }
+}
@@ -0,0 +1,713 @@
+use crate::algorithm;
+use crate::algorithm_sets::{self, AlgorithmSet, SetCase};
+use crate::beginner::{self, BeginnerSolution, TeachingStage};
+use crate::blindfolded::{self, BldStats, Memo, MemoOptions, MisorientedPiece, PieceMemo};
+use crate::cfop::{self, SolutionStage, StagedSolution};
+use crate::cross::{self, CrossSolution};
+use crate::cubie_cube::{CubieCube, CORNER_NAMES, EDGE_NAMES};
+use crate::eoline::{self, EoSolution, EoStep};
+use crate::f2l::{self, SlotCase};
+use crate::first_block::{self, FirstBlockOptions, FirstBlockSolution};
+use crate::insertions::{self, Insertion, InsertionSearch};
+use crate::last_layer::{self, OllCase, PllCase};
+use crate::optimized_cube::OptimizedCube;
+use crate::xcross::{self, ExtendedCrossOptions, ExtendedCrossSolution, SLOT_NAMES};
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+/// Color names indexed by color value
+const COLOR_NAMES: [&str; 6] = ["white", "yellow", "green", "blue", "red", "orange"];
+
+/// Optimal cross for one color, as returned to JavaScript
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CrossResult {
+    color: u8,
+    color_name: &'static str,
+    rotation: String,
+    solution: String,
+    length: usize,
+}
+
+impl From<&CrossSolution> for CrossResult {
+    fn from(solution: &CrossSolution) -> Self {
+        CrossResult {
+            color: solution.color,
+            color_name: COLOR_NAMES[solution.color as usize],
+            rotation: OptimizedCube::moves_to_string(&solution.rotation),
+            solution: OptimizedCube::moves_to_string(&solution.moves),
+            length: solution.moves.len(),
+        }
+    }
+}
+
+/// Optimal extended cross for one color and slot choice, as returned to JavaScript
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ExtendedCrossResult {
+    color: u8,
+    color_name: &'static str,
+    slots: Vec<&'static str>,
+    rotation: String,
+    solution: String,
+    length: usize,
+}
+
+impl From<&ExtendedCrossSolution> for ExtendedCrossResult {
+    fn from(solution: &ExtendedCrossSolution) -> Self {
+        ExtendedCrossResult {
+            color: solution.color,
+            color_name: COLOR_NAMES[solution.color as usize],
+            slots: solution
+                .slots
+                .iter()
+                .map(|&slot| SLOT_NAMES[slot])
+                .collect(),
+            rotation: OptimizedCube::moves_to_string(&solution.rotation),
+            solution: OptimizedCube::moves_to_string(&solution.moves),
+            length: solution.moves.len(),
+        }
+    }
+}
+
+/// Optimal EOLine or EOCross for one color, as returned to JavaScript
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct EoResult {
+    color: u8,
+    color_name: &'static str,
+    rotation: String,
+    solution: String,
+    length: usize,
+}
+
+impl From<&EoSolution> for EoResult {
+    fn from(solution: &EoSolution) -> Self {
+        EoResult {
+            color: solution.color,
+            color_name: COLOR_NAMES[solution.color as usize],
+            rotation: OptimizedCube::moves_to_string(&solution.rotation),
+            solution: OptimizedCube::moves_to_string(&solution.moves),
+            length: solution.moves.len(),
+        }
+    }
+}
+
+/// Extended cross analysis: solutions shortest first, and whether every color and
+/// slot choice was solved before a limit was hit
+#[derive(Serialize)]
+struct ExtendedCrossAnalysis {
+    complete: bool,
+    solutions: Vec<ExtendedCrossResult>,
+}
+
+/// Optimal Roux first block for one orientation, as returned to JavaScript
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct FirstBlockResult {
+    bottom_color: u8,
+    left_color: u8,
+    name: String,
+    rotation: String,
+    solution: String,
+    length: usize,
+}
+
+impl From<&FirstBlockSolution> for FirstBlockResult {
+    fn from(solution: &FirstBlockSolution) -> Self {
+        let bottom = COLOR_NAMES[solution.bottom_color as usize];
+        let left = COLOR_NAMES[solution.left_color as usize];
+        FirstBlockResult {
+            bottom_color: solution.bottom_color,
+            left_color: solution.left_color,
+            name: format!("{}-{}", bottom, left),
+            rotation: OptimizedCube::moves_to_string(&solution.rotation),
+            solution: OptimizedCube::moves_to_string(&solution.moves),
+            length: solution.moves.len(),
+        }
+    }
+}
+
+/// First block analysis: solutions shortest first, and whether every orientation was
+/// solved before the timeout
+#[derive(Serialize)]
+struct FirstBlockAnalysis {
+    complete: bool,
+    solutions: Vec<FirstBlockResult>,
+}
+
+/// One stage of a step-by-step solution, as returned to JavaScript
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct StageResult {
+    name: String,
+    moves: String,
+    case_id: Option<String>,
+    length: usize,
+}
+
+impl From<&SolutionStage> for StageResult {
+    fn from(stage: &SolutionStage) -> Self {
+        StageResult {
+            name: stage.name.clone(),
+            moves: OptimizedCube::moves_to_string(&stage.moves),
+            case_id: stage.case_id.clone(),
+            length: stage.moves.len(),
+        }
+    }
+}
+
+/// Step-by-step solution: rotation, stages and total move count
+#[derive(Serialize)]
+struct StagedSolutionResult {
+    rotation: String,
+    stages: Vec<StageResult>,
+    length: usize,
+}
+
+impl From<&StagedSolution> for StagedSolutionResult {
+    fn from(solution: &StagedSolution) -> Self {
+        StagedSolutionResult {
+            rotation: OptimizedCube::moves_to_string(&solution.rotation),
+            stages: solution.stages.iter().map(StageResult::from).collect(),
+            length: solution.moves().len(),
+        }
+    }
+}
+
+/// One stage of a beginner solution with the 54 stickers after it
+#[derive(Serialize)]
+struct TeachingStageResult {
+    name: String,
+    explanation: String,
+    moves: String,
+    length: usize,
+    stickers: Vec<u8>,
+}
+
+impl From<&TeachingStage> for TeachingStageResult {
+    fn from(stage: &TeachingStage) -> Self {
+        TeachingStageResult {
+            name: stage.name.clone(),
+            explanation: stage.explanation.clone(),
+            moves: OptimizedCube::moves_to_string(&stage.moves),
+            length: stage.moves.len(),
+            stickers: stage.stickers.to_vec(),
+        }
+    }
+}
+
+/// Beginner solution: rotation, stickers after it, stages and total move count
+#[derive(Serialize)]
+struct BeginnerSolutionResult {
+    rotation: String,
+    stickers: Vec<u8>,
+    stages: Vec<TeachingStageResult>,
+    length: usize,
+}
+
+impl From<&BeginnerSolution> for BeginnerSolutionResult {
+    fn from(solution: &BeginnerSolution) -> Self {
+        BeginnerSolutionResult {
+            rotation: OptimizedCube::moves_to_string(&solution.rotation),
+            stickers: solution.stickers.to_vec(),
+            stages: solution
+                .stages
+                .iter()
+                .map(TeachingStageResult::from)
+                .collect(),
+            length: solution.moves().len(),
+        }
+    }
+}
+
+/// Recognised OLL case, as returned to JavaScript
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct OllCaseResult {
+    number: u8,
+    case_id: String,
+    auf: String,
+    algorithm: String,
+}
+
+impl From<OllCase> for OllCaseResult {
+    fn from(case: OllCase) -> Self {
+        OllCaseResult {
+            number: case.number,
+            case_id: match case.number {
+                0 => "OLL skip".to_string(),
+                number => format!("OLL {}", number),
+            },
+            auf: OptimizedCube::moves_to_string(&case.auf_moves()),
+            algorithm: case.algorithm().unwrap_or_default().to_string(),
+        }
+    }
+}
+
+/// Recognised PLL case, as returned to JavaScript
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PllCaseResult {
+    name: &'static str,
+    pre_auf: String,
+    post_auf: String,
+    algorithm: String,
+    solution: String,
+}
+
+impl From<PllCase> for PllCaseResult {
+    fn from(case: PllCase) -> Self {
+        PllCaseResult {
+            name: case.name,
+            pre_auf: OptimizedCube::moves_to_string(&last_layer::auf_moves(case.pre_auf)),
+            post_auf: OptimizedCube::moves_to_string(&last_layer::auf_moves(case.post_auf)),
+            algorithm: case.algorithm().unwrap_or_default().to_string(),
+            solution: OptimizedCube::moves_to_string(&case.solution()),
+        }
+    }
+}
+
+/// Recognised case of an algorithm set, as returned to JavaScript
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SetCaseResult {
+    id: &'static str,
+    case_id: String,
+    pre_auf: String,
+    post_auf: String,
+    algorithm: String,
+    solution: String,
+}
+
+impl From<SetCase> for SetCaseResult {
+    fn from(case: SetCase) -> Self {
+        SetCaseResult {
+            id: case.id(),
+            case_id: format!("{} {}", case.set.name(), case.id()),
+            pre_auf: OptimizedCube::moves_to_string(&last_layer::auf_moves(case.pre_auf)),
+            post_auf: OptimizedCube::moves_to_string(&last_layer::auf_moves(case.post_auf)),
+            algorithm: case.algorithm().unwrap_or_default().to_string(),
+            solution: OptimizedCube::moves_to_string(&case.solution()),
+        }
+    }
+}
+
+/// Recognised F2L case of one slot, as returned to JavaScript
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SlotCaseResult {
+    slot: &'static str,
+    corner_position: &'static str,
+    corner_twist: u8,
+    edge_position: &'static str,
+    edge_flip: u8,
+    solved: bool,
+    number: Option<u8>,
+    case_id: Option<String>,
+    auf: String,
+    algorithm: String,
+    solution: String,
+}
+
+impl From<&SlotCase> for SlotCaseResult {
+    fn from(slot_case: &SlotCase) -> Self {
+        let case = slot_case.case;
+        SlotCaseResult {
+            slot: SLOT_NAMES[slot_case.slot],
+            corner_position: CORNER_NAMES[slot_case.corner_position as usize],
+            corner_twist: slot_case.corner_twist,
+            edge_position: EDGE_NAMES[slot_case.edge_position as usize],
+            edge_flip: slot_case.edge_flip,
+            solved: case.is_some_and(|case| case.number == 0),
+            number: case.map(|case| case.number).filter(|&number| number > 0),
+            case_id: case
+                .filter(|case| case.number > 0)
+                .map(|case| format!("F2L {}", case.number)),
+            auf: case
+                .map(|case| OptimizedCube::moves_to_string(&last_layer::auf_moves(case.auf)))
+                .unwrap_or_default(),
+            algorithm: case
+                .and_then(|case| case.algorithm())
+                .unwrap_or_default()
+                .to_string(),
+            solution: slot_case
+                .moves()
+                .map(|moves| OptimizedCube::moves_to_string(&moves))
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// Misoriented piece of a memo, as returned to JavaScript
+#[derive(Serialize)]
+struct MisorientedPieceResult {
+    position: &'static str,
+    orientation: u8,
+}
+
+impl From<&MisorientedPiece> for MisorientedPieceResult {
+    fn from(piece: &MisorientedPiece) -> Self {
+        MisorientedPieceResult {
+            position: piece.position,
+            orientation: piece.orientation,
+        }
+    }
+}
+
+/// Memo of one piece type, as returned to JavaScript
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PieceMemoResult {
+    letters: String,
+    pairs: Vec<String>,
+    cycle_breaks: String,
+    misoriented: Vec<MisorientedPieceResult>,
+}
+
+impl From<&PieceMemo> for PieceMemoResult {
+    fn from(memo: &PieceMemo) -> Self {
+        PieceMemoResult {
+            letters: memo.letters.iter().collect(),
+            pairs: memo.pairs(),
+            cycle_breaks: memo.cycle_breaks.iter().collect(),
+            misoriented: memo
+                .misoriented
+                .iter()
+                .map(MisorientedPieceResult::from)
+                .collect(),
+        }
+    }
+}
+
+/// Blindfolded memo, as returned to JavaScript
+#[derive(Serialize)]
+struct MemoResult {
+    corners: PieceMemoResult,
+    edges: PieceMemoResult,
+    parity: bool,
+}
+
+impl From<&Memo> for MemoResult {
+    fn from(memo: &Memo) -> Self {
+        MemoResult {
+            corners: PieceMemoResult::from(&memo.corners),
+            edges: PieceMemoResult::from(&memo.edges),
+            parity: memo.parity,
+        }
+    }
+}
+
+/// Blindfolded difficulty figures, as returned to JavaScript
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BldStatsResult {
+    corner_targets: usize,
+    edge_targets: usize,
+    corner_cycle_breaks: usize,
+    edge_cycle_breaks: usize,
+    twisted_corners: usize,
+    flipped_edges: usize,
+    parity: bool,
+    corner_buffer_solved: bool,
+    edge_buffer_solved: bool,
+}
+
+impl From<&BldStats> for BldStatsResult {
+    fn from(stats: &BldStats) -> Self {
+        BldStatsResult {
+            corner_targets: stats.corner_targets,
+            edge_targets: stats.edge_targets,
+            corner_cycle_breaks: stats.corner_cycle_breaks,
+            edge_cycle_breaks: stats.edge_cycle_breaks,
+            twisted_corners: stats.twisted_corners,
+            flipped_edges: stats.flipped_edges,
+            parity: stats.parity,
+            corner_buffer_solved: stats.corner_buffer_solved,
+            edge_buffer_solved: stats.edge_buffer_solved,
+        }
+    }
+}
+
+/// Commutator insertion, as returned to JavaScript
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct InsertionResult {
+    position: usize,
+    commutator: String,
+    solution: String,
+    move_count: usize,
+    cancelled: usize,
+}
+
+impl From<&Insertion> for InsertionResult {
+    fn from(insertion: &Insertion) -> Self {
+        InsertionResult {
+            position: insertion.position,
+            commutator: insertion.commutator.notation(),
+            solution: OptimizedCube::moves_to_string(&insertion.solution),
+            move_count: insertion.solution.len(),
+            cancelled: insertion.cancelled,
+        }
+    }
+}
+
+/// Insertion search, as returned to JavaScript
+#[derive(Serialize)]
+struct InsertionSearchResult {
+    residue: &'static str,
+    insertions: Vec<InsertionResult>,
+}
+
+impl From<&InsertionSearch> for InsertionSearchResult {
+    fn from(search: &InsertionSearch) -> Self {
+        InsertionSearchResult {
+            residue: search.residue.name(),
+            insertions: search
+                .insertions
+                .iter()
+                .map(InsertionResult::from)
+                .collect(),
+        }
+    }
+}
+
+/// Scrambled cube for a scramble string applied to a solved cube
+fn scrambled(scramble: &str) -> Result<OptimizedCube, JsValue> {
+    let mut cube = OptimizedCube::solved();
+    cube.apply_scramble(scramble)
+        .map_err(|e| JsValue::from_str(&e))?;
+    Ok(cube)
+}
+
+/// Scramble analysis for JavaScript
+#[wasm_bindgen]
+pub struct CubeAnalysis;
+
+#[wasm_bindgen]
+impl CubeAnalysis {
+    /// Optimal cross on each of the six colors:
+    /// `[{ color, colorName, rotation: "x2", solution: "R D' F", length }, ...]`
+    #[wasm_bindgen]
+    pub fn analyse_cross(scramble: &str) -> Result<JsValue, JsValue> {
+        Self::cross_results(scramble, false)
+    }
+
+    /// Same as `analyse_cross`, with each color also solved from its four y rotations
+    #[wasm_bindgen]
+    pub fn analyse_cross_all_rotations(scramble: &str) -> Result<JsValue, JsValue> {
+        Self::cross_results(scramble, true)
+    }
+
+    /// Optimal XCross (`pairs` = 1) or XXCross (`pairs` = 2) for each color and slot
+    /// choice, shortest first: `{ complete, solutions: [{ color, colorName, slots: ["FR"],
+    /// rotation, solution, length }] }`. Stops after `timeout_ms` or `max_results`
+    /// solutions, whichever comes first; `complete` tells whether every choice was solved.
+    #[wasm_bindgen]
+    pub fn analyse_xcross(
+        scramble: &str,
+        pairs: usize,
+        timeout_ms: f64,
+        max_results: usize,
+    ) -> Result<JsValue, JsValue> {
+        let cube = scrambled(scramble)?;
+        let options = ExtendedCrossOptions {
+            max_results,
+            timeout_ms: Some(timeout_ms),
+            ..ExtendedCrossOptions::default()
+        };
+        let report = xcross::solve_extended_cross(&cube, pairs, options)
+            .map_err(|e| JsValue::from_str(&e))?;
+        let analysis = ExtendedCrossAnalysis {
+            complete: report.complete,
+            solutions: report
+                .solutions
+                .iter()
+                .map(ExtendedCrossResult::from)
+                .collect(),
+        };
+        Ok(serde_wasm_bindgen::to_value(&analysis)?)
+    }
+
+    /// Optimal Roux first block (1x2x3 on the bottom left) for all 24 orientations,
+    /// shortest first: `{ complete, solutions: [{ bottomColor, leftColor,
+    /// name: "yellow-orange", rotation, solution, length }] }`
+    #[wasm_bindgen]
+    pub fn analyse_first_block(scramble: &str, timeout_ms: f64) -> Result<JsValue, JsValue> {
+        let cube = scrambled(scramble)?;
+        let options = FirstBlockOptions {
+            timeout_ms: Some(timeout_ms),
+            ..FirstBlockOptions::default()
+        };
+        let report =
+            first_block::solve_first_blocks(&cube, options).map_err(|e| JsValue::from_str(&e))?;
+        let analysis = FirstBlockAnalysis {
+            complete: report.complete,
+            solutions: report
+                .solutions
+                .iter()
+                .map(FirstBlockResult::from)
+                .collect(),
+        };
+        Ok(serde_wasm_bindgen::to_value(&analysis)?)
+    }
+
+    /// Human-like CFOP solution with the cross on `cross_color`: `{ rotation, length,
+    /// stages: [{ name: "F2L 1 (FR)", moves: "U R U' R'", caseId: "F2L 1", length }] }`
+    /// Stages are the cross, four F2L pairs, OLL, PLL and the final AUF.
+    #[wasm_bindgen]
+    pub fn solve_cfop(scramble: &str, cross_color: u8) -> Result<JsValue, JsValue> {
+        let cube = scrambled(scramble)?;
+        let solution = cfop::solve_cfop(&cube, cross_color).map_err(|e| JsValue::from_str(&e))?;
+        Ok(serde_wasm_bindgen::to_value(&StagedSolutionResult::from(
+            &solution,
+        ))?)
+    }
+
+    /// Layer-by-layer beginner solution with the white cross at the bottom:
+    /// `{ rotation: "x2", stickers, length, stages: [{ name, explanation, moves, length,
+    /// stickers }] }`, where `stickers` is the 54-sticker state after the rotation or stage
+    #[wasm_bindgen]
+    pub fn solve_beginner(scramble: &str) -> Result<JsValue, JsValue> {
+        let cube = scrambled(scramble)?;
+        let solution = beginner::solve_beginner(&cube).map_err(|e| JsValue::from_str(&e))?;
+        Ok(serde_wasm_bindgen::to_value(
+            &BeginnerSolutionResult::from(&solution),
+        )?)
+    }
+
+    /// OLL case showing after the scramble, which must leave the first two layers
+    /// solved: `{ number: 27, caseId: "OLL 27", auf: "U'", algorithm }`, where the AUF
+    /// comes before the algorithm (number 0 and an empty algorithm for an OLL skip)
+    #[wasm_bindgen]
+    pub fn recognize_oll(scramble: &str) -> Result<JsValue, JsValue> {
+        let cube =
+            CubieCube::from_optimized(&scrambled(scramble)?).map_err(|e| JsValue::from_str(&e))?;
+        let case = last_layer::recognize_oll(&cube).map_err(|e| JsValue::from_str(&e))?;
+        Ok(serde_wasm_bindgen::to_value(&OllCaseResult::from(case))?)
+    }
+
+    /// PLL case showing after the scramble, which must leave the last layer oriented:
+    /// `{ name: "Ua", preAuf: "U2", postAuf: "", algorithm, solution }`, where the
+    /// solution is the pre-AUF, the algorithm and the post-AUF (name "Skip" for AUF only)
+    #[wasm_bindgen]
+    pub fn recognize_pll(scramble: &str) -> Result<JsValue, JsValue> {
+        let cube =
+            CubieCube::from_optimized(&scrambled(scramble)?).map_err(|e| JsValue::from_str(&e))?;
+        let case = last_layer::recognize_pll(&cube).map_err(|e| JsValue::from_str(&e))?;
+        Ok(serde_wasm_bindgen::to_value(&PllCaseResult::from(case))?)
+    }
+
+    /// Case of an algorithm set ("COLL", "CMLL", "ZBLL", "OLLCP") showing after the
+    /// scramble: `{ id: "AS3", caseId: "ZBLL AS3", preAuf: "U", postAuf: "U2", algorithm,
+    /// solution }` (id "Skip" when only a U turn is left)
+    #[wasm_bindgen]
+    pub fn recognize_case(set: &str, scramble: &str) -> Result<JsValue, JsValue> {
+        let set = AlgorithmSet::from_name(set).map_err(|e| JsValue::from_str(&e))?;
+        let case = algorithm_sets::recognize(set, &scrambled(scramble)?)
+            .map_err(|e| JsValue::from_str(&e))?;
+        Ok(serde_wasm_bindgen::to_value(&SetCaseResult::from(case))?)
+    }
+
+    /// F2L case of each slot after the scramble, which must leave the D cross solved:
+    /// `[{ slot: "FR", cornerPosition: "URF", cornerTwist: 2, edgePosition: "UR", edgeFlip: 0,
+    /// solved: false, number: 1, caseId: "F2L 1", auf: "", algorithm, solution }, ...]`
+    /// The algorithm is written for FR; the solution is the AUF and the algorithm
+    /// performed at the slot. A pair with a piece in another slot has no number.
+    #[wasm_bindgen]
+    pub fn recognize_f2l(scramble: &str) -> Result<JsValue, JsValue> {
+        let cube =
+            CubieCube::from_optimized(&scrambled(scramble)?).map_err(|e| JsValue::from_str(&e))?;
+        let cases = f2l::recognize_f2l(&cube).map_err(|e| JsValue::from_str(&e))?;
+        let results: Vec<SlotCaseResult> = cases.iter().map(SlotCaseResult::from).collect();
+        Ok(serde_wasm_bindgen::to_value(&results)?)
+    }
+
+    /// 3BLD memo of a scramble: `{ corners: { letters: "BKP", pairs: ["BK", "P"],
+    /// cycleBreaks: "", misoriented: [{ position: "DRB", orientation: 1 }] }, edges,
+    /// parity }`
+    /// Options are optional, e.g. `{ cornerBuffer: "UFR", edgeBuffer: "UF",
+    /// cornerScheme, edgeScheme }`, schemes being 24 letters in Speffz order.
+    #[wasm_bindgen]
+    pub fn blind_memo(scramble: &str, options: JsValue) -> Result<JsValue, JsValue> {
+        let memo = Self::memo(scramble, options)?;
+        Ok(serde_wasm_bindgen::to_value(&MemoResult::from(&memo))?)
+    }
+
+    /// 3BLD difficulty of a scramble: `{ cornerTargets, edgeTargets, cornerCycleBreaks,
+    /// edgeCycleBreaks, twistedCorners, flippedEdges, parity, cornerBufferSolved,
+    /// edgeBufferSolved }`, with the same options as `blind_memo`
+    #[wasm_bindgen]
+    pub fn blind_stats(scramble: &str, options: JsValue) -> Result<JsValue, JsValue> {
+        let stats = Self::memo(scramble, options)?.stats();
+        Ok(serde_wasm_bindgen::to_value(&BldStatsResult::from(&stats))?)
+    }
+
+    /// FMC insertions for a skeleton leaving a corner or edge 3-cycle or two twisted
+    /// corners: `{ residue: "corner 3-cycle", insertions: [{ position, commutator,
+    /// solution, moveCount, cancelled }] }`, up to `limit` (default 10), shortest first
+    /// `position` counts the skeleton moves before the inserted commutator.
+    #[wasm_bindgen]
+    pub fn find_insertions(
+        scramble: &str,
+        skeleton: &str,
+        limit: Option<usize>,
+    ) -> Result<JsValue, JsValue> {
+        let scramble =
+            OptimizedCube::parse_scramble(scramble).map_err(|e| JsValue::from_str(&e))?;
+        let skeleton = algorithm::parse_algorithm(skeleton).map_err(|e| JsValue::from_str(&e))?;
+        let search = insertions::find_insertions(&scramble, &skeleton, limit.unwrap_or(10))
+            .map_err(|e| JsValue::from_str(&e))?;
+        Ok(serde_wasm_bindgen::to_value(&InsertionSearchResult::from(
+            &search,
+        ))?)
+    }
+
+    /// Optimal ZZ EOLine (edges oriented, DF and DB solved) on each color, solved both
+    /// with edges oriented on F/B and, after a y, on R/L:
+    /// `[{ color, colorName, rotation: "x2 y", solution, length }, ...]`
+    #[wasm_bindgen]
+    pub fn analyse_eoline(scramble: &str) -> Result<JsValue, JsValue> {
+        Self::eo_results(scramble, EoStep::EoLine, None)
+    }
+
+    /// Optimal ZZ EOCross on each color and EO axis, like `analyse_eoline`
+    /// Colors not solved within `timeout_ms` are left out.
+    #[wasm_bindgen]
+    pub fn analyse_eocross(scramble: &str, timeout_ms: f64) -> Result<JsValue, JsValue> {
+        Self::eo_results(scramble, EoStep::EoCross, Some(timeout_ms))
+    }
+}
+
+impl CubeAnalysis {
+    fn cross_results(scramble: &str, all_rotations: bool) -> Result<JsValue, JsValue> {
+        let cube = scrambled(scramble)?;
+        let solutions =
+            cross::solve_all_crosses(&cube, all_rotations).map_err(|e| JsValue::from_str(&e))?;
+        let results: Vec<CrossResult> = solutions.iter().map(CrossResult::from).collect();
+        Ok(serde_wasm_bindgen::to_value(&results)?)
+    }
+
+    fn eo_results(
+        scramble: &str,
+        step: EoStep,
+        timeout_ms: Option<f64>,
+    ) -> Result<JsValue, JsValue> {
+        let cube = scrambled(scramble)?;
+        let solutions = eoline::solve_all_eo_steps(&cube, step, true, timeout_ms)
+            .map_err(|e| JsValue::from_str(&e))?;
+        let results: Vec<EoResult> = solutions.iter().map(EoResult::from).collect();
+        Ok(serde_wasm_bindgen::to_value(&results)?)
+    }
+
+    /// Memo of a scramble with the settings of JavaScript memo options (default if missing)
+    fn memo(scramble: &str, options: JsValue) -> Result<Memo, JsValue> {
+        let options: MemoOptions = if options.is_undefined() || options.is_null() {
+            MemoOptions::default()
+        } else {
+            serde_wasm_bindgen::from_value(options)?
+        };
+        let settings = options.settings().map_err(|e| JsValue::from_str(&e))?;
+        blindfolded::memo(&scrambled(scramble)?, &settings).map_err(|e| JsValue::from_str(&e))
+    }
+}
@@ -0,0 +1,353 @@
+// Layer-by-layer (beginner's method) teaching solver
+// Every stage uses the moves a beginner learns: cross edges one at a time, corners with
+// repeated R U R' U', the two second-layer insertions, then the yellow cross, yellow edges,
+// corner placement and R' D' R D corner twisting. Each stage keeps the sticker state it
+// leaves, so a tutorial can show the cube between stages.
+use crate::algorithm::{cancel_moves, parse_algorithm, y_rotated};
+use crate::analysis::F2L_PAIRS;
+use crate::cubie_cube::CubieCube;
+use crate::optimized_cube::{MoveCode, OptimizedCube};
+use crate::pieces::{self, Piece, PieceSpace};
+use crate::search::{self, Deadline};
+use crate::{cross, xcross::SLOT_NAMES};
+
+/// Cross edges in the order they are placed: DF, DR, DB, DL
+const CROSS_ORDER: [u8; 4] = [5, 4, 7, 6];
+const LAST_LAYER: [u8; 4] = [0, 1, 2, 3];
+const U_TURNS: [MoveCode; 3] = [MoveCode::U, MoveCode::Up, MoveCode::U2];
+/// Longest single cross edge placement (every edge takes at most 7)
+const MAX_EDGE_MOVES: usize = 8;
+/// Most R' D' R D repeats a corner can need
+const MAX_TWIST_REPEATS: usize = 4;
+
+/// First layer corner trigger for the FR slot, repeated until the corner is solved
+const CORNER_TRIGGER: &str = "R U R' U'";
+/// Second layer insertions into the FR slot, from the front and from the right
+const RIGHT_INSERT: &str = "U R U' R' U' F' U F";
+const LEFT_INSERT: &str = "U' F' U F U R U' R'";
+const YELLOW_CROSS: &str = "F R U R' U' F'";
+const YELLOW_EDGES: &str = "R U R' U R U2 R' U";
+/// Cycles the three last-layer corners other than UFR
+const CORNER_CYCLE: &str = "U R U' L' U R' U' L";
+const CORNER_TWIST: &str = "R' D' R D";
+
+/// One stage of a beginner solution with what it does and the cube after it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TeachingStage {
+    pub name: String,
+    pub explanation: String,
+    /// Moves of the stage (empty when already done)
+    pub moves: Vec<u8>,
+    /// Stickers after the stage
+    pub stickers: [u8; 54],
+}
+
+/// A full layer-by-layer solution
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BeginnerSolution {
+    /// Rotation (move codes) holding the white center at the bottom
+    pub rotation: Vec<u8>,
+    /// Stickers after the rotation, before the first stage
+    pub stickers: [u8; 54],
+    pub stages: Vec<TeachingStage>,
+}
+
+impl BeginnerSolution {
+    /// Moves of all stages, rotation excluded
+    pub fn moves(&self) -> Vec<u8> {
+        self.stages
+            .iter()
+            .flat_map(|stage| stage.moves.iter().copied())
+            .collect()
+    }
+}
+
+/// An algorithm for the FR slot performed on each of the given slots
+fn slot_algorithms(algorithm: &str, slots: &[usize]) -> Result<Vec<Vec<u8>>, String> {
+    let moves = parse_algorithm(algorithm)?;
+    Ok(slots.iter().map(|&slot| y_rotated(&moves, slot)).collect())
+}
+
+/// Single U turns, as sequences
+fn u_turns() -> Vec<Vec<u8>> {
+    U_TURNS.iter().map(|&m| vec![m as u8]).collect()
+}
+
+/// Pieces solved when their states are the solved ones
+fn all_solved(pieces: &[Piece]) -> impl Fn(&[u8]) -> bool + '_ {
+    move |states| {
+        states
+            .iter()
+            .zip(pieces)
+            .all(|(&state, piece)| state == piece.solved_state())
+    }
+}
+
+struct Solver {
+    cube: OptimizedCube,
+    stages: Vec<TeachingStage>,
+}
+
+impl Solver {
+    fn state(&self) -> Result<CubieCube, String> {
+        CubieCube::from_optimized(&self.cube)
+    }
+
+    fn stage(&mut self, name: &str, explanation: &str, moves: Vec<u8>) {
+        let moves = cancel_moves(&moves);
+        self.cube.apply_moves(&moves);
+        self.stages.push(TeachingStage {
+            name: name.to_string(),
+            explanation: explanation.to_string(),
+            moves,
+            stickers: *self.cube.stickers(),
+        });
+    }
+
+    /// Cross edges one at a time, each in the fewest moves keeping the placed ones
+    fn cross(&mut self) -> Result<(), String> {
+        let mut moves = Vec::new();
+        let mut state = self.state()?;
+        for count in 1..=CROSS_ORDER.len() {
+            let edges: Vec<Piece> = CROSS_ORDER[..count]
+                .iter()
+                .map(|&e| Piece::Edge(e))
+                .collect();
+            let space = PieceSpace::new(&edges, std::slice::from_ref(&edges));
+            let outcome = search::search(
+                &space,
+                space.state(&state),
+                MAX_EDGE_MOVES,
+                1,
+                Deadline::none(),
+            );
+            let edge_moves = outcome
+                .solutions
+                .into_iter()
+                .next()
+                .ok_or_else(|| "No cross edge placement found".to_string())?;
+            state.apply_moves(&edge_moves)?;
+            moves.extend(edge_moves);
+        }
+        self.stage(
+            "White cross",
+            "Bring each white edge down next to its center color, one at a time, without \
+             disturbing the edges already placed.",
+            moves,
+        );
+        Ok(())
+    }
+
+    /// Pieces of the slots, one slot at a time (cheapest first), using the algorithms
+    /// of the unsolved slots; `slot_pieces` gives the pieces solved for a slot
+    fn slots(
+        &mut self,
+        algorithms: &[&str],
+        slot_pieces: impl Fn(usize) -> Vec<Piece>,
+    ) -> Result<Vec<u8>, String> {
+        let mut moves = Vec::new();
+        let mut state = self.state()?;
+        let mut free: Vec<usize> = (0..4).collect();
+        loop {
+            free.retain(|&slot| {
+                !slot_pieces(slot)
+                    .iter()
+                    .all(|piece| piece.state(&state) == piece.solved_state())
+            });
+            if free.is_empty() {
+                break;
+            }
+            let mut sequences = u_turns();
+            for algorithm in algorithms {
+                sequences.extend(slot_algorithms(algorithm, &free)?);
+            }
+            let slot_moves = free
+                .iter()
+                .filter_map(|&slot| {
+                    let pieces = slot_pieces(slot);
+                    pieces::sequence_solution(&pieces, &state, &sequences, all_solved(&pieces))
+                })
+                .min_by_key(Vec::len)
+                .ok_or_else(|| format!("No solution found for slot {}", SLOT_NAMES[free[0]]))?;
+            state.apply_moves(&slot_moves)?;
+            moves.extend(slot_moves);
+        }
+        Ok(moves)
+    }
+
+    /// Last-layer pieces brought to `goal` with U turns (if `with_u_turns`) and one
+    /// algorithm performed from each side
+    fn last_layer(
+        &mut self,
+        pieces: &[Piece],
+        algorithm: &str,
+        with_u_turns: bool,
+        goal: impl Fn(&[u8]) -> bool,
+    ) -> Result<Vec<u8>, String> {
+        let mut sequences = if with_u_turns { u_turns() } else { Vec::new() };
+        sequences.extend(slot_algorithms(algorithm, &[0, 1, 2, 3])?);
+        pieces::sequence_solution(pieces, &self.state()?, &sequences, goal)
+            .ok_or_else(|| format!("No solution found with {}", algorithm))
+    }
+
+    /// Twist each last-layer corner at UFR with R' D' R D, then turn U to finish
+    fn twist_corners(&mut self) -> Result<Vec<u8>, String> {
+        let twist = parse_algorithm(CORNER_TWIST)?;
+        let mut moves = Vec::new();
+        let mut state = self.state()?;
+        for corner in 0..4 {
+            let mut repeats = 0;
+            // Until a last-layer corner sits at UFR with its yellow sticker on top
+            while state.cp[0] >= 4 || state.co[0] != 0 {
+                if repeats == MAX_TWIST_REPEATS {
+                    return Err("Corner cannot be twisted".to_string());
+                }
+                state.apply_moves(&twist)?;
+                moves.extend_from_slice(&twist);
+                repeats += 1;
+            }
+            if corner < 3 {
+                state.apply_move(MoveCode::U as u8)?;
+                moves.push(MoveCode::U as u8);
+            }
+        }
+        for turns in 0..4 {
+            if state.is_solved() {
+                return Ok(moves);
+            }
+            if turns < 3 {
+                state.apply_move(MoveCode::U as u8)?;
+                moves.push(MoveCode::U as u8);
+            }
+        }
+        Err("Last layer left unsolved".to_string())
+    }
+}
+
+/// Layer-by-layer solution with the white cross at the bottom
+pub fn solve_beginner(cube: &OptimizedCube) -> Result<BeginnerSolution, String> {
+    let rotation = cross::rotation_to_bottom(cube, OptimizedCube::WHITE)?;
+    let mut solver = Solver {
+        cube: cube.clone(),
+        stages: Vec::new(),
+    };
+    solver.cube.apply_moves(&rotation);
+    solver.state()?.verify()?;
+    let stickers = *solver.cube.stickers();
+
+    solver.cross()?;
+
+    let moves = solver.slots(&[CORNER_TRIGGER], |slot| {
+        vec![Piece::Corner(F2L_PAIRS[slot].0)]
+    })?;
+    solver.stage(
+        "First layer corners",
+        "Turn U until a white corner is above its slot, then repeat R U R' U' (from the \
+         side of that slot) until the corner is solved.",
+        moves,
+    );
+
+    let moves = solver.slots(&[RIGHT_INSERT, LEFT_INSERT], |slot| {
+        vec![Piece::Edge(F2L_PAIRS[slot].1)]
+    })?;
+    solver.stage(
+        "Second layer",
+        "Match each middle edge with its front center, then insert it to the right \
+         (U R U' R' U' F' U F) or to the left (U' L' U L U F U' F').",
+        moves,
+    );
+
+    let edges = LAST_LAYER.map(Piece::Edge);
+    let moves = solver.last_layer(&edges, YELLOW_CROSS, true, |states| {
+        states.iter().all(|&state| state % 2 == 0)
+    })?;
+    solver.stage(
+        "Yellow cross",
+        "Hold the dot, L or line shape as shown and apply F R U R' U' F' until a yellow \
+         cross appears.",
+        moves,
+    );
+
+    let moves = solver.last_layer(&edges, YELLOW_EDGES, true, all_solved(&edges))?;
+    solver.stage(
+        "Yellow edges",
+        "Turn U to match two edges with their centers, hold them at the back and right, \
+         and apply R U R' U R U2 R' U.",
+        moves,
+    );
+
+    let corners = LAST_LAYER.map(Piece::Corner);
+    let moves = solver.last_layer(&corners, CORNER_CYCLE, false, |states| {
+        states
+            .iter()
+            .zip(LAST_LAYER)
+            .all(|(&state, corner)| state / 3 == corner)
+    })?;
+    solver.stage(
+        "Yellow corners position",
+        "Hold a corner that is in its place at front right and apply U R U' L' U R' U' L \
+         until all four corners are in place.",
+        moves,
+    );
+
+    let moves = solver.twist_corners()?;
+    solver.stage(
+        "Yellow corners orientation",
+        "With an unsolved corner at front right, repeat R' D' R D until its yellow sticker \
+         is on top, then turn U to the next one. Finish by turning U to solve the cube.",
+        moves,
+    );
+
+    Ok(BeginnerSolution {
+        rotation,
+        stickers,
+        stages: solver.stages,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_beginner_solution_solves_and_records_stages() {
+        for scramble in [
+            "D2 F' R U2 B L' D R2 F U' L2 B2 R",
+            "R U R' U' F2 D' L B2 U2 R' D F' L2 U B R2 D' F2 U' L",
+            "",
+        ] {
+            let mut cube = OptimizedCube::solved();
+            cube.apply_scramble(scramble).unwrap();
+            let solution = solve_beginner(&cube).unwrap();
+            assert_eq!(solution.stages.len(), 7);
+
+            cube.apply_moves(&solution.rotation);
+            assert_eq!(cube.stickers(), &solution.stickers);
+            for stage in &solution.stages {
+                cube.apply_moves(&stage.moves);
+                assert_eq!(cube.stickers(), &stage.stickers, "{}", stage.name);
+                let state = CubieCube::from_optimized(&cube).unwrap();
+                if stage.name == "White cross" {
+                    assert_eq!(cross::cross_length(&state), 0);
+                }
+                if stage.name == "Second layer" {
+                    assert!(crate::subset_scrambler::is_f2l_solved(&state));
+                }
+            }
+            let state = CubieCube::from_optimized(&cube).unwrap();
+            assert!(state.is_solved(), "{}", scramble);
+        }
+    }
+
+    #[test]
+    fn test_beginner_solution_is_deterministic() {
+        let mut cube = OptimizedCube::solved();
+        cube.apply_scramble("F2 U' B R' D2 L U F' R2 D").unwrap();
+        let first = solve_beginner(&cube).unwrap();
+        assert_eq!(first, solve_beginner(&cube).unwrap());
+        assert!(first
+            .stages
+            .iter()
+            .all(|stage| !stage.explanation.is_empty()));
+    }
+}
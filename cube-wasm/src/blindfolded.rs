@@ -0,0 +1,380 @@
+// Blindfolded memo tracing
+// Each sticker of a corner or edge gets a letter from a letter scheme (Speffz by
+// default: the faces U L F R B D in turn, each lettered clockwise from the top-left
+// corner or top edge of the standard net). Tracing starts at the buffer: the sticker in
+// the buffer's reference slot is read, its home sticker is the first target, the sticker
+// sitting there gives the next target, and so on. When the buffer piece comes back the
+// cycle is broken into the first unsolved position (in CORNER_NAMES or EDGE_NAMES
+// order). Pieces already in place but twisted or flipped are listed separately instead
+// of being traced.
+use crate::cubie_cube::{CubieCube, CORNER_FACELETS, CORNER_NAMES, EDGE_FACELETS, EDGE_NAMES};
+use crate::optimized_cube::OptimizedCube;
+use serde::Deserialize;
+
+/// Speffz letters, shared by corners and edges
+const SPEFFZ: &str = "ABCDEFGHIJKLMNOPQRSTUVWX";
+
+/// Block of four letters of each face in the scheme, indexed by face (U D F B R L)
+const SCHEME_FACE: [usize; 6] = [0, 5, 2, 4, 3, 1];
+
+/// Letter of each corner sticker of a face within its block, indexed by sticker (0-8)
+const CORNER_SLOT: [usize; 9] = [0, 0, 1, 0, 0, 0, 3, 0, 2];
+
+/// Letter of each edge sticker of a face within its block, indexed by sticker (0-8)
+const EDGE_SLOT: [usize; 9] = [0, 0, 0, 3, 0, 1, 0, 2, 0];
+
+/// Letters for the 24 corner and 24 edge stickers, in Speffz order
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LetterScheme {
+    pub corners: [char; 24],
+    pub edges: [char; 24],
+}
+
+impl Default for LetterScheme {
+    fn default() -> Self {
+        LetterScheme::new(SPEFFZ, SPEFFZ).expect("Speffz is a valid scheme")
+    }
+}
+
+impl LetterScheme {
+    /// Scheme from 24 distinct letters for the corners and 24 for the edges, listed in
+    /// Speffz order
+    pub fn new(corners: &str, edges: &str) -> Result<LetterScheme, String> {
+        Ok(LetterScheme {
+            corners: Self::letters(corners)?,
+            edges: Self::letters(edges)?,
+        })
+    }
+
+    fn letters(scheme: &str) -> Result<[char; 24], String> {
+        let letters: Vec<char> = scheme.chars().filter(|c| !c.is_whitespace()).collect();
+        let letters: [char; 24] = letters
+            .try_into()
+            .map_err(|_| format!("Letter scheme needs 24 letters: {}", scheme))?;
+        for (i, letter) in letters.iter().enumerate() {
+            if letters[..i].contains(letter) {
+                return Err(format!("Letter scheme repeats {}", letter));
+            }
+        }
+        Ok(letters)
+    }
+
+    /// Letter of a corner sticker: orientation 0 is the U or D sticker, then clockwise
+    pub fn corner(&self, position: usize, orientation: usize) -> char {
+        let sticker = CORNER_FACELETS[position][orientation];
+        self.corners[SCHEME_FACE[sticker / 9] * 4 + CORNER_SLOT[sticker % 9]]
+    }
+
+    /// Letter of an edge sticker: orientation 0 is the reference sticker (U/D, else F/B)
+    pub fn edge(&self, position: usize, orientation: usize) -> char {
+        let sticker = EDGE_FACELETS[position][orientation];
+        self.edges[SCHEME_FACE[sticker / 9] * 4 + EDGE_SLOT[sticker % 9]]
+    }
+}
+
+/// Buffers and letters used for tracing
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemoSettings {
+    pub scheme: LetterScheme,
+    /// Corner position of the buffer (index into CORNER_NAMES)
+    pub corner_buffer: usize,
+    /// Edge position of the buffer (index into EDGE_NAMES)
+    pub edge_buffer: usize,
+}
+
+impl Default for MemoSettings {
+    /// Speffz with the UFR and UF buffers
+    fn default() -> Self {
+        MemoSettings {
+            scheme: LetterScheme::default(),
+            corner_buffer: 0,
+            edge_buffer: 1,
+        }
+    }
+}
+
+/// Memo settings from JavaScript, e.g. `{ cornerBuffer: "UFR", edgeBuffer: "DF",
+/// cornerScheme: "ABCD...", edgeScheme: "ABCD..." }`; unset fields keep the defaults
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct MemoOptions {
+    pub corner_buffer: Option<String>,
+    pub edge_buffer: Option<String>,
+    pub corner_scheme: Option<String>,
+    pub edge_scheme: Option<String>,
+}
+
+impl MemoOptions {
+    /// Settings described by these options
+    pub fn settings(&self) -> Result<MemoSettings, String> {
+        let mut settings = MemoSettings::default();
+        if let Some(name) = &self.corner_buffer {
+            settings.corner_buffer = position_named(&CORNER_NAMES, name)?;
+        }
+        if let Some(name) = &self.edge_buffer {
+            settings.edge_buffer = position_named(&EDGE_NAMES, name)?;
+        }
+        let corners = self.corner_scheme.as_deref().unwrap_or(SPEFFZ);
+        let edges = self.edge_scheme.as_deref().unwrap_or(SPEFFZ);
+        settings.scheme = LetterScheme::new(corners, edges)?;
+        Ok(settings)
+    }
+}
+
+/// Position with the given faces in any order, e.g. "UFR" for URF
+pub fn position_named(names: &[&str], name: &str) -> Result<usize, String> {
+    let key = |name: &str| {
+        let mut faces: Vec<char> = name.trim().to_ascii_uppercase().chars().collect();
+        faces.sort_unstable();
+        faces
+    };
+    let wanted = key(name);
+    names
+        .iter()
+        .position(|&candidate| key(candidate) == wanted)
+        .ok_or_else(|| format!("Unknown piece position: {}", name))
+}
+
+/// A piece in its own position but twisted or flipped
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MisorientedPiece {
+    pub position: &'static str,
+    /// Twist (corners) or flip (edges) of the piece
+    pub orientation: u8,
+}
+
+/// Traced targets of one piece type
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PieceMemo {
+    /// Targets in order, cycle breaks included
+    pub letters: Vec<char>,
+    /// First letter of each new cycle started when the buffer piece came back
+    pub cycle_breaks: Vec<char>,
+    /// Misoriented pieces in place, outside the traced cycles
+    pub misoriented: Vec<MisorientedPiece>,
+    /// The buffer piece starts solved, so tracing opens with a cycle break
+    pub buffer_solved: bool,
+}
+
+impl PieceMemo {
+    /// Letters grouped in pairs, the last one possibly alone
+    pub fn pairs(&self) -> Vec<String> {
+        self.letters
+            .chunks(2)
+            .map(|pair| pair.iter().collect())
+            .collect()
+    }
+}
+
+/// Memo of a whole cube
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Memo {
+    pub corners: PieceMemo,
+    pub edges: PieceMemo,
+    /// Odd number of corner targets, needing a parity algorithm
+    pub parity: bool,
+}
+
+/// Difficulty figures of a memo
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BldStats {
+    pub corner_targets: usize,
+    pub edge_targets: usize,
+    pub corner_cycle_breaks: usize,
+    pub edge_cycle_breaks: usize,
+    pub twisted_corners: usize,
+    pub flipped_edges: usize,
+    pub parity: bool,
+    pub corner_buffer_solved: bool,
+    pub edge_buffer_solved: bool,
+}
+
+impl Memo {
+    pub fn stats(&self) -> BldStats {
+        BldStats {
+            corner_targets: self.corners.letters.len(),
+            edge_targets: self.edges.letters.len(),
+            corner_cycle_breaks: self.corners.cycle_breaks.len(),
+            edge_cycle_breaks: self.edges.cycle_breaks.len(),
+            twisted_corners: self.corners.misoriented.len(),
+            flipped_edges: self.edges.misoriented.len(),
+            parity: self.parity,
+            corner_buffer_solved: self.corners.buffer_solved,
+            edge_buffer_solved: self.edges.buffer_solved,
+        }
+    }
+}
+
+/// Trace one piece type
+/// `pieces` and `orientations` are the permutation and orientation of the cube,
+/// `letter(position, orientation)` names a sticker.
+fn trace(
+    pieces: &[u8],
+    orientations: &[u8],
+    modulus: u8,
+    buffer: usize,
+    names: &[&'static str],
+    letter: impl Fn(usize, usize) -> char,
+) -> PieceMemo {
+    let mut memo = PieceMemo {
+        letters: Vec::new(),
+        cycle_breaks: Vec::new(),
+        misoriented: Vec::new(),
+        buffer_solved: pieces[buffer] == buffer as u8 && orientations[buffer] == 0,
+    };
+    let mut done: Vec<bool> = (0..pieces.len()).map(|i| pieces[i] == i as u8).collect();
+    for (position, &orientation) in orientations.iter().enumerate() {
+        if done[position] && orientation != 0 {
+            memo.misoriented.push(MisorientedPiece {
+                position: names[position],
+                orientation,
+            });
+        }
+    }
+    done[buffer] = true;
+    // Home sticker (piece and orientation) of the sticker at a position
+    let home = |position: usize, orientation: usize| {
+        let turned =
+            (orientation + modulus as usize - orientations[position] as usize) % modulus as usize;
+        (pieces[position] as usize, turned)
+    };
+    // Piece closing the current cycle, and the sticker read next
+    let mut cycle_start = buffer;
+    let mut current = (buffer, 0);
+    loop {
+        let (piece, orientation) = home(current.0, current.1);
+        if piece == cycle_start {
+            if cycle_start != buffer {
+                memo.letters.push(letter(piece, orientation));
+            }
+            let Some(next) = (0..pieces.len()).find(|&p| !done[p]) else {
+                break;
+            };
+            let target = letter(next, 0);
+            memo.cycle_breaks.push(target);
+            memo.letters.push(target);
+            done[next] = true;
+            cycle_start = next;
+            current = (next, 0);
+            continue;
+        }
+        memo.letters.push(letter(piece, orientation));
+        done[piece] = true;
+        current = (piece, orientation);
+    }
+    memo
+}
+
+/// Memo of a cube for the given buffers and letter scheme
+pub fn memo(cube: &OptimizedCube, settings: &MemoSettings) -> Result<Memo, String> {
+    let state = CubieCube::from_optimized(cube)?;
+    let scheme = &settings.scheme;
+    let corners = trace(
+        &state.cp,
+        &state.co,
+        3,
+        settings.corner_buffer,
+        &CORNER_NAMES,
+        |position, orientation| scheme.corner(position, orientation),
+    );
+    let edges = trace(
+        &state.ep,
+        &state.eo,
+        2,
+        settings.edge_buffer,
+        &EDGE_NAMES,
+        |position, orientation| scheme.edge(position, orientation),
+    );
+    Ok(Memo {
+        parity: corners.letters.len() % 2 == 1,
+        corners,
+        edges,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithm;
+
+    fn memo_of(scramble: &str, settings: &MemoSettings) -> Memo {
+        let mut cube = OptimizedCube::solved();
+        cube.apply_moves(&algorithm::parse_algorithm(scramble).unwrap());
+        memo(&cube, settings).unwrap()
+    }
+
+    #[test]
+    fn test_speffz_letters() {
+        let scheme = LetterScheme::default();
+        // UFR is C on U, J on F and M on R; UF is C and I
+        assert_eq!(scheme.corner(0, 0), 'C');
+        assert_eq!(scheme.corner(0, 1), 'M');
+        assert_eq!(scheme.corner(0, 2), 'J');
+        assert_eq!(scheme.corner(6, 0), 'X');
+        assert_eq!(scheme.edge(1, 0), 'C');
+        assert_eq!(scheme.edge(1, 1), 'I');
+        assert_eq!(scheme.edge(11, 0), 'T');
+        assert_eq!(scheme.edge(11, 1), 'N');
+
+        let mut seen: Vec<char> = (0..8)
+            .flat_map(|p| (0..3).map(move |o| (p, o)))
+            .map(|(p, o)| scheme.corner(p, o))
+            .collect();
+        seen.sort_unstable();
+        assert_eq!(seen.iter().collect::<String>(), SPEFFZ);
+
+        assert!(LetterScheme::new("ABC", SPEFFZ).is_err());
+        assert_eq!(position_named(&CORNER_NAMES, "ufr"), Ok(0));
+        assert!(position_named(&EDGE_NAMES, "UFR").is_err());
+    }
+
+    #[test]
+    fn test_trace() {
+        let settings = MemoSettings::default();
+        let solved = memo_of("", &settings);
+        assert!(solved.corners.letters.is_empty() && solved.edges.letters.is_empty());
+        assert!(!solved.parity);
+
+        // T permutation: swaps UFR-UBR and UR-UL
+        let t_perm = memo_of("R U R' U' R' F R2 U' R' U' R U R' F'", &settings);
+        assert_eq!(t_perm.corners.letters, vec!['B']);
+        // The UF buffer is not in the edge cycle: break into UR, shoot to UL, back to UR
+        assert_eq!(t_perm.edges.letters, vec!['B', 'D', 'B']);
+        assert_eq!(t_perm.edges.cycle_breaks, vec!['B']);
+        assert!(t_perm.parity);
+        assert_eq!(t_perm.corners.pairs(), vec!["B"]);
+
+        // U perm cycling the buffer with two other edges
+        let u_perm = memo_of("M2 U M U2 M' U M2", &settings);
+        assert!(u_perm.corners.buffer_solved && !u_perm.edges.buffer_solved);
+        assert_eq!(u_perm.edges.letters.len(), 2);
+        assert!(u_perm.edges.cycle_breaks.is_empty());
+        assert!(u_perm.corners.letters.is_empty() && !u_perm.parity);
+
+        // Sune followed by U2 twists corners in place
+        let sune = memo_of("R U R' U R U2 R' U2", &settings);
+        assert!(sune.corners.misoriented.len() >= 2);
+
+        // Superflip: every edge flipped in place
+        let superflip = memo_of(
+            "U R2 F B R B2 R U2 L B2 R U' D' R2 F R' L B2 U2 F2",
+            &settings,
+        );
+        assert_eq!(superflip.edges.misoriented.len(), 12);
+        let stats = superflip.stats();
+        assert_eq!((stats.edge_targets, stats.flipped_edges), (0, 12));
+        assert!(!stats.edge_buffer_solved && stats.corner_buffer_solved);
+
+        // With UR as the buffer the T permutation edges are a single target
+        let other = MemoOptions {
+            edge_buffer: Some("RU".to_string()),
+            ..MemoOptions::default()
+        };
+        let t_perm = memo_of(
+            "R U R' U' R' F R2 U' R' U' R U R' F'",
+            &other.settings().unwrap(),
+        );
+        assert_eq!(t_perm.edges.letters, vec!['D']);
+        assert!(t_perm.edges.cycle_breaks.is_empty());
+    }
+}
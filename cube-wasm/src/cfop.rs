@@ -0,0 +1,234 @@
+// Step-by-step CFOP solver producing a human-like solution
+// The cross is solved optimally. Each F2L pair is then inserted with U turns and the
+// basic slot triggers (R U R', F' U F, ...), which only move the pair's slot and the U
+// layer (`pieces::sequence_solution`); the cheapest remaining pair goes first and is
+// reported with its standard F2L case. The last layer uses full OLL and PLL, recognised
+// with the last-layer case tables.
+use crate::algorithm::{cancel_moves, parse_algorithm};
+use crate::analysis::{self, F2L_PAIRS};
+use crate::cross;
+use crate::cubie_cube::CubieCube;
+use crate::f2l;
+use crate::last_layer::{self, auf_moves};
+use crate::optimized_cube::{MoveCode, OptimizedCube};
+use crate::pieces::{self, Piece};
+use crate::xcross::SLOT_NAMES;
+
+/// The two turns taking each slot (FR, FL, BL, BR) to the U layer, so that
+/// `turn U* turn'` only moves that slot and the U layer
+const SLOT_TRIGGERS: [[MoveCode; 2]; 4] = [
+    [MoveCode::R, MoveCode::Fp],
+    [MoveCode::Lp, MoveCode::F],
+    [MoveCode::L, MoveCode::Bp],
+    [MoveCode::Rp, MoveCode::B],
+];
+const U_TURNS: [MoveCode; 3] = [MoveCode::U, MoveCode::Up, MoveCode::U2];
+
+/// One stage of a solution
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SolutionStage {
+    /// Stage name, e.g. "Cross", "F2L 2 (FR)" or "OLL"
+    pub name: String,
+    /// Moves of the stage (empty when skipped)
+    pub moves: Vec<u8>,
+    /// Standard case solved: "F2L 5", "OLL 27" or a PLL name ("Skip" if none)
+    /// None for the cross and the final AUF, and for a pair with a piece in another slot
+    pub case_id: Option<String>,
+}
+
+/// A full step-by-step solution
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StagedSolution {
+    /// Rotation (move codes) applied before the first stage
+    pub rotation: Vec<u8>,
+    pub stages: Vec<SolutionStage>,
+}
+
+impl StagedSolution {
+    /// Moves of all stages, rotation excluded
+    pub fn moves(&self) -> Vec<u8> {
+        self.stages
+            .iter()
+            .flat_map(|stage| stage.moves.iter().copied())
+            .collect()
+    }
+}
+
+/// Cube being solved, held as stickers so slice moves and wide moves can be applied
+struct Solver {
+    cube: OptimizedCube,
+}
+
+impl Solver {
+    fn state(&self) -> Result<CubieCube, String> {
+        CubieCube::from_optimized(&self.cube)
+    }
+
+    fn stage(&mut self, name: &str, moves: Vec<u8>, case_id: Option<String>) -> SolutionStage {
+        self.cube.apply_moves(&moves);
+        SolutionStage {
+            name: name.to_string(),
+            moves,
+            case_id,
+        }
+    }
+}
+
+/// Cheapest insertion of one F2L pair with U turns and the triggers of the free slots
+fn insert_pair(cube: &CubieCube, slot: usize, free_slots: &[usize]) -> Option<Vec<u8>> {
+    let pair = [
+        Piece::Corner(F2L_PAIRS[slot].0),
+        Piece::Edge(F2L_PAIRS[slot].1),
+    ];
+    let mut sequences: Vec<Vec<u8>> = U_TURNS.iter().map(|&m| vec![m as u8]).collect();
+    for &free in free_slots {
+        for turn in SLOT_TRIGGERS[free] {
+            for u_turn in U_TURNS {
+                sequences.push(vec![turn as u8, u_turn as u8, turn.inverse() as u8]);
+            }
+        }
+    }
+    let solved = pair.map(Piece::solved_state);
+    let moves = pieces::sequence_solution(&pair, cube, &sequences, |states| states == solved)?;
+    Some(cancel_moves(&moves))
+}
+
+/// Step-by-step CFOP solution with the cross on `cross_color`
+pub fn solve_cfop(cube: &OptimizedCube, cross_color: u8) -> Result<StagedSolution, String> {
+    let cross_solution = cross::solve_cross_for_color(cube, cross_color)?;
+    let mut solver = Solver { cube: cube.clone() };
+    solver.cube.apply_moves(&cross_solution.rotation);
+    solver.state()?.verify()?;
+    let mut stages = vec![solver.stage("Cross", cross_solution.moves, None)];
+
+    let mut free_slots: Vec<usize> = (0..4).collect();
+    for pair_number in 1..=4 {
+        let state = solver.state()?;
+        free_slots.retain(|&slot| {
+            let (corner, edge) = F2L_PAIRS[slot];
+            !analysis::is_pair_solved(&state, corner, edge)
+        });
+        let name = format!("F2L {}", pair_number);
+        let Some(&first_free) = free_slots.first() else {
+            stages.push(solver.stage(&name, Vec::new(), Some("Skip".to_string())));
+            continue;
+        };
+        let (slot, moves) = free_slots
+            .iter()
+            .filter_map(|&slot| insert_pair(&state, slot, &free_slots).map(|moves| (slot, moves)))
+            .min_by_key(|(_, moves)| moves.len())
+            .ok_or_else(|| format!("No insertion found for slot {}", SLOT_NAMES[first_free]))?;
+        let case_id = f2l::recognize_f2l(&state)?[slot]
+            .case
+            .map(|case| format!("F2L {}", case.number));
+        let name = format!("{} ({})", name, SLOT_NAMES[slot]);
+        stages.push(solver.stage(&name, moves, case_id));
+        free_slots.retain(|&free| free != slot);
+    }
+
+    let oll = last_layer::recognize_oll(&solver.state()?)?;
+    let mut moves = oll.auf_moves();
+    let case_id = match oll.algorithm() {
+        Some(algorithm) => {
+            moves.extend(parse_algorithm(algorithm)?);
+            format!("OLL {}", oll.number)
+        }
+        None => "Skip".to_string(),
+    };
+    stages.push(solver.stage("OLL", moves, Some(case_id)));
+
+    let pll = last_layer::recognize_pll(&solver.state()?)?;
+    let mut moves = Vec::new();
+    if let Some(algorithm) = pll.algorithm() {
+        moves = auf_moves(pll.pre_auf);
+        moves.extend(parse_algorithm(algorithm)?);
+    }
+    stages.push(solver.stage("PLL", moves, Some(pll.name.to_string())));
+    let auf = (0..4)
+        .map(auf_moves)
+        .find(|moves| {
+            let mut cube = solver.cube.clone();
+            cube.apply_moves(moves);
+            CubieCube::from_optimized(&cube).is_ok_and(|state| state.is_solved())
+        })
+        .ok_or_else(|| "Last layer left unsolved".to_string())?;
+    stages.push(solver.stage("AUF", auf, None));
+
+    Ok(StagedSolution {
+        rotation: cross_solution.rotation,
+        stages,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::subset_scrambler::is_f2l_solved;
+
+    #[test]
+    fn test_cfop_solves_scrambles() {
+        for scramble in [
+            "D2 F' R U2 B L' D R2 F U' L2 B2 R",
+            "R U R' U' F2 D' L B2 U2 R' D F' L2 U B R2 D' F2 U' L",
+            "F R U R' U' F'",
+            "",
+        ] {
+            let mut cube = OptimizedCube::solved();
+            cube.apply_scramble(scramble).unwrap();
+            let solution = solve_cfop(&cube, OptimizedCube::WHITE).unwrap();
+            let names: Vec<&str> = solution.stages.iter().map(|s| s.name.as_str()).collect();
+            assert_eq!(names[0], "Cross");
+            assert!(names[4].starts_with("F2L 4"));
+            assert_eq!(names.len(), 8);
+
+            cube.apply_moves(&solution.rotation);
+            for stage in &solution.stages {
+                cube.apply_moves(&stage.moves);
+                let state = CubieCube::from_optimized(&cube).unwrap();
+                if stage.name == "Cross" {
+                    assert_eq!(cross::cross_length(&state), 0, "{}", scramble);
+                }
+                if stage.name.starts_with("F2L 4") {
+                    assert!(is_f2l_solved(&state), "{}", scramble);
+                }
+            }
+            assert!(
+                CubieCube::from_optimized(&cube).unwrap().is_solved(),
+                "{}",
+                scramble
+            );
+        }
+    }
+
+    #[test]
+    fn test_cfop_case_ids() {
+        // Sune case with the yellow cross solved on the bottom
+        let mut cube = OptimizedCube::solved();
+        cube.apply_scramble("R U2 R' U' R U' R'").unwrap();
+        let solution = solve_cfop(&cube, OptimizedCube::YELLOW).unwrap();
+        let case = |name: &str| {
+            solution
+                .stages
+                .iter()
+                .find(|stage| stage.name == name)
+                .and_then(|stage| stage.case_id.clone())
+        };
+        assert!(solution.stages[..5]
+            .iter()
+            .all(|stage| stage.moves.is_empty()));
+        assert_eq!(case("OLL").as_deref(), Some("OLL 27"));
+        assert_eq!(case("PLL").as_deref(), Some("Skip"));
+
+        // A pair taken out with R U R' is the basic insertion case
+        let mut cube = OptimizedCube::solved();
+        cube.apply_scramble("R U' R'").unwrap();
+        let solution = solve_cfop(&cube, OptimizedCube::YELLOW).unwrap();
+        let pair = solution
+            .stages
+            .iter()
+            .find(|stage| !stage.moves.is_empty())
+            .unwrap();
+        assert_eq!(pair.name, "F2L 1 (FR)");
+        assert_eq!(pair.case_id.as_deref(), Some("F2L 4"));
+    }
+}
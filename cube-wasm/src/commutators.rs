@@ -0,0 +1,522 @@
+// 3-style commutator search
+// Stickers are followed through a cube labelled with its own sticker indices, so each
+// move sequence gives a permutation of the 54 facelets. Pure commutators [A, B], with
+// one side a single move and the other up to three moves, are enumerated once and
+// indexed by the three stickers they cycle. A commutator for three given stickers is
+// such a pure commutator, possibly after a setup S of up to two moves: [S: [A, B]].
+// Pure twists of two corners are found the same way from a one-corner twister against
+// a single face move.
+use crate::algorithm;
+use crate::blindfolded::position_named;
+use crate::cubie_cube::{
+    CORNER_FACELETS, CORNER_FACES, CORNER_NAMES, EDGE_FACELETS, EDGE_FACES, EDGE_NAMES,
+};
+use crate::equivalence;
+use crate::ergonomics::{self, ErgonomicsOptions};
+use crate::optimized_cube::{MoveCode, OptimizedCube};
+use std::collections::{HashMap, HashSet};
+use std::sync::OnceLock;
+
+/// Face letters by face index
+const FACE_LETTERS: [char; 6] = ['U', 'D', 'F', 'B', 'R', 'L'];
+
+/// Longest side of a pure commutator
+const MAX_INSERTION: usize = 3;
+
+/// Longest setup
+const MAX_SETUP: usize = 2;
+
+/// Sequences twisting only the URF corner, disturbing nothing but the D layer: the
+/// sledgehammer pair "R' D' R D" twice, and its inverse
+const CORNER_TWISTERS: [&str; 2] = ["R' D' R D R' D' R D", "D' R' D R D' R' D R"];
+
+/// Facelet permutation: entry `i` is the sticker that ends at position `i`
+pub(crate) type Facelets = [u8; 54];
+
+/// The two sides A and B of a pure commutator
+type Sides = (Vec<u8>, Vec<u8>);
+
+/// Piece type cycled by a commutator
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PieceKind {
+    Corner,
+    Edge,
+}
+
+/// Sticker index of a sticker named by its piece, starting with its own face: "RUF" is
+/// the R sticker of the URF corner, "FU" the F sticker of the UF edge
+pub fn sticker_named(name: &str) -> Result<(PieceKind, usize), String> {
+    let (kind, facelets, faces): (PieceKind, &[usize], &[u8]) = match name.trim().len() {
+        3 => {
+            let position = position_named(&CORNER_NAMES, name)?;
+            (
+                PieceKind::Corner,
+                &CORNER_FACELETS[position],
+                &CORNER_FACES[position],
+            )
+        }
+        2 => {
+            let position = position_named(&EDGE_NAMES, name)?;
+            (
+                PieceKind::Edge,
+                &EDGE_FACELETS[position],
+                &EDGE_FACES[position],
+            )
+        }
+        _ => return Err(format!("Unknown sticker: {}", name)),
+    };
+    let letter = name.trim().chars().next().map(|c| c.to_ascii_uppercase());
+    let orientation = faces
+        .iter()
+        .position(|&face| Some(FACE_LETTERS[face as usize]) == letter)
+        .ok_or_else(|| format!("Unknown sticker: {}", name))?;
+    Ok((kind, facelets[orientation]))
+}
+
+/// A commutator `[setup: [a, b]]`, the setup possibly empty
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Commutator {
+    pub setup: Vec<u8>,
+    pub a: Vec<u8>,
+    pub b: Vec<u8>,
+}
+
+impl Commutator {
+    /// Notation accepted by the algorithm parser, e.g. "[D: [R U R', D]]"
+    pub fn notation(&self) -> String {
+        let commutator = format!(
+            "[{}, {}]",
+            OptimizedCube::moves_to_string(&self.a),
+            OptimizedCube::moves_to_string(&self.b)
+        );
+        if self.setup.is_empty() {
+            commutator
+        } else {
+            format!(
+                "[{}: {}]",
+                OptimizedCube::moves_to_string(&self.setup),
+                commutator
+            )
+        }
+    }
+
+    /// Expanded moves, with cancellations between the parts
+    pub fn moves(&self) -> Vec<u8> {
+        let mut moves = self.setup.clone();
+        moves.extend_from_slice(&self.a);
+        moves.extend_from_slice(&self.b);
+        moves.extend(algorithm::invert_algorithm(&self.a));
+        moves.extend(algorithm::invert_algorithm(&self.b));
+        moves.extend(algorithm::invert_algorithm(&self.setup));
+        algorithm::cancel_moves(&moves)
+    }
+}
+
+/// A commutator found for three stickers, with its ranking
+#[derive(Debug, Clone, PartialEq)]
+pub struct FoundCommutator {
+    pub commutator: Commutator,
+    /// Expanded move count (slice moves count as one)
+    pub move_count: usize,
+    /// Ergonomics score of the expanded moves; lower is easier
+    pub ergonomics: f64,
+}
+
+/// Pure commutators of one piece type, indexed by cycled stickers
+struct CommutatorTable {
+    commutators: Vec<Sides>,
+    /// Stickers `[x, y, z]` (the sticker at x goes to y, y to z, z to x) to commutators
+    by_cycle: HashMap<[u8; 3], Vec<usize>>,
+}
+
+/// Facelet permutation of a move sequence
+pub(crate) fn facelets(moves: &[u8]) -> Facelets {
+    let mut cube = OptimizedCube::solved();
+    for (index, sticker) in cube.stickers_mut().iter_mut().enumerate() {
+        *sticker = index as u8;
+    }
+    cube.apply_moves(moves);
+    *cube.stickers()
+}
+
+/// Permutation of `first` followed by `second`
+pub(crate) fn then(first: &Facelets, second: &Facelets) -> Facelets {
+    let mut result = [0; 54];
+    for (position, &from) in second.iter().enumerate() {
+        result[position] = first[from as usize];
+    }
+    result
+}
+
+pub(crate) fn inverse(permutation: &Facelets) -> Facelets {
+    let mut result = [0; 54];
+    for (position, &from) in permutation.iter().enumerate() {
+        result[from as usize] = position as u8;
+    }
+    result
+}
+
+/// Moves used for commutators: face turns, and slice turns for edges
+fn move_set(kind: PieceKind) -> Vec<u8> {
+    let slices = match kind {
+        PieceKind::Corner => 0..0,
+        PieceKind::Edge => MoveCode::M as u8..MoveCode::X as u8,
+    };
+    (0..18).chain(slices).collect()
+}
+
+/// Layer turned by a face or slice move
+fn layer(code: u8) -> u8 {
+    if code < 18 {
+        code % 6
+    } else {
+        6 + (code - MoveCode::M as u8) % 3
+    }
+}
+
+/// Sequences of 1 to `max_length` moves, never turning the same layer twice in a row
+fn sequences(moves: &[u8], max_length: usize) -> Vec<Vec<u8>> {
+    let mut all: Vec<Vec<u8>> = moves.iter().map(|&m| vec![m]).collect();
+    let mut start = 0;
+    for _ in 1..max_length {
+        let end = all.len();
+        for index in start..end {
+            for &m in moves {
+                let last = *all[index].last().expect("non-empty sequence");
+                if layer(last) != layer(m) {
+                    let mut longer = all[index].clone();
+                    longer.push(m);
+                    all.push(longer);
+                }
+            }
+        }
+        start = end;
+    }
+    all
+}
+
+/// Sticker cycles `[x, y, z]` of a permutation that is a 3-cycle of pieces of one kind,
+/// listed from every moved sticker; pieces only twisted in place are no cycle
+pub(crate) fn three_cycle(permutation: &Facelets, kind: PieceKind) -> Option<Vec<[u8; 3]>> {
+    let cycled = match kind {
+        PieceKind::Corner => 9,
+        PieceKind::Edge => 6,
+    };
+    let moved: Vec<usize> = (0..54)
+        .filter(|&i| permutation[i] != i as u8)
+        .take(cycled + 1)
+        .collect();
+    if moved.len() != cycled || moved.iter().any(|&i| sticker_kind(i) != Some(kind)) {
+        return None;
+    }
+    let piece = |sticker: usize| match kind {
+        PieceKind::Corner => CORNER_FACELETS.iter().position(|f| f.contains(&sticker)),
+        PieceKind::Edge => EDGE_FACELETS.iter().position(|f| f.contains(&sticker)),
+    };
+    let destination = inverse(permutation);
+    moved
+        .iter()
+        .map(|&x| {
+            let y = destination[x];
+            let z = destination[y as usize];
+            (destination[z as usize] == x as u8 && piece(x) != piece(y as usize))
+                .then_some([x as u8, y, z])
+        })
+        .collect()
+}
+
+/// Piece type of a sticker, `None` for centers
+fn sticker_kind(sticker: usize) -> Option<PieceKind> {
+    match sticker % 9 {
+        4 => None,
+        position if position % 2 == 0 => Some(PieceKind::Corner),
+        _ => Some(PieceKind::Edge),
+    }
+}
+
+fn build_table(kind: PieceKind) -> CommutatorTable {
+    let moves = move_set(kind);
+    let singles: Vec<(u8, Facelets, Facelets)> = moves
+        .iter()
+        .map(|&b| {
+            let b_facelets = facelets(&[b]);
+            (b, b_facelets, inverse(&b_facelets))
+        })
+        .collect();
+    let mut table = CommutatorTable {
+        commutators: Vec::new(),
+        by_cycle: HashMap::new(),
+    };
+    for a in sequences(&moves, MAX_INSERTION) {
+        let a_facelets = facelets(&a);
+        let a_inverse = inverse(&a_facelets);
+        for &(b, b_facelets, b_inverse) in &singles {
+            let permutation = then(
+                &then(&then(&a_facelets, &b_facelets), &a_inverse),
+                &b_inverse,
+            );
+            let Some(cycles) = three_cycle(&permutation, kind) else {
+                continue;
+            };
+            // [b, a] is the inverse of [a, b] and cycles the other way
+            for (first, second, reversed) in
+                [(a.clone(), vec![b], false), (vec![b], a.clone(), true)]
+            {
+                let index = table.commutators.len();
+                table.commutators.push((first, second));
+                for &[x, y, z] in &cycles {
+                    let key = if reversed { [x, z, y] } else { [x, y, z] };
+                    table.by_cycle.entry(key).or_default().push(index);
+                }
+            }
+        }
+    }
+    table
+}
+
+fn table(kind: PieceKind) -> &'static CommutatorTable {
+    static CORNERS: OnceLock<CommutatorTable> = OnceLock::new();
+    static EDGES: OnceLock<CommutatorTable> = OnceLock::new();
+    match kind {
+        PieceKind::Corner => CORNERS.get_or_init(|| build_table(kind)),
+        PieceKind::Edge => EDGES.get_or_init(|| build_table(kind)),
+    }
+}
+
+/// Whether a permutation only twists corners in place
+pub(crate) fn is_corner_twist(permutation: &Facelets) -> bool {
+    let corner = |sticker: usize| CORNER_FACELETS.iter().position(|f| f.contains(&sticker));
+    let destination = inverse(permutation);
+    let mut moved = (0..54).filter(|&i| permutation[i] != i as u8).peekable();
+    moved.peek().is_some()
+        && moved.all(|i| {
+            sticker_kind(i) == Some(PieceKind::Corner)
+                && corner(destination[i] as usize) == corner(i)
+        })
+}
+
+/// Face moves relabelled by each whole-cube rotation: performing `map[m]` has the
+/// effect of the rotation, `m` and the rotation undone
+fn rotated_face_moves() -> Vec<[u8; 18]> {
+    let singles: Vec<Facelets> = (0..18).map(|m| facelets(&[m])).collect();
+    equivalence::rotations()
+        .iter()
+        .map(|rotation| {
+            let mut map = [0; 18];
+            for (code, face_move) in map.iter_mut().enumerate() {
+                let mut moves = rotation.clone();
+                moves.push(code as u8);
+                moves.extend(algorithm::invert_algorithm(rotation));
+                let effect = facelets(&moves);
+                *face_move = (0..18)
+                    .find(|&m| singles[m as usize] == effect)
+                    .expect("rotations take faces to faces");
+            }
+            map
+        })
+        .collect()
+}
+
+/// Pure commutators twisting two corners, by effect: a one-corner twister performed
+/// from any side against a single face move
+fn twist_table() -> &'static HashMap<Facelets, Vec<Sides>> {
+    static TABLE: OnceLock<HashMap<Facelets, Vec<Sides>>> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        let mut table: HashMap<Facelets, Vec<Sides>> = HashMap::new();
+        for map in rotated_face_moves() {
+            for twister in CORNER_TWISTERS {
+                let a: Vec<u8> = algorithm::parse_algorithm(twister)
+                    .expect("valid twister")
+                    .iter()
+                    .map(|&m| map[m as usize])
+                    .collect();
+                let a_facelets = facelets(&a);
+                for b in 0..18 {
+                    let b_facelets = facelets(&[b]);
+                    let effect = then(
+                        &then(&then(&a_facelets, &b_facelets), &inverse(&a_facelets)),
+                        &inverse(&b_facelets),
+                    );
+                    if is_corner_twist(&effect) {
+                        let reversed = table.entry(inverse(&effect)).or_default();
+                        reversed.push((vec![b], a.clone()));
+                        table.entry(effect).or_default().push((a.clone(), vec![b]));
+                    }
+                }
+            }
+        }
+        table
+    })
+}
+
+/// Commutators, with setups of face moves, twisting two corners in place exactly like
+/// `effect`, with no two giving the same moves
+pub(crate) fn twist_commutators(effect: &Facelets) -> Vec<Commutator> {
+    let face_moves: Vec<u8> = (0..18).collect();
+    let setups = std::iter::once(Vec::new()).chain(sequences(&face_moves, MAX_SETUP));
+    let mut found = Vec::new();
+    let mut seen = HashSet::new();
+    for setup in setups {
+        // [S: C] has the effect when C has it seen through the setup
+        let setup_facelets = facelets(&setup);
+        let key = then(&then(&inverse(&setup_facelets), effect), &setup_facelets);
+        for (a, b) in twist_table().get(&key).into_iter().flatten() {
+            let commutator = Commutator {
+                setup: setup.clone(),
+                a: a.clone(),
+                b: b.clone(),
+            };
+            if seen.insert(commutator.moves()) {
+                found.push(commutator);
+            }
+        }
+    }
+    found
+}
+
+/// Commutators whose sticker at `x` goes to `y`, `y` to `z` and `z` to `x`, with no
+/// two giving the same moves, in no particular order
+pub fn commutators_cycling(
+    kind: PieceKind,
+    [x, y, z]: [u8; 3],
+    face_moves_only: bool,
+) -> Vec<Commutator> {
+    let table = table(kind);
+    let allowed = |moves: &[u8]| !face_moves_only || moves.iter().all(|&m| m < 18);
+    let moves: Vec<u8> = move_set(kind)
+        .into_iter()
+        .filter(|&m| allowed(&[m]))
+        .collect();
+    let setups = std::iter::once(Vec::new()).chain(sequences(&moves, MAX_SETUP));
+    let mut found = Vec::new();
+    let mut seen = HashSet::new();
+    for setup in setups {
+        // Where the setup takes each sticker
+        let moved = inverse(&facelets(&setup));
+        let key = [x, y, z].map(|sticker| moved[sticker as usize]);
+        for &index in table.by_cycle.get(&key).into_iter().flatten() {
+            let (a, b) = &table.commutators[index];
+            if !allowed(a) || !allowed(b) {
+                continue;
+            }
+            let commutator = Commutator {
+                setup: setup.clone(),
+                a: a.clone(),
+                b: b.clone(),
+            };
+            if seen.insert(commutator.moves()) {
+                found.push(commutator);
+            }
+        }
+    }
+    found
+}
+
+/// Commutators sending the buffer sticker to `first`, `first` to `second` and `second`
+/// back to the buffer, shortest first, then easiest by the default ergonomics model
+/// Stickers are named like "UFR" or "RDF" (see `sticker_named`); at most `limit`
+/// commutators are returned.
+pub fn find_commutators(
+    buffer: &str,
+    first: &str,
+    second: &str,
+    limit: usize,
+) -> Result<Vec<FoundCommutator>, String> {
+    let (kind, buffer) = sticker_named(buffer)?;
+    let targets = [sticker_named(first)?, sticker_named(second)?];
+    if targets.iter().any(|&(target_kind, _)| target_kind != kind) {
+        return Err("Buffer and targets must be the same piece type".to_string());
+    }
+    let stickers = [buffer, targets[0].1, targets[1].1];
+    let pieces = stickers.map(|sticker| match kind {
+        PieceKind::Corner => CORNER_FACELETS.iter().position(|f| f.contains(&sticker)),
+        PieceKind::Edge => EDGE_FACELETS.iter().position(|f| f.contains(&sticker)),
+    });
+    if pieces[0] == pieces[1] || pieces[0] == pieces[2] || pieces[1] == pieces[2] {
+        return Err("Buffer and targets must be three different pieces".to_string());
+    }
+
+    let mut candidates: Vec<(Commutator, Vec<u8>)> =
+        commutators_cycling(kind, stickers.map(|sticker| sticker as u8), false)
+            .into_iter()
+            .map(|commutator| {
+                let moves = commutator.moves();
+                (commutator, moves)
+            })
+            .collect();
+
+    // Only commutators as short as the `limit`th shortest need an ergonomics score
+    candidates.sort_by_key(|(_, moves)| moves.len());
+    if let Some((_, longest)) = candidates.get(limit.saturating_sub(1)) {
+        let longest = longest.len();
+        candidates.retain(|(_, moves)| moves.len() <= longest);
+    }
+    let mut found = candidates
+        .into_iter()
+        .map(|(commutator, moves)| {
+            Ok(FoundCommutator {
+                commutator,
+                move_count: moves.len(),
+                ergonomics: ergonomics::analyse(&moves, &ErgonomicsOptions::default())?.score,
+            })
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    found.sort_by(|a, b| {
+        a.move_count
+            .cmp(&b.move_count)
+            .then(a.ergonomics.total_cmp(&b.ergonomics))
+    });
+    found.truncate(limit);
+    Ok(found)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Whether the moves cycle the stickers like the search promises
+    fn cycles(moves: &[u8], buffer: &str, first: &str, second: &str) -> bool {
+        let permutation = facelets(moves);
+        let [b, x, y] = [buffer, first, second].map(|name| sticker_named(name).unwrap().1);
+        permutation[x] == b as u8 && permutation[y] == x as u8 && permutation[b] == y as u8
+    }
+
+    #[test]
+    fn test_sticker_names() {
+        assert_eq!(sticker_named("UFR"), Ok((PieceKind::Corner, 8)));
+        assert_eq!(sticker_named("rfu"), Ok((PieceKind::Corner, 36)));
+        assert_eq!(sticker_named("FU"), Ok((PieceKind::Edge, 19)));
+        assert!(sticker_named("UFD").is_err());
+        assert!(sticker_named("U").is_err());
+    }
+
+    #[test]
+    fn test_find_commutators() {
+        // An edge 3-cycle in the M slice has a four-move commutator like [M', U2]
+        let shortest = ["UB", "DF"]
+            .iter()
+            .zip(["DF", "UB"])
+            .map(|(&first, second)| find_commutators("UF", first, second, 5).unwrap()[0].move_count)
+            .min();
+        assert_eq!(shortest, Some(4));
+
+        // Corner commutators, checked through the notation the parser reads
+        for (first, second) in [("UBL", "RDF"), ("LDF", "BDR"), ("FDR", "UBR")] {
+            let found = find_commutators("UFR", first, second, 10).unwrap();
+            assert!(!found.is_empty(), "{} {}", first, second);
+            assert!(found.windows(2).all(|w| w[0].move_count <= w[1].move_count));
+            for result in &found {
+                let moves = algorithm::parse_algorithm(&result.commutator.notation()).unwrap();
+                assert!(
+                    cycles(&moves, "UFR", first, second),
+                    "{}",
+                    result.commutator.notation()
+                );
+                assert!(cycles(&result.commutator.moves(), "UFR", first, second));
+            }
+        }
+
+        assert!(find_commutators("UFR", "UF", "UB", 5).is_err());
+        assert!(find_commutators("UFR", "RUF", "UBL", 5).is_err());
+    }
+}
@@ -0,0 +1,258 @@
+// Cross analysis
+// The four D-layer edges are tracked as (position, orientation) pairs, 24 values each, and a
+// breadth-first distance table over all 24^4 combinations gives the optimal cross length.
+// Other colors are handled by rotating that color to the bottom before reading the state.
+// Following decreasing distances gives an optimal solution directly, without a search.
+use crate::cubie_cube::CubieCube;
+use crate::optimized_cube::{MoveCode, OptimizedCube};
+use std::sync::OnceLock;
+
+/// Edge cubies of the D cross: DR, DF, DL, DB
+const CROSS_EDGES: [u8; 4] = [4, 5, 6, 7];
+/// Values per cross edge: 12 positions times 2 orientations
+const N_EDGE_STATES: usize = 24;
+const N_CROSS: usize = N_EDGE_STATES * N_EDGE_STATES * N_EDGE_STATES * N_EDGE_STATES;
+const N_MOVES: usize = 18;
+const UNVISITED: u8 = u8::MAX;
+
+/// Whole-cube rotation bringing each face (U, D, F, B, R, L) to the bottom
+const TO_BOTTOM: [&[MoveCode]; 6] = [
+    &[MoveCode::X2],
+    &[],
+    &[MoveCode::Xp],
+    &[MoveCode::X],
+    &[MoveCode::Z],
+    &[MoveCode::Zp],
+];
+
+/// y rotations that keep the bottom face and change the front one
+pub const Y_TURNS: [&[MoveCode]; 4] = [&[], &[MoveCode::Y], &[MoveCode::Y2], &[MoveCode::Yp]];
+
+struct CrossTables {
+    edge_move: [[u8; N_MOVES]; N_EDGE_STATES],
+    distance: Vec<u8>,
+}
+
+/// Optimal cross for one color, seen after a whole-cube rotation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CrossSolution {
+    pub color: u8,
+    /// Rotation (move codes) to do before the solution, bringing the color to the bottom
+    pub rotation: Vec<u8>,
+    /// Face moves solving the cross after the rotation
+    pub moves: Vec<u8>,
+}
+
+static TABLES: OnceLock<CrossTables> = OnceLock::new();
+
+fn tables() -> &'static CrossTables {
+    TABLES.get_or_init(CrossTables::build)
+}
+
+impl CrossTables {
+    fn build() -> CrossTables {
+        // A move takes the cubie at position move.ep[i] to position i
+        let mut edge_move = [[0u8; N_MOVES]; N_EDGE_STATES];
+        let move_cubes = (0..N_MOVES as u8).map(|m| CubieCube::move_cube(m).expect("face move"));
+        for (m, move_cube) in move_cubes.enumerate() {
+            for position in 0..12 {
+                let source = move_cube.ep[position] as usize;
+                for flip in 0..2 {
+                    edge_move[source * 2 + flip][m] =
+                        (position * 2 + (flip ^ move_cube.eo[position] as usize)) as u8;
+                }
+            }
+        }
+
+        let mut distance = vec![UNVISITED; N_CROSS];
+        let solved = cross_index(&CubieCube::solved());
+        distance[solved] = 0;
+        let mut frontier = vec![solved];
+        let mut depth = 0;
+        while !frontier.is_empty() {
+            let mut next = Vec::new();
+            for &index in &frontier {
+                for m in 0..N_MOVES {
+                    let target = move_index(&edge_move, index, m);
+                    if distance[target] == UNVISITED {
+                        distance[target] = depth + 1;
+                        next.push(target);
+                    }
+                }
+            }
+            frontier = next;
+            depth += 1;
+        }
+        CrossTables {
+            edge_move,
+            distance,
+        }
+    }
+}
+
+/// Apply move `m` to every edge of a cross index
+fn move_index(edge_move: &[[u8; N_MOVES]; N_EDGE_STATES], index: usize, m: usize) -> usize {
+    let mut rest = index;
+    let mut result = 0;
+    let mut scale = 1;
+    for _ in 0..CROSS_EDGES.len() {
+        let value = rest % N_EDGE_STATES;
+        rest /= N_EDGE_STATES;
+        result += edge_move[value][m] as usize * scale;
+        scale *= N_EDGE_STATES;
+    }
+    result
+}
+
+/// Index of the cross edges' positions and orientations
+fn cross_index(cube: &CubieCube) -> usize {
+    CROSS_EDGES.iter().rev().fold(0, |index, &piece| {
+        let position = cube
+            .ep
+            .iter()
+            .position(|&p| p == piece)
+            .expect("every edge is placed");
+        index * N_EDGE_STATES + position * 2 + cube.eo[position] as usize
+    })
+}
+
+/// Optimal number of face moves to solve the D cross
+pub fn cross_length(cube: &CubieCube) -> u8 {
+    tables().distance[cross_index(cube)]
+}
+
+/// Optimal face move solution of the D cross
+pub fn solve_cross(cube: &CubieCube) -> Vec<u8> {
+    let tables = tables();
+    let mut index = cross_index(cube);
+    let mut solution = Vec::new();
+    while tables.distance[index] > 0 {
+        let (m, next) = (0..N_MOVES)
+            .map(|m| (m, move_index(&tables.edge_move, index, m)))
+            .find(|&(_, next)| tables.distance[next] < tables.distance[index])
+            .expect("some move gets closer to the cross");
+        solution.push(m as u8);
+        index = next;
+    }
+    solution
+}
+
+/// Rotation (move codes) that brings the center of `color` to the bottom
+pub fn rotation_to_bottom(cube: &OptimizedCube, color: u8) -> Result<Vec<u8>, String> {
+    let face = (0..6)
+        .find(|&face| cube.stickers()[face * 9 + 4] == color)
+        .ok_or_else(|| format!("No center with color {}", color))?;
+    Ok(TO_BOTTOM[face].iter().map(|&m| m as u8).collect())
+}
+
+/// Cubie state seen after a whole-cube rotation
+pub fn rotated_state(cube: &OptimizedCube, rotation: &[u8]) -> Result<CubieCube, String> {
+    let mut rotated = cube.clone();
+    rotated.apply_moves(rotation);
+    CubieCube::from_optimized(&rotated)
+}
+
+/// Cubie state seen with the center of `color` at the bottom
+pub fn with_color_down(cube: &OptimizedCube, color: u8) -> Result<CubieCube, String> {
+    rotated_state(cube, &rotation_to_bottom(cube, color)?)
+}
+
+/// Optimal cross length on the given color
+pub fn cross_length_for_color(cube: &OptimizedCube, color: u8) -> Result<u8, String> {
+    Ok(cross_length(&with_color_down(cube, color)?))
+}
+
+/// Optimal cross on the given color, with the rotation putting it at the bottom
+pub fn solve_cross_for_color(cube: &OptimizedCube, color: u8) -> Result<CrossSolution, String> {
+    let rotation = rotation_to_bottom(cube, color)?;
+    let moves = solve_cross(&rotated_state(cube, &rotation)?);
+    Ok(CrossSolution {
+        color,
+        rotation,
+        moves,
+    })
+}
+
+/// Optimal crosses on all six colors (in color order)
+/// With `all_rotations`, every color is also solved from its four y rotations, so each
+/// cross appears once per front face.
+pub fn solve_all_crosses(
+    cube: &OptimizedCube,
+    all_rotations: bool,
+) -> Result<Vec<CrossSolution>, String> {
+    let y_turns = if all_rotations {
+        &Y_TURNS[..]
+    } else {
+        &Y_TURNS[..1]
+    };
+    let mut solutions = Vec::new();
+    for color in 0..6 {
+        let to_bottom = rotation_to_bottom(cube, color)?;
+        for y_turn in y_turns {
+            let mut rotation = to_bottom.clone();
+            rotation.extend(y_turn.iter().map(|&m| m as u8));
+            let moves = solve_cross(&rotated_state(cube, &rotation)?);
+            solutions.push(CrossSolution {
+                color,
+                rotation,
+                moves,
+            });
+        }
+    }
+    Ok(solutions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cross_length() {
+        assert_eq!(cross_length(&CubieCube::solved()), 0);
+        let parse = |s: &str| CubieCube::from_moves(&OptimizedCube::parse_scramble(s).unwrap());
+        assert_eq!(cross_length(&parse("U R2 L2").unwrap()), 2);
+        assert_eq!(cross_length(&parse("F R D'").unwrap()), 3);
+        // U layer moves do not count
+        assert_eq!(cross_length(&parse("R U2 D").unwrap()), 2);
+        // No cross needs more than 8 moves
+        assert!(tables().distance.iter().all(|&d| d <= 8 || d == UNVISITED));
+    }
+
+    #[test]
+    fn test_cross_length_for_each_color() {
+        let mut cube = OptimizedCube::solved();
+        cube.apply_scramble("F2").unwrap();
+        // F2 only leaves the blue cross intact
+        assert_eq!(cross_length_for_color(&cube, OptimizedCube::WHITE), Ok(1));
+        assert_eq!(cross_length_for_color(&cube, OptimizedCube::YELLOW), Ok(1));
+        assert_eq!(cross_length_for_color(&cube, OptimizedCube::GREEN), Ok(1));
+        assert_eq!(cross_length_for_color(&cube, OptimizedCube::BLUE), Ok(0));
+        assert_eq!(cross_length_for_color(&cube, OptimizedCube::RED), Ok(1));
+        assert_eq!(cross_length_for_color(&cube, OptimizedCube::ORANGE), Ok(1));
+        assert!(cross_length_for_color(&cube, 9).is_err());
+    }
+
+    #[test]
+    fn test_solve_all_crosses() {
+        let mut cube = OptimizedCube::solved();
+        cube.apply_scramble("D2 F' R U2 B L' D R2 F U' L2 B2 R")
+            .unwrap();
+        let solutions = solve_all_crosses(&cube, true).unwrap();
+        assert_eq!(solutions.len(), 24);
+        for solution in &solutions {
+            let length = cross_length_for_color(&cube, solution.color).unwrap();
+            assert_eq!(solution.moves.len(), length as usize);
+
+            let mut solved = cube.clone();
+            solved.apply_moves(&solution.rotation);
+            solved.apply_moves(&solution.moves);
+            assert_eq!(cross_length_for_color(&solved, solution.color), Ok(0));
+        }
+        // Without rotations there is one solution per color, starting with x2 for white
+        let solutions = solve_all_crosses(&cube, false).unwrap();
+        assert_eq!(solutions.len(), 6);
+        assert_eq!(solutions[0].rotation, vec![MoveCode::X2 as u8]);
+        assert!(solutions[1].rotation.is_empty());
+        assert_eq!(solutions[2], solve_cross_for_color(&cube, 2).unwrap());
+    }
+}
@@ -0,0 +1,594 @@
+// Cubie-level Rubik's cube representation used by the solvers and scramblers
+// Corners: URF, UFL, ULB, UBR, DFR, DLF, DBL, DRB (0-7)
+// Edges: UR, UF, UL, UB, DR, DF, DL, DB, FR, FL, BL, BR (0-11)
+// cp/ep hold which cubie sits in each position, co/eo its orientation there
+// Converts to and from the 54-sticker OptimizedCube buffer
+use crate::optimized_cube::OptimizedCube;
+use crate::rng::Rng;
+
+/// Face indices, matching the OptimizedCube layout
+const U: u8 = 0;
+const D: u8 = 1;
+const F: u8 = 2;
+const B: u8 = 3;
+const R: u8 = 4;
+const L: u8 = 5;
+
+/// Sticker indices of each corner position, starting with the U/D sticker, clockwise
+pub const CORNER_FACELETS: [[usize; 3]; 8] = [
+    [8, 36, 20],  // URF
+    [6, 18, 47],  // UFL
+    [0, 45, 29],  // ULB
+    [2, 27, 38],  // UBR
+    [11, 26, 42], // DFR
+    [9, 53, 24],  // DLF
+    [15, 35, 51], // DBL
+    [17, 44, 33], // DRB
+];
+
+/// Sticker indices of each edge position, starting with the reference sticker
+pub const EDGE_FACELETS: [[usize; 2]; 12] = [
+    [5, 37],  // UR
+    [7, 19],  // UF
+    [3, 46],  // UL
+    [1, 28],  // UB
+    [14, 43], // DR
+    [10, 25], // DF
+    [12, 52], // DL
+    [16, 34], // DB
+    [23, 39], // FR
+    [21, 50], // FL
+    [32, 48], // BL
+    [30, 41], // BR
+];
+
+/// Home faces of each corner cubie, in the same order as CORNER_FACELETS
+pub const CORNER_FACES: [[u8; 3]; 8] = [
+    [U, R, F],
+    [U, F, L],
+    [U, L, B],
+    [U, B, R],
+    [D, F, R],
+    [D, L, F],
+    [D, B, L],
+    [D, R, B],
+];
+
+/// Home faces of each edge cubie, in the same order as EDGE_FACELETS
+pub const EDGE_FACES: [[u8; 2]; 12] = [
+    [U, R],
+    [U, F],
+    [U, L],
+    [U, B],
+    [D, R],
+    [D, F],
+    [D, L],
+    [D, B],
+    [F, R],
+    [F, L],
+    [B, L],
+    [B, R],
+];
+
+/// Piece names for display, indexed like the positions above
+pub const CORNER_NAMES: [&str; 8] = ["URF", "UFL", "ULB", "UBR", "DFR", "DLF", "DBL", "DRB"];
+pub const EDGE_NAMES: [&str; 12] = [
+    "UR", "UF", "UL", "UB", "DR", "DF", "DL", "DB", "FR", "FL", "BL", "BR",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CubieCube {
+    pub cp: [u8; 8],
+    pub co: [u8; 8],
+    pub ep: [u8; 12],
+    pub eo: [u8; 12],
+}
+
+/// Clockwise quarter turns of each face in OptimizedCube face order (U, D, F, B, R, L)
+/// Each entry lists the cubie that moves into every position ("replaced by" form)
+const FACE_TURNS: [CubieCube; 6] = [
+    // U
+    CubieCube {
+        cp: [3, 0, 1, 2, 4, 5, 6, 7],
+        co: [0; 8],
+        ep: [3, 0, 1, 2, 4, 5, 6, 7, 8, 9, 10, 11],
+        eo: [0; 12],
+    },
+    // D
+    CubieCube {
+        cp: [0, 1, 2, 3, 5, 6, 7, 4],
+        co: [0; 8],
+        ep: [0, 1, 2, 3, 5, 6, 7, 4, 8, 9, 10, 11],
+        eo: [0; 12],
+    },
+    // F
+    CubieCube {
+        cp: [1, 5, 2, 3, 0, 4, 6, 7],
+        co: [1, 2, 0, 0, 2, 1, 0, 0],
+        ep: [0, 9, 2, 3, 4, 8, 6, 7, 1, 5, 10, 11],
+        eo: [0, 1, 0, 0, 0, 1, 0, 0, 1, 1, 0, 0],
+    },
+    // B
+    CubieCube {
+        cp: [0, 1, 3, 7, 4, 5, 2, 6],
+        co: [0, 0, 1, 2, 0, 0, 2, 1],
+        ep: [0, 1, 2, 11, 4, 5, 6, 10, 8, 9, 3, 7],
+        eo: [0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 1, 1],
+    },
+    // R
+    CubieCube {
+        cp: [4, 1, 2, 0, 7, 5, 6, 3],
+        co: [2, 0, 0, 1, 1, 0, 0, 2],
+        ep: [8, 1, 2, 3, 11, 5, 6, 7, 4, 9, 10, 0],
+        eo: [0; 12],
+    },
+    // L
+    CubieCube {
+        cp: [0, 2, 6, 3, 4, 1, 5, 7],
+        co: [0, 1, 2, 0, 0, 2, 1, 0],
+        ep: [0, 1, 10, 3, 4, 5, 9, 7, 8, 2, 6, 11],
+        eo: [0; 12],
+    },
+];
+
+impl Default for CubieCube {
+    fn default() -> Self {
+        Self::solved()
+    }
+}
+
+impl CubieCube {
+    /// Create a cubie cube in solved state
+    pub const fn solved() -> CubieCube {
+        CubieCube {
+            cp: [0, 1, 2, 3, 4, 5, 6, 7],
+            co: [0; 8],
+            ep: [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11],
+            eo: [0; 12],
+        }
+    }
+
+    /// Check if all cubies are in place and oriented
+    pub fn is_solved(&self) -> bool {
+        *self == Self::solved()
+    }
+
+    /// Cubie cube for a face move code (0-17); other codes are rejected
+    pub fn move_cube(move_code: u8) -> Result<CubieCube, String> {
+        if move_code >= 18 {
+            return Err(format!("Not a face move: {}", move_code));
+        }
+        let turn = FACE_TURNS[(move_code % 6) as usize];
+        let mut result = turn;
+        for _ in 0..Self::quarter_turns(move_code) - 1 {
+            result = result.multiply(&turn);
+        }
+        Ok(result)
+    }
+
+    /// Number of clockwise quarter turns a face move code stands for
+    pub(crate) fn quarter_turns(move_code: u8) -> usize {
+        match move_code / 6 {
+            0 => 1,
+            1 => 3,
+            _ => 2,
+        }
+    }
+
+    /// Compose two cube states: the result is `self` followed by `other`
+    pub fn multiply(&self, other: &CubieCube) -> CubieCube {
+        let mut result = CubieCube::solved();
+        for i in 0..8 {
+            let from = other.cp[i] as usize;
+            result.cp[i] = self.cp[from];
+            result.co[i] = (self.co[from] + other.co[i]) % 3;
+        }
+        for i in 0..12 {
+            let from = other.ep[i] as usize;
+            result.ep[i] = self.ep[from];
+            result.eo[i] = (self.eo[from] + other.eo[i]) % 2;
+        }
+        result
+    }
+
+    /// Inverse state: applying it after `self` returns to solved
+    pub fn inverse(&self) -> CubieCube {
+        let mut result = CubieCube::solved();
+        for i in 0..8 {
+            let piece = self.cp[i] as usize;
+            result.cp[piece] = i as u8;
+            result.co[piece] = (3 - self.co[i]) % 3;
+        }
+        for i in 0..12 {
+            let piece = self.ep[i] as usize;
+            result.ep[piece] = i as u8;
+            result.eo[piece] = self.eo[i];
+        }
+        result
+    }
+
+    /// Apply a face move code (0-17) in place
+    pub fn apply_move(&mut self, move_code: u8) -> Result<(), String> {
+        let turn = Self::move_cube(move_code)?;
+        *self = self.multiply(&turn);
+        Ok(())
+    }
+
+    /// Apply a sequence of face move codes in place
+    pub fn apply_moves(&mut self, moves: &[u8]) -> Result<(), String> {
+        for &move_code in moves {
+            self.apply_move(move_code)?;
+        }
+        Ok(())
+    }
+
+    /// Build a cubie cube from a move sequence applied to the solved state
+    pub fn from_moves(moves: &[u8]) -> Result<CubieCube, String> {
+        let mut cube = CubieCube::solved();
+        cube.apply_moves(moves)?;
+        Ok(cube)
+    }
+
+    /// Read the cubie state from a sticker cube
+    /// Colors are interpreted relative to the centers, so whole-cube rotations are
+    /// seen from the current orientation. Fails for impossible sticker patterns.
+    pub fn from_optimized(cube: &OptimizedCube) -> Result<CubieCube, String> {
+        let stickers = cube.stickers();
+        let mut face_of_color = [u8::MAX; 256];
+        for face in 0..6u8 {
+            let center = stickers[face as usize * 9 + 4] as usize;
+            if face_of_color[center] != u8::MAX {
+                return Err("Invalid cube state: duplicate center colors".to_string());
+            }
+            face_of_color[center] = face;
+        }
+        let face_at = |index: usize| face_of_color[stickers[index] as usize];
+
+        let mut result = CubieCube::solved();
+        for (position, facelets) in CORNER_FACELETS.iter().enumerate() {
+            let twist = (0..3)
+                .find(|&ori| matches!(face_at(facelets[ori]), U | D))
+                .ok_or_else(|| format!("Invalid corner at {}", CORNER_NAMES[position]))?;
+            let first = face_at(facelets[(twist + 1) % 3]);
+            let second = face_at(facelets[(twist + 2) % 3]);
+            let piece = CORNER_FACES
+                .iter()
+                .position(|faces| faces[1] == first && faces[2] == second)
+                .ok_or_else(|| format!("Invalid corner at {}", CORNER_NAMES[position]))?;
+            result.cp[position] = piece as u8;
+            result.co[position] = twist as u8;
+        }
+        for (position, facelets) in EDGE_FACELETS.iter().enumerate() {
+            let first = face_at(facelets[0]);
+            let second = face_at(facelets[1]);
+            let (piece, flip) = EDGE_FACES
+                .iter()
+                .enumerate()
+                .find_map(|(piece, faces)| {
+                    if faces[0] == first && faces[1] == second {
+                        Some((piece, 0))
+                    } else if faces[0] == second && faces[1] == first {
+                        Some((piece, 1))
+                    } else {
+                        None
+                    }
+                })
+                .ok_or_else(|| format!("Invalid edge at {}", EDGE_NAMES[position]))?;
+            result.ep[position] = piece as u8;
+            result.eo[position] = flip;
+        }
+        result.verify()?;
+        Ok(result)
+    }
+
+    /// Convert to a sticker cube in the standard orientation
+    pub fn to_optimized(&self) -> OptimizedCube {
+        let mut cube = OptimizedCube::solved();
+        let stickers = cube.stickers_mut();
+        for (position, facelets) in CORNER_FACELETS.iter().enumerate() {
+            let piece = self.cp[position] as usize;
+            let twist = self.co[position] as usize;
+            for (k, &face) in CORNER_FACES[piece].iter().enumerate() {
+                stickers[facelets[(k + twist) % 3]] = face;
+            }
+        }
+        for (position, facelets) in EDGE_FACELETS.iter().enumerate() {
+            let piece = self.ep[position] as usize;
+            let flip = self.eo[position] as usize;
+            for (k, &face) in EDGE_FACES[piece].iter().enumerate() {
+                stickers[facelets[(k + flip) % 2]] = face;
+            }
+        }
+        cube
+    }
+
+    /// Check that this is a reachable cube state
+    pub fn verify(&self) -> Result<(), String> {
+        let mut corner_seen = [false; 8];
+        for &piece in &self.cp {
+            if piece >= 8 || corner_seen[piece as usize] {
+                return Err("Invalid cube state: corners are not a permutation".to_string());
+            }
+            corner_seen[piece as usize] = true;
+        }
+        let mut edge_seen = [false; 12];
+        for &piece in &self.ep {
+            if piece >= 12 || edge_seen[piece as usize] {
+                return Err("Invalid cube state: edges are not a permutation".to_string());
+            }
+            edge_seen[piece as usize] = true;
+        }
+        if self.co.iter().any(|&ori| ori >= 3) || self.co.iter().sum::<u8>() % 3 != 0 {
+            return Err("Invalid cube state: twisted corner".to_string());
+        }
+        if self.eo.iter().any(|&ori| ori >= 2) || self.eo.iter().sum::<u8>() % 2 != 0 {
+            return Err("Invalid cube state: flipped edge".to_string());
+        }
+        if self.corner_parity() != self.edge_parity() {
+            return Err("Invalid cube state: parity error".to_string());
+        }
+        Ok(())
+    }
+
+    /// Permutation parity of the corners (0 = even, 1 = odd)
+    pub fn corner_parity(&self) -> u8 {
+        permutation_parity(&self.cp)
+    }
+
+    /// Permutation parity of the edges (0 = even, 1 = odd)
+    pub fn edge_parity(&self) -> u8 {
+        permutation_parity(&self.ep)
+    }
+
+    /// Uniformly random reachable cube state
+    pub fn random(rng: &mut Rng) -> CubieCube {
+        let mut cube = CubieCube::solved();
+        rng.shuffle(&mut cube.cp);
+        rng.shuffle(&mut cube.ep);
+        if cube.corner_parity() != cube.edge_parity() {
+            cube.ep.swap(10, 11);
+        }
+        for i in 0..7 {
+            cube.co[i] = rng.below(3) as u8;
+        }
+        cube.co[7] = (3 - cube.co[..7].iter().sum::<u8>() % 3) % 3;
+        for i in 0..11 {
+            cube.eo[i] = rng.below(2) as u8;
+        }
+        cube.eo[11] = cube.eo[..11].iter().sum::<u8>() % 2;
+        cube
+    }
+
+    // Coordinates used by the two-phase solver
+
+    /// Corner orientation coordinate (0-2186)
+    pub fn twist(&self) -> u16 {
+        self.co[..7]
+            .iter()
+            .fold(0u16, |acc, &ori| acc * 3 + ori as u16)
+    }
+
+    pub fn set_twist(&mut self, mut twist: u16) {
+        let mut total = 0;
+        for i in (0..7).rev() {
+            self.co[i] = (twist % 3) as u8;
+            total += self.co[i];
+            twist /= 3;
+        }
+        self.co[7] = (3 - total % 3) % 3;
+    }
+
+    /// Edge orientation coordinate (0-2047)
+    pub fn flip(&self) -> u16 {
+        self.eo[..11]
+            .iter()
+            .fold(0u16, |acc, &ori| acc * 2 + ori as u16)
+    }
+
+    pub fn set_flip(&mut self, mut flip: u16) {
+        let mut total = 0;
+        for i in (0..11).rev() {
+            self.eo[i] = (flip % 2) as u8;
+            total += self.eo[i];
+            flip /= 2;
+        }
+        self.eo[11] = total % 2;
+    }
+
+    /// Positions of the FR, FL, BL, BR slice edges, ignoring their order (0-494)
+    pub fn slice(&self) -> u16 {
+        let mut index = 0;
+        let mut found = 0;
+        for position in (0..12).rev() {
+            if self.ep[position] >= 8 {
+                index += binomial(11 - position, found + 1);
+                found += 1;
+            }
+        }
+        index as u16
+    }
+
+    pub fn set_slice(&mut self, slice: u16) {
+        let mut remaining = slice as usize;
+        let mut left = 4;
+        let mut next_other = 0;
+        for (position, piece) in self.ep.iter_mut().enumerate() {
+            if left > 0 && remaining >= binomial(11 - position, left) {
+                remaining -= binomial(11 - position, left);
+                *piece = 12 - left as u8;
+                left -= 1;
+            } else {
+                *piece = next_other;
+                next_other += 1;
+            }
+        }
+    }
+
+    /// Corner permutation coordinate (0-40319)
+    pub fn corner_permutation(&self) -> u16 {
+        permutation_rank(&self.cp) as u16
+    }
+
+    pub fn set_corner_permutation(&mut self, rank: u16) {
+        permutation_unrank(rank as usize, &mut self.cp);
+    }
+
+    /// Permutation of the eight U/D layer edges, valid once the slice edges are home (0-40319)
+    pub fn ud_edge_permutation(&self) -> u16 {
+        permutation_rank(&self.ep[..8]) as u16
+    }
+
+    pub fn set_ud_edge_permutation(&mut self, rank: u16) {
+        permutation_unrank(rank as usize, &mut self.ep[..8]);
+    }
+
+    /// Permutation of the four slice edges, valid once they are in the slice (0-23)
+    pub fn slice_permutation(&self) -> u16 {
+        let mut slice = [0u8; 4];
+        for (target, &piece) in slice.iter_mut().zip(&self.ep[8..]) {
+            *target = piece.wrapping_sub(8);
+        }
+        permutation_rank(&slice) as u16
+    }
+
+    pub fn set_slice_permutation(&mut self, rank: u16) {
+        let mut slice = [0u8; 4];
+        permutation_unrank(rank as usize, &mut slice);
+        for (target, &piece) in self.ep[8..].iter_mut().zip(&slice) {
+            *target = piece + 8;
+        }
+    }
+}
+
+/// Binomial coefficient C(n, k), zero when k > n
+pub(crate) fn binomial(n: usize, k: usize) -> usize {
+    if k > n {
+        return 0;
+    }
+    let mut result = 1;
+    for i in 0..k {
+        result = result * (n - i) / (i + 1);
+    }
+    result
+}
+
+/// Parity of a permutation of 0..n (0 = even, 1 = odd)
+pub(crate) fn permutation_parity(perm: &[u8]) -> u8 {
+    let mut inversions = 0;
+    for i in 0..perm.len() {
+        for j in i + 1..perm.len() {
+            if perm[i] > perm[j] {
+                inversions += 1;
+            }
+        }
+    }
+    (inversions % 2) as u8
+}
+
+/// Lexicographic rank of a permutation of the values it contains
+pub(crate) fn permutation_rank(perm: &[u8]) -> usize {
+    let mut rank = 0;
+    for i in 0..perm.len() {
+        let smaller = perm[i + 1..].iter().filter(|&&p| p < perm[i]).count();
+        rank = rank * (perm.len() - i) + smaller;
+    }
+    rank
+}
+
+/// Inverse of `permutation_rank`, producing a permutation of 0..n
+pub(crate) fn permutation_unrank(mut rank: usize, perm: &mut [u8]) {
+    let n = perm.len();
+    let mut digits = vec![0usize; n];
+    for i in (0..n).rev() {
+        let base = n - i;
+        digits[i] = rank % base;
+        rank /= base;
+    }
+    let mut available: Vec<u8> = (0..n as u8).collect();
+    for (target, digit) in perm.iter_mut().zip(digits) {
+        *target = available.remove(digit);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_face_moves_match_sticker_moves() {
+        for move_code in 0..18u8 {
+            let mut stickers = OptimizedCube::solved();
+            stickers.apply_moves(&[4, 0, move_code]);
+            let cubie = CubieCube::from_moves(&[4, 0, move_code]).unwrap();
+            assert_eq!(CubieCube::from_optimized(&stickers).unwrap(), cubie);
+            assert_eq!(cubie.to_optimized(), stickers);
+        }
+    }
+
+    #[test]
+    fn test_scramble_round_trip() {
+        let scramble = OptimizedCube::parse_scramble("R U R' U' F2 D L' B2 R2 U F' L D2").unwrap();
+        let mut stickers = OptimizedCube::solved();
+        stickers.apply_moves(&scramble);
+        let cubie = CubieCube::from_optimized(&stickers).unwrap();
+        assert_eq!(cubie, CubieCube::from_moves(&scramble).unwrap());
+        assert!(cubie.verify().is_ok());
+        assert!(cubie.multiply(&cubie.inverse()).is_solved());
+        assert!(cubie.inverse().multiply(&cubie).is_solved());
+    }
+
+    #[test]
+    fn test_from_optimized_reads_relative_to_centers() {
+        // A wide move leaves the cube solved relative to its own centers' frame
+        let mut cube = OptimizedCube::solved();
+        cube.apply_scramble("Rw L'").unwrap();
+        assert!(CubieCube::from_optimized(&cube).unwrap().is_solved());
+    }
+
+    #[test]
+    fn test_invalid_states_are_rejected() {
+        let mut cube = OptimizedCube::solved();
+        cube.stickers_mut().swap(8, 36); // twist one corner
+        assert!(CubieCube::from_optimized(&cube).is_err());
+
+        let mut swapped = CubieCube::solved();
+        swapped.ep.swap(0, 1);
+        assert!(swapped.verify().is_err());
+    }
+
+    #[test]
+    fn test_coordinates_round_trip() {
+        let mut cube = CubieCube::solved();
+        for twist in [0, 1, 1000, 2186] {
+            cube.set_twist(twist);
+            assert_eq!(cube.twist(), twist);
+        }
+        for flip in [0, 1, 1000, 2047] {
+            cube.set_flip(flip);
+            assert_eq!(cube.flip(), flip);
+        }
+        for slice in [0, 1, 250, 494] {
+            cube.set_slice(slice);
+            assert_eq!(cube.slice(), slice);
+        }
+        for rank in [0, 1, 20000, 40319] {
+            cube.set_corner_permutation(rank);
+            assert_eq!(cube.corner_permutation(), rank);
+        }
+        assert_eq!(CubieCube::solved().slice(), 0);
+        assert_eq!(CubieCube::solved().corner_permutation(), 0);
+    }
+
+    #[test]
+    fn test_random_states_are_valid() {
+        let mut rng = Rng::new(7);
+        for _ in 0..200 {
+            let cube = CubieCube::random(&mut rng);
+            assert!(cube.verify().is_ok());
+            assert_eq!(
+                CubieCube::from_optimized(&cube.to_optimized()).unwrap(),
+                cube
+            );
+        }
+    }
+}
@@ -0,0 +1,180 @@
+// Group-theoretic analysis of a move sequence: order and cycle structure
+// Cycles are read from the cubie effect of the sequence (relative to the centers). The
+// order counts repetitions until the cube is back to its starting stickers, so slice
+// moves and rotations moving the centers are taken into account.
+use crate::algorithm;
+use crate::cubie_cube::{CORNER_NAMES, EDGE_NAMES};
+use crate::optimized_cube::{MoveCode, OptimizedCube};
+
+/// No sequence has a larger order: 1260 for the cube times 4 for a net rotation
+const MAX_ORDER: usize = 5040;
+
+/// Positions visited by pieces of one cycle, each piece moving to the next position
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PieceCycle {
+    pub positions: Vec<&'static str>,
+    /// Net twist (corners, mod 3) or flip (edges, mod 2) after one pass round the cycle
+    pub orientation: u8,
+}
+
+/// Order and cycle decomposition of a move sequence
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CycleStructure {
+    /// Repetitions returning the cube to its starting state
+    pub order: usize,
+    /// Cycles of moved or reoriented corners; a twisted corner in place is a 1-cycle
+    pub corner_cycles: Vec<PieceCycle>,
+    pub edge_cycles: Vec<PieceCycle>,
+    /// Positions holding a twisted corner or a flipped edge, whether moved or not
+    pub twisted_corners: usize,
+    pub flipped_edges: usize,
+}
+
+/// Cycles of a permutation (`pieces[i]` = piece at position `i`) with orientations
+fn piece_cycles(
+    pieces: &[u8],
+    orientations: &[u8],
+    modulus: u8,
+    names: &[&'static str],
+) -> Vec<PieceCycle> {
+    // Position each piece moves to
+    let mut destination = vec![0; pieces.len()];
+    for (position, &piece) in pieces.iter().enumerate() {
+        destination[piece as usize] = position;
+    }
+    let mut seen = vec![false; pieces.len()];
+    let mut cycles = Vec::new();
+    for start in 0..pieces.len() {
+        if seen[start] {
+            continue;
+        }
+        let mut positions = Vec::new();
+        let mut orientation = 0;
+        let mut position = start;
+        while !seen[position] {
+            seen[position] = true;
+            positions.push(names[position]);
+            orientation = (orientation + orientations[position]) % modulus;
+            position = destination[position];
+        }
+        if positions.len() > 1 || orientation != 0 {
+            cycles.push(PieceCycle {
+                positions,
+                orientation,
+            });
+        }
+    }
+    cycles
+}
+
+/// Order, cycles and reoriented pieces of a move sequence
+pub fn cycle_structure(moves: &[u8]) -> Result<CycleStructure, String> {
+    let effect = algorithm::state_after(moves)?;
+    let corner_cycles = piece_cycles(&effect.cp, &effect.co, 3, &CORNER_NAMES);
+    let edge_cycles = piece_cycles(&effect.ep, &effect.eo, 2, &EDGE_NAMES);
+    Ok(CycleStructure {
+        order: order(moves)?,
+        corner_cycles,
+        edge_cycles,
+        twisted_corners: effect.co.iter().filter(|&&o| o != 0).count(),
+        flipped_edges: effect.eo.iter().filter(|&&o| o != 0).count(),
+    })
+}
+
+/// Repetitions of `moves` returning a solved cube to the same stickers
+pub fn order(moves: &[u8]) -> Result<usize, String> {
+    for &code in moves {
+        MoveCode::from_u8(code)?;
+    }
+    let solved = OptimizedCube::solved();
+    let mut cube = OptimizedCube::solved();
+    for repetitions in 1..=MAX_ORDER {
+        cube.apply_moves(moves);
+        if cube.stickers() == solved.stickers() {
+            return Ok(repetitions);
+        }
+    }
+    Err("Sequence order is too large".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn structure(alg: &str) -> CycleStructure {
+        cycle_structure(&algorithm::parse_algorithm(alg).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_orders() {
+        assert_eq!(structure("").order, 1);
+        assert_eq!(structure("R").order, 4);
+        assert_eq!(structure("R U R' U'").order, 6);
+        assert_eq!(structure("R U").order, 105);
+        assert_eq!(structure("R U2 D' B D'").order, 1260);
+        assert_eq!(structure("M").order, 4);
+        assert_eq!(structure("y").order, 4);
+
+        // The order agrees with the cycles when the centers stay put
+        for alg in ["R U R' U'", "R U", "R U2 D' B D'", "F R U' R' U' R U R' F'"] {
+            let cycles = structure(alg);
+            let lcm = cycles
+                .corner_cycles
+                .iter()
+                .map(|c| cycle_order(c, 3))
+                .chain(cycles.edge_cycles.iter().map(|c| cycle_order(c, 2)))
+                .fold(1, |lcm, n| lcm * n / gcd(lcm, n));
+            assert_eq!(lcm, cycles.order, "{}", alg);
+        }
+    }
+
+    /// Repetitions bringing the pieces of a cycle home with their orientation
+    fn cycle_order(cycle: &PieceCycle, modulus: usize) -> usize {
+        if cycle.orientation == 0 {
+            cycle.positions.len()
+        } else {
+            cycle.positions.len() * modulus
+        }
+    }
+
+    fn gcd(a: usize, b: usize) -> usize {
+        if b == 0 {
+            a
+        } else {
+            gcd(b, a % b)
+        }
+    }
+
+    #[test]
+    fn test_cycles() {
+        let t_perm = structure("R U R' U' R' F R2 U' R' U' R U R' F'");
+        assert_eq!(t_perm.order, 2);
+        assert_eq!(t_perm.corner_cycles.len(), 1);
+        assert_eq!(t_perm.corner_cycles[0].positions.len(), 2);
+        assert_eq!(t_perm.edge_cycles[0].positions, vec!["UR", "UL"]);
+        assert_eq!((t_perm.twisted_corners, t_perm.flipped_edges), (0, 0));
+
+        let u_perm = structure("M2 U M U2 M' U M2");
+        assert_eq!(u_perm.order, 3);
+        assert!(u_perm.corner_cycles.is_empty());
+        assert_eq!(u_perm.edge_cycles.len(), 1);
+        assert_eq!(u_perm.edge_cycles[0].positions.len(), 3);
+
+        // Sune followed by U2 leaves twisted corners in place
+        let sune = structure("R U R' U R U2 R' U2");
+        assert!(sune
+            .corner_cycles
+            .iter()
+            .any(|c| c.positions.len() == 1 && c.orientation != 0));
+        assert_eq!((sune.twisted_corners, sune.flipped_edges), (3, 0));
+        assert_eq!(structure("F R U R' U' F'").flipped_edges, 2);
+
+        // R takes the URF corner to UBR
+        let r = structure("R");
+        assert_eq!(
+            r.corner_cycles[0].positions,
+            vec!["URF", "UBR", "DRB", "DFR"]
+        );
+        assert_eq!(r.edge_cycles[0].positions, vec!["UR", "BR", "DR", "FR"]);
+    }
+}
@@ -0,0 +1,287 @@
+// ZZ EOLine and EOCross solver
+// Edge orientation is the F/B-axis flip coordinate (0-2047) read from the stickers after
+// rotating the chosen color to the bottom. Tables over the flip and two D edges give the
+// exact EOLine distance; EOCross also bounds by the other two D edges and the cross table.
+use crate::cross;
+use crate::cubie_cube::CubieCube;
+use crate::optimized_cube::{MoveCode, OptimizedCube};
+use crate::pieces::{Piece, PieceTable, N_PIECE_STATES};
+use crate::search::{self, Deadline, SearchSpace};
+use std::sync::{Arc, OnceLock};
+
+const N_FLIP: usize = 2048;
+const N_MOVES: usize = 18;
+const UNVISITED: u8 = u8::MAX;
+/// D edges in cross order: DR, DF, DL, DB
+const D_EDGES: [Piece; 4] = [
+    Piece::Edge(4),
+    Piece::Edge(5),
+    Piece::Edge(6),
+    Piece::Edge(7),
+];
+/// Indices into `D_EDGES` of the line (DF, DB) and of the other two edges (DR, DL)
+const LINE: [usize; 2] = [1, 3];
+const SIDES: [usize; 2] = [0, 2];
+/// Longest EOCross searched for; every EOCross is shorter
+const MAX_EOCROSS_LENGTH: usize = 14;
+
+/// ZZ first step to solve
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EoStep {
+    /// All edges oriented, DF and DB solved
+    EoLine,
+    /// All edges oriented, all four D edges solved
+    EoCross,
+}
+
+/// Optimal solution of a ZZ first step for one bottom color
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EoSolution {
+    pub color: u8,
+    /// Rotation (move codes) bringing the color to the bottom; EO is then on F/B
+    pub rotation: Vec<u8>,
+    /// Face moves after the rotation
+    pub moves: Vec<u8>,
+}
+
+struct EoTables {
+    flip_move: Vec<[u16; N_MOVES]>,
+    /// Distance to oriented edges with DF and DB solved, by (flip, DF, DB)
+    line: Vec<u8>,
+    /// Distance to oriented edges with DR and DL solved, by (flip, DR, DL)
+    sides: Vec<u8>,
+}
+
+static TABLES: OnceLock<EoTables> = OnceLock::new();
+
+fn tables() -> &'static EoTables {
+    TABLES.get_or_init(|| {
+        let flip_move = (0..N_FLIP as u16)
+            .map(|flip| {
+                let mut cube = CubieCube::solved();
+                cube.set_flip(flip);
+                let mut row = [0u16; N_MOVES];
+                for (m, entry) in row.iter_mut().enumerate() {
+                    let move_cube = CubieCube::move_cube(m as u8).expect("face move");
+                    *entry = cube.multiply(&move_cube).flip();
+                }
+                row
+            })
+            .collect::<Vec<_>>();
+        let line = build_table(&flip_move, [D_EDGES[LINE[0]], D_EDGES[LINE[1]]]);
+        let sides = build_table(&flip_move, [D_EDGES[SIDES[0]], D_EDGES[SIDES[1]]]);
+        EoTables {
+            flip_move,
+            line,
+            sides,
+        }
+    })
+}
+
+/// Table index of a flip and two edge states
+fn eo_index(flip: u16, first: u8, second: u8) -> usize {
+    (flip as usize * N_PIECE_STATES + first as usize) * N_PIECE_STATES + second as usize
+}
+
+/// Breadth-first distances to oriented edges with two edges solved
+fn build_table(flip_move: &[[u16; N_MOVES]], edges: [Piece; 2]) -> Vec<u8> {
+    let mut distance = vec![UNVISITED; N_FLIP * N_PIECE_STATES * N_PIECE_STATES];
+    let solved = eo_index(0, edges[0].solved_state(), edges[1].solved_state());
+    distance[solved] = 0;
+    let mut frontier = vec![solved];
+    let mut depth = 0;
+    while !frontier.is_empty() {
+        let mut next = Vec::new();
+        for &index in &frontier {
+            let second = (index % N_PIECE_STATES) as u8;
+            let first = (index / N_PIECE_STATES % N_PIECE_STATES) as u8;
+            let flip = index / (N_PIECE_STATES * N_PIECE_STATES);
+            for (m, &moved_flip) in flip_move[flip].iter().enumerate() {
+                let target = eo_index(
+                    moved_flip,
+                    edges[0].apply(first, m as u8),
+                    edges[1].apply(second, m as u8),
+                );
+                if distance[target] == UNVISITED {
+                    distance[target] = depth + 1;
+                    next.push(target);
+                }
+            }
+        }
+        frontier = next;
+        depth += 1;
+    }
+    distance
+}
+
+/// Flip coordinate and D edge states
+#[derive(Debug, Clone, Copy)]
+struct EoState {
+    flip: u16,
+    edges: [u8; 4],
+}
+
+struct EoSpace {
+    step: EoStep,
+    tables: &'static EoTables,
+    cross: Arc<PieceTable>,
+}
+
+impl EoSpace {
+    fn new(step: EoStep) -> EoSpace {
+        EoSpace {
+            step,
+            tables: tables(),
+            cross: PieceTable::get(&D_EDGES),
+        }
+    }
+
+    fn state(cube: &CubieCube) -> EoState {
+        let mut edges = [0u8; 4];
+        for (state, piece) in edges.iter_mut().zip(D_EDGES) {
+            *state = piece.state(cube);
+        }
+        EoState {
+            flip: cube.flip(),
+            edges,
+        }
+    }
+}
+
+impl SearchSpace for EoSpace {
+    type State = EoState;
+
+    fn apply(&self, state: &EoState, m: u8) -> EoState {
+        let mut edges = state.edges;
+        for (edge, piece) in edges.iter_mut().zip(D_EDGES) {
+            *edge = piece.apply(*edge, m);
+        }
+        EoState {
+            flip: self.tables.flip_move[state.flip as usize][m as usize],
+            edges,
+        }
+    }
+
+    fn lower_bound(&self, state: &EoState) -> u8 {
+        let line =
+            self.tables.line[eo_index(state.flip, state.edges[LINE[0]], state.edges[LINE[1]])];
+        match self.step {
+            EoStep::EoLine => line,
+            EoStep::EoCross => {
+                let sides = self.tables.sides
+                    [eo_index(state.flip, state.edges[SIDES[0]], state.edges[SIDES[1]])];
+                line.max(sides).max(self.cross.distance(&state.edges))
+            }
+        }
+    }
+}
+
+/// Edge orientation (F/B axis flip coordinate) with `color` at the bottom
+pub fn edge_orientation(cube: &OptimizedCube, color: u8) -> Result<u16, String> {
+    Ok(cross::with_color_down(cube, color)?.flip())
+}
+
+/// Optimal solution of a ZZ first step after the given rotation
+/// Returns None if the deadline passed first.
+fn solve_rotated(
+    cube: &OptimizedCube,
+    step: EoStep,
+    color: u8,
+    rotation: Vec<u8>,
+    deadline: Deadline,
+) -> Result<Option<EoSolution>, String> {
+    let space = EoSpace::new(step);
+    let start = EoSpace::state(&cross::rotated_state(cube, &rotation)?);
+    let outcome = search::search(&space, start, MAX_EOCROSS_LENGTH, 1, deadline);
+    Ok(outcome
+        .solutions
+        .into_iter()
+        .next()
+        .map(|moves| EoSolution {
+            color,
+            rotation,
+            moves,
+        }))
+}
+
+/// Optimal EOLine or EOCross on every bottom color (in color order)
+/// With `both_axes`, each color is also solved after a y rotation, orienting edges on
+/// the other side axis. Colors not solved before the timeout are left out.
+pub fn solve_all_eo_steps(
+    cube: &OptimizedCube,
+    step: EoStep,
+    both_axes: bool,
+    timeout_ms: Option<f64>,
+) -> Result<Vec<EoSolution>, String> {
+    let deadline = Deadline::from_timeout(timeout_ms);
+    let y_turns: &[&[MoveCode]] = if both_axes {
+        &[&[], &[MoveCode::Y]]
+    } else {
+        &[&[]]
+    };
+    let mut solutions = Vec::new();
+    for color in 0..6 {
+        for y_turn in y_turns {
+            let mut rotation = cross::rotation_to_bottom(cube, color)?;
+            rotation.extend(y_turn.iter().map(|&m| m as u8));
+            if let Some(solution) = solve_rotated(cube, step, color, rotation, deadline)? {
+                solutions.push(solution);
+            }
+        }
+    }
+    Ok(solutions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_solved(cube: &OptimizedCube, solution: &EoSolution, step: EoStep) -> bool {
+        let mut solved = cube.clone();
+        solved.apply_moves(&solution.rotation);
+        solved.apply_moves(&solution.moves);
+        let state = CubieCube::from_optimized(&solved).unwrap();
+        let edges: &[usize] = match step {
+            EoStep::EoLine => &[5, 7],
+            EoStep::EoCross => &[4, 5, 6, 7],
+        };
+        state.flip() == 0 && edges.iter().all(|&e| state.ep[e] == e as u8)
+    }
+
+    #[test]
+    fn test_eoline_solutions() {
+        let mut cube = OptimizedCube::solved();
+        cube.apply_scramble("F").unwrap();
+        assert_eq!(
+            edge_orientation(&cube, OptimizedCube::YELLOW),
+            Ok(0b010_0010_0110)
+        );
+        let solutions = solve_all_eo_steps(&cube, EoStep::EoLine, false, None).unwrap();
+        assert_eq!(solutions.len(), 6);
+        assert_eq!(solutions[1].color, OptimizedCube::YELLOW);
+        assert_eq!(solutions[1].moves, vec![MoveCode::Fp as u8]);
+
+        let mut cube = OptimizedCube::solved();
+        cube.apply_scramble("D2 F' R U2 B L' D R2 F U' L2 B2 R")
+            .unwrap();
+        let solutions = solve_all_eo_steps(&cube, EoStep::EoLine, true, None).unwrap();
+        assert_eq!(solutions.len(), 12);
+        for solution in &solutions {
+            assert!(is_solved(&cube, solution, EoStep::EoLine));
+            assert!(solution.moves.len() <= 9);
+        }
+    }
+
+    #[test]
+    fn test_eocross_solutions() {
+        let mut cube = OptimizedCube::solved();
+        cube.apply_scramble("R U F' L2 B D'").unwrap();
+        let lines = solve_all_eo_steps(&cube, EoStep::EoLine, false, None).unwrap();
+        let crosses = solve_all_eo_steps(&cube, EoStep::EoCross, false, None).unwrap();
+        assert_eq!(crosses.len(), 6);
+        for (line, cross) in lines.iter().zip(&crosses) {
+            assert!(is_solved(&cube, cross, EoStep::EoCross));
+            assert!(cross.moves.len() >= line.moves.len());
+        }
+    }
+}
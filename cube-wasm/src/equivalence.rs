@@ -0,0 +1,312 @@
+// Algorithm effects: equivalence and classification
+// An algorithm's effect is the state it leaves a solved cube in, read relative to the
+// centers, so rotations inside the algorithm don't matter. Two algorithms are equivalent
+// if their effects match up to U turns before and after, and optionally when one of
+// them is performed in another orientation.
+use crate::algorithm;
+use crate::algorithm_sets::COLL_ALGORITHMS;
+use crate::cross::Y_TURNS;
+use crate::cubie_cube::{CubieCube, CORNER_NAMES, EDGE_NAMES};
+use crate::last_layer::after_auf;
+use crate::optimized_cube::MoveCode;
+use crate::subset_scrambler::is_f2l_solved;
+use std::sync::OnceLock;
+
+/// Rotations bringing each face to the top; followed by a y turn they give all 24
+/// orientations
+const TO_TOP: [&[MoveCode]; 6] = [
+    &[],
+    &[MoveCode::X2],
+    &[MoveCode::X],
+    &[MoveCode::Xp],
+    &[MoveCode::Zp],
+    &[MoveCode::Z],
+];
+
+/// The 24 whole-cube rotations as move codes, starting with no rotation
+pub fn rotations() -> Vec<Vec<u8>> {
+    TO_TOP
+        .iter()
+        .flat_map(|to_top| {
+            Y_TURNS.iter().map(move |y_turn| {
+                to_top
+                    .iter()
+                    .chain(y_turn.iter())
+                    .map(|&m| m as u8)
+                    .collect()
+            })
+        })
+        .collect()
+}
+
+/// How one algorithm matches another: `rotation`, then `pre_auf` U turns, the other
+/// algorithm, the inverse rotation and `post_auf` U turns have this algorithm's effect
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Equivalence {
+    pub rotation: Vec<u8>,
+    pub pre_auf: u8,
+    pub post_auf: u8,
+}
+
+/// Whether `first` and `second` have the same effect up to AUF, and optionally up to
+/// the orientation `second` is performed in
+pub fn equivalence(
+    first: &[u8],
+    second: &[u8],
+    modulo_rotation: bool,
+) -> Result<Option<Equivalence>, String> {
+    let target = algorithm::state_after(first)?;
+    let rotations = if modulo_rotation {
+        rotations()
+    } else {
+        vec![Vec::new()]
+    };
+    for rotation in rotations {
+        let mut moves = rotation.clone();
+        moves.extend_from_slice(second);
+        moves.extend(algorithm::invert_algorithm(&rotation));
+        let effect = algorithm::state_after(&moves)?;
+        for pre_auf in 0..4 {
+            let turned = after_auf(&CubieCube::solved(), pre_auf).multiply(&effect);
+            if let Some(post_auf) = (0..4).find(|&turns| after_auf(&turned, turns) == target) {
+                return Ok(Some(Equivalence {
+                    rotation,
+                    pre_auf,
+                    post_auf,
+                }));
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// What kind of step an algorithm's effect belongs to, most specific first
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EffectKind {
+    /// Nothing changes
+    Identity,
+    /// Only a U turn
+    Auf,
+    /// Last-layer edges permuted
+    Epll,
+    /// Last-layer corners permuted
+    Cpll,
+    /// Last layer permuted, nothing reoriented
+    Pll,
+    /// Last layer reoriented, nothing permuted
+    PureOll,
+    /// Last-layer edges only
+    Ell,
+    /// Last-layer corners only
+    Cll,
+    /// Last-layer corners solved with edge orientation kept and edges only permuted,
+    /// as by a COLL table algorithm
+    Coll,
+    /// Last layer with edge orientation kept
+    Zbll,
+    /// Anything else keeping the first two layers
+    LastLayer,
+    /// Roux blocks and last-layer corners kept, M slice and last-layer edges changed
+    Lse,
+    /// Roux blocks kept
+    Cmll,
+    /// Cross kept
+    F2l,
+    Other,
+}
+
+impl EffectKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            EffectKind::Identity => "Identity",
+            EffectKind::Auf => "AUF",
+            EffectKind::Epll => "EPLL",
+            EffectKind::Cpll => "CPLL",
+            EffectKind::Pll => "PLL",
+            EffectKind::PureOll => "Pure OLL",
+            EffectKind::Ell => "ELL",
+            EffectKind::Cll => "CLL",
+            EffectKind::Coll => "COLL",
+            EffectKind::Zbll => "ZBLL",
+            EffectKind::LastLayer => "1LLL",
+            EffectKind::Lse => "LSE",
+            EffectKind::Cmll => "CMLL",
+            EffectKind::F2l => "F2L",
+            EffectKind::Other => "Other",
+        }
+    }
+}
+
+/// Classified effect of an algorithm, after the U turn leaving the fewest pieces moved
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EffectClass {
+    pub kind: EffectKind,
+    /// Quarter turns of U after the algorithm
+    pub auf: u8,
+    /// Positions holding another piece
+    pub permuted_corners: Vec<&'static str>,
+    pub permuted_edges: Vec<&'static str>,
+    /// Positions whose piece is twisted or flipped
+    pub twisted_corners: Vec<&'static str>,
+    pub flipped_edges: Vec<&'static str>,
+}
+
+/// Number of pieces an effect moves or reorients
+fn changed_pieces(cube: &CubieCube) -> usize {
+    let corners = (0..8).filter(|&i| cube.cp[i] != i as u8 || cube.co[i] != 0);
+    let edges = (0..12).filter(|&i| cube.ep[i] != i as u8 || cube.eo[i] != 0);
+    corners.count() + edges.count()
+}
+
+/// Effects of the COLL table algorithms, with every U turn before and after
+fn coll_effects() -> &'static [CubieCube] {
+    static EFFECTS: OnceLock<Vec<CubieCube>> = OnceLock::new();
+    EFFECTS.get_or_init(|| {
+        let mut effects = Vec::new();
+        for (_, alg) in COLL_ALGORITHMS {
+            let moves = algorithm::parse_algorithm(alg).expect("COLL table algorithm");
+            let effect = algorithm::state_after(&moves).expect("COLL table algorithm");
+            for pre_auf in 0..4 {
+                let turned = after_auf(&CubieCube::solved(), pre_auf).multiply(&effect);
+                effects.extend((0..4).map(|post_auf| after_auf(&turned, post_auf)));
+            }
+        }
+        effects
+    })
+}
+
+fn kind(cube: &CubieCube, auf: u8) -> EffectKind {
+    let permuted = |pieces: &[u8]| pieces.iter().enumerate().any(|(i, &p)| p != i as u8);
+    let oriented = |orientations: &[u8]| orientations.iter().any(|&o| o != 0);
+    let (cp, co) = (permuted(&cube.cp), oriented(&cube.co));
+    let (ep, eo) = (permuted(&cube.ep), oriented(&cube.eo));
+    if is_f2l_solved(cube) {
+        return match (cp, co, ep, eo) {
+            (false, false, false, false) if auf == 0 => EffectKind::Identity,
+            (false, false, false, false) => EffectKind::Auf,
+            (false, false, _, false) => EffectKind::Epll,
+            (_, false, false, false) => EffectKind::Cpll,
+            (_, false, _, false) => EffectKind::Pll,
+            (false, _, false, _) => EffectKind::PureOll,
+            (false, false, _, _) => EffectKind::Ell,
+            (_, _, false, false) => EffectKind::Cll,
+            (_, true, true, false) if coll_effects().contains(cube) => EffectKind::Coll,
+            (_, _, _, false) => EffectKind::Zbll,
+            _ => EffectKind::LastLayer,
+        };
+    }
+    let solved_corner = |i: usize| cube.cp[i] == i as u8 && cube.co[i] == 0;
+    let solved_edge = |i: usize| cube.ep[i] == i as u8 && cube.eo[i] == 0;
+    let blocks_solved =
+        (4..8).all(solved_corner) && [4, 6, 8, 9, 10, 11].into_iter().all(solved_edge);
+    if blocks_solved && (0..4).all(solved_corner) {
+        EffectKind::Lse
+    } else if blocks_solved {
+        EffectKind::Cmll
+    } else if (4..8).all(solved_edge) {
+        EffectKind::F2l
+    } else {
+        EffectKind::Other
+    }
+}
+
+/// Which pieces an algorithm permutes or reorients and what kind of step it is
+pub fn classify(moves: &[u8]) -> Result<EffectClass, String> {
+    let effect = algorithm::state_after(moves)?;
+    let auf = (0..4)
+        .min_by_key(|&turns| changed_pieces(&after_auf(&effect, turns)))
+        .expect("four U turns");
+    let cube = after_auf(&effect, auf);
+    let positions = |names: &[&'static str], changed: &dyn Fn(usize) -> bool| {
+        (0..names.len())
+            .filter(|&i| changed(i))
+            .map(|i| names[i])
+            .collect()
+    };
+    Ok(EffectClass {
+        kind: kind(&cube, auf),
+        auf,
+        permuted_corners: positions(&CORNER_NAMES, &|i| cube.cp[i] != i as u8),
+        permuted_edges: positions(&EDGE_NAMES, &|i| cube.ep[i] != i as u8),
+        twisted_corners: positions(&CORNER_NAMES, &|i| cube.co[i] != 0),
+        flipped_edges: positions(&EDGE_NAMES, &|i| cube.eo[i] != 0),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::last_layer::PLL_ALGORITHMS;
+
+    fn moves(alg: &str) -> Vec<u8> {
+        algorithm::parse_algorithm(alg).unwrap()
+    }
+
+    #[test]
+    fn test_equivalence() {
+        // Two T permutations, one of them with an extra AUF on both sides
+        let t_perm = moves("R U R' U' R' F R2 U' R' U' R U R' F'");
+        let shifted = moves("U R U R' U' R' F R2 U' R' U' R U R' F' U'");
+        let found = equivalence(&t_perm, &shifted, false).unwrap().unwrap();
+        assert_eq!((found.pre_auf, found.post_auf), (3, 1));
+        assert!(found.rotation.is_empty());
+
+        // A pair insertion at another slot needs a rotation
+        let insert = moves("R U R'");
+        let other_slot = moves("F U F'");
+        assert!(equivalence(&insert, &other_slot, false).unwrap().is_none());
+        let found = equivalence(&insert, &other_slot, true).unwrap();
+        assert!(found.is_some_and(|found| !found.rotation.is_empty()));
+
+        // Last-layer algorithms from another side only differ by AUF
+        let sune = moves("R U R' U R U2 R'");
+        assert!(equivalence(&sune, &moves("F U F' U F U2 F'"), false)
+            .unwrap()
+            .is_some());
+        assert!(equivalence(&sune, &moves("R U2 R' U' R U' R'"), true)
+            .unwrap()
+            .is_none());
+
+        assert_eq!(rotations().len(), 24);
+        let mut states: Vec<_> = rotations()
+            .iter()
+            .map(|rotation| {
+                let mut conjugated = rotation.clone();
+                conjugated.extend([MoveCode::R as u8, MoveCode::U as u8]);
+                conjugated.extend(algorithm::invert_algorithm(rotation));
+                algorithm::state_after(&conjugated).unwrap()
+            })
+            .map(|cube| (cube.cp, cube.co, cube.ep, cube.eo))
+            .collect();
+        states.sort();
+        states.dedup();
+        assert_eq!(states.len(), 24);
+    }
+
+    #[test]
+    fn test_classify() {
+        let kind = |alg: &str| classify(&moves(alg)).unwrap().kind;
+        assert_eq!(kind(""), EffectKind::Identity);
+        assert_eq!(kind("U2"), EffectKind::Auf);
+        assert_eq!(kind("M2 U M U2 M' U M2"), EffectKind::Epll);
+        assert_eq!(kind("x R' U R' D2 R U' R' D2 R2 x'"), EffectKind::Cpll);
+        assert_eq!(kind(PLL_ALGORITHMS[19].1), EffectKind::Pll);
+        assert_eq!(
+            kind("M' U M' U M' U M' U2 M' U M' U M' U M'"),
+            EffectKind::PureOll
+        );
+        assert_eq!(kind("R U R' U R U2 R'"), EffectKind::Coll);
+        assert_eq!(kind(COLL_ALGORITHMS[6].1), EffectKind::Coll);
+        assert_eq!(kind("R U R' U R U' R' U R U2 R'"), EffectKind::Zbll);
+        assert_eq!(kind("F R U R' U' F'"), EffectKind::LastLayer);
+        assert_eq!(kind("M' U2 M U2"), EffectKind::Lse);
+        assert_eq!(kind("R U R'"), EffectKind::F2l);
+        assert_eq!(kind("R"), EffectKind::Other);
+
+        let t_perm = classify(&moves("R U R' U' R' F R2 U' R' U' R U R' F'")).unwrap();
+        assert_eq!(t_perm.auf, 0);
+        assert_eq!(t_perm.permuted_corners, vec!["URF", "UBR"]);
+        assert_eq!(t_perm.permuted_edges, vec!["UR", "UL"]);
+        assert!(t_perm.twisted_corners.is_empty() && t_perm.flipped_edges.is_empty());
+    }
+}
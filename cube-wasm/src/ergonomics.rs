@@ -0,0 +1,390 @@
+// Ergonomics of a move sequence: a heuristic for how easy an algorithm is to execute
+// Moves are played through a simple two-hand model. Each wrist has a tilt from -1
+// (turned down) to 2 (turned up twice), changed by R and L turns; finger moves like U
+// flicks need the hand in a suitable tilt. When no hand can make a move from its grip
+// the hands regrip. A left-handed solver is modelled by mirroring the sequence through
+// the M slice and swapping the hands, so L turns become wrist turns and R turns become
+// finger moves.
+use crate::optimized_cube::MoveCode;
+use serde::Deserialize;
+
+/// Lowest and highest wrist tilt reachable without regripping
+const WRIST_RANGE: std::ops::RangeInclusive<i8> = -1..=2;
+
+/// Cost of each kind of move; higher costs mean harder algorithms
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ErgonomicsWeights {
+    /// Finger flick (U, U')
+    pub flick: f64,
+    /// Wrist turn (R, L, r, l)
+    pub wrist: f64,
+    /// Thumb or index push (F, F')
+    pub push: f64,
+    /// Awkward move (B, D, E, S, other wide moves)
+    pub awkward: f64,
+    /// M slice
+    pub slice: f64,
+    /// Whole-cube rotation
+    pub rotation: f64,
+    /// Regrip needed before a move
+    pub regrip: f64,
+    /// Multiplier for half turns
+    pub double_factor: f64,
+    /// Added once, scaled by the share of moves other than the dominant side and U
+    pub move_set: f64,
+}
+
+impl Default for ErgonomicsWeights {
+    fn default() -> Self {
+        ErgonomicsWeights {
+            flick: 1.0,
+            wrist: 1.0,
+            push: 1.5,
+            awkward: 3.0,
+            slice: 1.5,
+            rotation: 2.5,
+            regrip: 3.0,
+            double_factor: 1.5,
+            move_set: 2.0,
+        }
+    }
+}
+
+/// Analyser settings, e.g. `{ leftHanded: true, weights: { regrip: 4 } }` from JavaScript
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ErgonomicsOptions {
+    pub weights: ErgonomicsWeights,
+    pub left_handed: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hand {
+    Left,
+    Right,
+    Both,
+}
+
+impl Hand {
+    pub fn name(self) -> &'static str {
+        match self {
+            Hand::Left => "left",
+            Hand::Right => "right",
+            Hand::Both => "both",
+        }
+    }
+
+    fn mirrored(self) -> Hand {
+        match self {
+            Hand::Left => Hand::Right,
+            Hand::Right => Hand::Left,
+            Hand::Both => Hand::Both,
+        }
+    }
+}
+
+/// How a move is executed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Technique {
+    Flick,
+    Wrist,
+    Push,
+    Awkward,
+    Slice,
+    Rotation,
+}
+
+impl Technique {
+    pub fn name(self) -> &'static str {
+        match self {
+            Technique::Flick => "flick",
+            Technique::Wrist => "wrist",
+            Technique::Push => "push",
+            Technique::Awkward => "awkward",
+            Technique::Slice => "slice",
+            Technique::Rotation => "rotation",
+        }
+    }
+}
+
+/// One move of the sequence with the hand and technique used
+#[derive(Debug, Clone, PartialEq)]
+pub struct MoveAnnotation {
+    /// Move as written in the sequence
+    pub notation: &'static str,
+    pub hand: Hand,
+    pub technique: Technique,
+    /// The hands regripped before this move
+    pub regrip: bool,
+    pub cost: f64,
+}
+
+/// Ergonomics of a whole sequence
+#[derive(Debug, Clone, PartialEq)]
+pub struct ErgonomicsReport {
+    pub moves: Vec<MoveAnnotation>,
+    pub regrips: usize,
+    pub wrist_turns: usize,
+    pub awkward_moves: usize,
+    pub rotations: usize,
+    /// Share of moves turning U or the dominant side (R, or L when left-handed)
+    pub ru_ratio: f64,
+    /// Sum of the move costs plus the move-set penalty; lower is easier
+    pub score: f64,
+}
+
+/// Layer turned by a move
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Layer {
+    /// Outer face, by face index (U D F B R L)
+    Face(u8),
+    /// Wide move, by face index
+    Wide(u8),
+    /// M, E or S
+    Slice(u8),
+    /// x, y or z
+    Rotation(u8),
+}
+
+const U: u8 = 0;
+const D: u8 = 1;
+const F: u8 = 2;
+const B: u8 = 3;
+const R: u8 = 4;
+const L: u8 = 5;
+
+/// Layer and quarter turns (1, -1 or 2) of a move code
+fn decode(code: u8) -> (Layer, i8) {
+    let (layer, group) = match code {
+        0..=17 => (Layer::Face(code % 6), code / 6),
+        18..=35 => (Layer::Wide((code - 18) % 6), (code - 18) / 6),
+        36..=44 => (Layer::Slice((code - 36) % 3), (code - 36) / 3),
+        _ => (Layer::Rotation((code - 45) % 3), (code - 45) / 3),
+    };
+    (layer, [1, -1, 2][group as usize])
+}
+
+/// The move seen in a mirror through the M slice: R and L swap and every turn reverses
+/// except those about the x axis (M and x)
+fn mirror((layer, turns): (Layer, i8)) -> (Layer, i8) {
+    let reversed = if turns == 2 { 2 } else { -turns };
+    let swap = |face| match face {
+        R => L,
+        L => R,
+        face => face,
+    };
+    match layer {
+        Layer::Face(face) => (Layer::Face(swap(face)), reversed),
+        Layer::Wide(face) => (Layer::Wide(swap(face)), reversed),
+        Layer::Slice(0) | Layer::Rotation(0) => (layer, turns),
+        _ => (layer, reversed),
+    }
+}
+
+/// Wrist tilts of both hands
+#[derive(Debug, Default)]
+struct Grip {
+    right: i8,
+    left: i8,
+}
+
+/// New tilt after turning a wrist by `turns`, trying both directions for half turns
+fn turn_wrist(tilt: i8, turns: i8) -> Option<i8> {
+    let options: &[i8] = if turns == 2 { &[2, -2] } else { &[turns] };
+    options
+        .iter()
+        .map(|&t| tilt + t)
+        .find(|t| WRIST_RANGE.contains(t))
+}
+
+impl Grip {
+    /// Hand, technique and whether a regrip was needed, updating the tilts
+    fn play(&mut self, layer: Layer, turns: i8) -> (Hand, Technique, bool) {
+        match layer {
+            Layer::Face(R) | Layer::Wide(R) => {
+                let regrip = Self::wrist(&mut self.right, turns);
+                (Hand::Right, Technique::Wrist, regrip)
+            }
+            Layer::Face(L) | Layer::Wide(L) => {
+                // Turning L' tilts the left wrist up, like R for the right hand
+                let regrip = Self::wrist(&mut self.left, if turns == 2 { 2 } else { -turns });
+                (Hand::Left, Technique::Wrist, regrip)
+            }
+            Layer::Face(U) => {
+                // An index finger reaches U unless its wrist is turned up twice
+                let right_flick = (-1..=1).contains(&self.right);
+                let left_flick = (-1..=1).contains(&self.left);
+                let (hand, regrip) = match turns {
+                    // U' is the left index flick, or a right index pull from home grip
+                    -1 if left_flick => (Hand::Left, false),
+                    -1 if self.right == 0 => (Hand::Right, false),
+                    -1 => (Hand::Left, true),
+                    _ if right_flick => (Hand::Right, false),
+                    _ if left_flick => (Hand::Left, false),
+                    _ => (Hand::Right, true),
+                };
+                if regrip {
+                    *self = Grip::default();
+                }
+                (hand, Technique::Flick, regrip)
+            }
+            Layer::Face(F) => {
+                // F with the right index from home grip, F' with the left thumb
+                let hand = if turns == -1 { Hand::Left } else { Hand::Right };
+                let regrip = hand == Hand::Right && self.right != 0;
+                if regrip {
+                    self.right = 0;
+                }
+                (hand, Technique::Push, regrip)
+            }
+            // D' is a right ring pull, D a left one; B' a right ring push, B a left one
+            Layer::Face(D) | Layer::Face(B) => {
+                let hand = if turns == 1 { Hand::Left } else { Hand::Right };
+                (hand, Technique::Awkward, false)
+            }
+            Layer::Slice(0) => (Hand::Left, Technique::Slice, false),
+            Layer::Rotation(_) => {
+                *self = Grip::default();
+                (Hand::Both, Technique::Rotation, false)
+            }
+            _ => (Hand::Right, Technique::Awkward, false),
+        }
+    }
+
+    /// Turn a wrist, regripping to home first when it would go out of range
+    fn wrist(tilt: &mut i8, turns: i8) -> bool {
+        match turn_wrist(*tilt, turns) {
+            Some(new_tilt) => {
+                *tilt = new_tilt;
+                false
+            }
+            None => {
+                *tilt = turn_wrist(0, turns).expect("any turn fits from home grip");
+                true
+            }
+        }
+    }
+}
+
+/// Annotate every move of a sequence and score it; lower scores are easier
+pub fn analyse(moves: &[u8], options: &ErgonomicsOptions) -> Result<ErgonomicsReport, String> {
+    let weights = &options.weights;
+    let mut grip = Grip::default();
+    let mut annotations = Vec::with_capacity(moves.len());
+    let mut ru_moves = 0;
+    for &code in moves {
+        let notation = MoveCode::from_u8(code)?.notation();
+        let decoded = decode(code);
+        let (layer, turns) = if options.left_handed {
+            mirror(decoded)
+        } else {
+            decoded
+        };
+        if matches!(layer, Layer::Face(U) | Layer::Face(R)) {
+            ru_moves += 1;
+        }
+        let (hand, technique, regrip) = grip.play(layer, turns);
+        let base = match technique {
+            Technique::Flick => weights.flick,
+            Technique::Wrist => weights.wrist,
+            Technique::Push => weights.push,
+            Technique::Awkward => weights.awkward,
+            Technique::Slice => weights.slice,
+            Technique::Rotation => weights.rotation,
+        };
+        let factor = if turns == 2 && technique != Technique::Rotation {
+            weights.double_factor
+        } else {
+            1.0
+        };
+        let regrip_cost = if regrip { weights.regrip } else { 0.0 };
+        annotations.push(MoveAnnotation {
+            notation,
+            hand: if options.left_handed {
+                hand.mirrored()
+            } else {
+                hand
+            },
+            technique,
+            regrip,
+            cost: base * factor + regrip_cost,
+        });
+    }
+    let count = |technique| {
+        annotations
+            .iter()
+            .filter(|a| a.technique == technique)
+            .count()
+    };
+    let ru_ratio = if moves.is_empty() {
+        1.0
+    } else {
+        ru_moves as f64 / moves.len() as f64
+    };
+    let score =
+        annotations.iter().map(|a| a.cost).sum::<f64>() + weights.move_set * (1.0 - ru_ratio);
+    Ok(ErgonomicsReport {
+        regrips: annotations.iter().filter(|a| a.regrip).count(),
+        wrist_turns: count(Technique::Wrist),
+        awkward_moves: count(Technique::Awkward),
+        rotations: count(Technique::Rotation),
+        moves: annotations,
+        ru_ratio,
+        score,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithm;
+
+    fn report(alg: &str, left_handed: bool) -> ErgonomicsReport {
+        let options = ErgonomicsOptions {
+            left_handed,
+            ..ErgonomicsOptions::default()
+        };
+        analyse(&algorithm::parse_algorithm(alg).unwrap(), &options).unwrap()
+    }
+
+    #[test]
+    fn test_scores() {
+        let sexy = report("R U R' U'", false);
+        assert_eq!(sexy.regrips, 0);
+        assert_eq!(sexy.wrist_turns, 2);
+        assert_eq!(sexy.ru_ratio, 1.0);
+        assert_eq!(sexy.moves[1].hand, Hand::Right);
+        assert_eq!(sexy.moves[3].hand, Hand::Left);
+
+        let awkward = report("B D B' D'", false);
+        assert_eq!(awkward.awkward_moves, 4);
+        assert!(awkward.score > sexy.score);
+
+        // Three R turns in a row run out of wrist
+        let triple = report("R R R", false);
+        assert_eq!(triple.regrips, 1);
+        assert!(triple.moves[2].regrip);
+        assert!(report("R2 U R2", false).score < triple.score + 1.0);
+
+        // A rotation resets the grip
+        let rotated = report("y R U R'", false);
+        assert_eq!(rotated.rotations, 1);
+        assert_eq!(rotated.moves[0].hand, Hand::Both);
+    }
+
+    #[test]
+    fn test_left_handed() {
+        // The mirror of R U R' U' for the left hand scores the same
+        let right = report("R U R' U'", false);
+        let left = report("L' U' L U", true);
+        assert_eq!(left.score, right.score);
+        assert_eq!(left.regrips, 0);
+        assert_eq!(left.moves[0].hand, Hand::Left);
+        assert_eq!(left.moves[1].hand, Hand::Left);
+        assert_eq!(left.moves[1].notation, "U'");
+
+        // Right-hand algorithms are harder for a left-handed solver
+        assert!(report("R U R' U'", true).score > right.score);
+    }
+}
@@ -0,0 +1,251 @@
+// F2L pair case table and recognition
+// Cases are numbered for the FR slot (standard numbering) and told apart by where the
+// slot's corner and edge are, up to AUF. The other slots use the same algorithms seen
+// from another side (`algorithm::y_rotated`), so every slot has the same 41 cases.
+use crate::algorithm;
+use crate::analysis::F2L_PAIRS;
+use crate::cubie_cube::CubieCube;
+use crate::last_layer::{after_auf, auf_moves};
+use crate::pieces::Piece;
+use std::sync::OnceLock;
+
+/// F2L algorithms for the FR slot, case "F2L n" is entry n - 1
+/// 1-24: both pieces in U, 25-30: corner in the slot, 31-36: edge in the slot,
+/// 37-41: both pieces in the slot
+pub const F2L_ALGORITHMS: [&str; 41] = [
+    "U R U' R'",
+    "U' F' U F",
+    "F' U' F",
+    "R U R'",
+    "U' R U R' U2 R U' R'",
+    "U F' U' F U2 F' U F",
+    "U' R U2 R' U2 R U' R'",
+    "U F' U2 F U2 F' U F",
+    "U' F' U F U' F' U' F",
+    "U' R U R' U R U R'",
+    "U' R U2 R' U F' U' F",
+    "R U' R' U R U' R' U2 R U' R'",
+    "U F' U F U2 R U R'",
+    "U' R U' R' U R U R'",
+    "U F' U' F U' F' U' F",
+    "R U' R' U2 F' U' F",
+    "R U2 R' U' R U R'",
+    "F' U2 F U F' U' F",
+    "U R U2 R' U R U' R'",
+    "U' F' U2 F U' F' U F",
+    "U2 R U R' U R U' R'",
+    "U2 F' U' F U' F' U F",
+    "U R U' R' U' R U' R' U R U' R'",
+    "F U R U' R' F' R U' R'",
+    "U' R' F R F' R U R'",
+    "U R U' R' F R' F' R",
+    "R U' R' U R U' R'",
+    "F' U F U' F' U F",
+    "R U' R' F' U' F",
+    "R U R' U' R U R'",
+    "U' R' F R F' R U' R'",
+    "U R U' R' U R U' R' U R U' R'",
+    "U' R U' R' U2 R U' R'",
+    "U R U R' U2 R U R'",
+    "U2 R U' R' U' F' U' F",
+    "U F' U' F U' R U R'",
+    "R2 U2 F R2 F' U2 R' U R'",
+    "R U' R' U' R U R' U2 R U' R'",
+    "R U' R' U R U2 R' U R U' R'",
+    "R U' R' U2 R U R' F' U' F",
+    "R U' R' F R U R' U' F' R U' R'",
+];
+
+/// Corner and edge states (see `Piece::state`) of a slot's pair
+type PairState = (u8, u8);
+
+/// Standard case of one pair, with the U turns to do before the algorithm
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct F2lCase {
+    /// 1-based case number, 0 when the pair is already solved
+    pub number: u8,
+    /// Quarter turns of U before the algorithm
+    pub auf: u8,
+}
+
+impl F2lCase {
+    /// FR-slot algorithm of the case, none for a solved pair
+    pub fn algorithm(&self) -> Option<&'static str> {
+        match self.number {
+            0 => None,
+            number => F2L_ALGORITHMS.get(number as usize - 1).copied(),
+        }
+    }
+}
+
+/// Where the pieces of one slot are and which case they make
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SlotCase {
+    /// Slot index: FR, FL, BL, BR
+    pub slot: usize,
+    /// Corner position (URF, UFL, ... ordering) and twist
+    pub corner_position: u8,
+    pub corner_twist: u8,
+    /// Edge position (UR, UF, ... ordering) and flip
+    pub edge_position: u8,
+    pub edge_flip: u8,
+    /// None when a piece is stuck in another slot
+    pub case: Option<F2lCase>,
+}
+
+impl SlotCase {
+    /// AUF and the case algorithm performed at this slot
+    pub fn moves(&self) -> Option<Vec<u8>> {
+        let case = self.case?;
+        let mut moves = auf_moves(case.auf);
+        if let Some(alg) = case.algorithm() {
+            let alg = algorithm::parse_algorithm(alg).expect("valid F2L algorithm");
+            moves.extend(algorithm::y_rotated(&alg, self.slot));
+        }
+        Some(moves)
+    }
+}
+
+fn pair_state(cube: &CubieCube, slot: usize) -> PairState {
+    let (corner, edge) = F2L_PAIRS[slot];
+    (
+        Piece::Corner(corner).state(cube),
+        Piece::Edge(edge).state(cube),
+    )
+}
+
+/// Pair state of each case at each slot, from the inverse of its algorithm
+fn case_states() -> &'static [Vec<PairState>; 4] {
+    static STATES: OnceLock<[Vec<PairState>; 4]> = OnceLock::new();
+    STATES.get_or_init(|| {
+        [0, 1, 2, 3].map(|slot| {
+            F2L_ALGORITHMS
+                .iter()
+                .map(|alg| {
+                    let moves = algorithm::parse_algorithm(alg).expect("valid F2L algorithm");
+                    let moves = algorithm::y_rotated(&moves, slot);
+                    pair_state(
+                        &algorithm::case_state(&moves).expect("valid F2L case"),
+                        slot,
+                    )
+                })
+                .collect()
+        })
+    })
+}
+
+fn recognize_slot(cube: &CubieCube, slot: usize) -> Option<F2lCase> {
+    let solved = pair_state(&CubieCube::solved(), slot);
+    if pair_state(cube, slot) == solved {
+        return Some(F2lCase { number: 0, auf: 0 });
+    }
+    (0..4).find_map(|auf| {
+        let state = pair_state(&after_auf(cube, auf), slot);
+        let index = case_states()[slot].iter().position(|&case| case == state)?;
+        Some(F2lCase {
+            number: index as u8 + 1,
+            auf,
+        })
+    })
+}
+
+/// F2L case at each of the four slots of a cube whose D cross is solved
+pub fn recognize_f2l(cube: &CubieCube) -> Result<Vec<SlotCase>, String> {
+    if !(4..8).all(|i| cube.ep[i] == i as u8 && cube.eo[i] == 0) {
+        return Err("Cross is not solved".to_string());
+    }
+    Ok((0..4)
+        .map(|slot| {
+            let (corner, edge) = pair_state(cube, slot);
+            SlotCase {
+                slot,
+                corner_position: corner / 3,
+                corner_twist: corner % 3,
+                edge_position: edge / 2,
+                edge_flip: edge % 2,
+                case: recognize_slot(cube, slot),
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::optimized_cube::MoveCode;
+    use crate::rng::Rng;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_f2l_table_covers_every_case_once() {
+        for slot in 0..4 {
+            let mut classes = HashSet::new();
+            for (index, alg) in F2L_ALGORITHMS.iter().enumerate() {
+                let moves = algorithm::parse_algorithm(alg).unwrap();
+                let case = algorithm::case_state(&algorithm::y_rotated(&moves, slot)).unwrap();
+                for other in (0..4).filter(|&other| other != slot) {
+                    assert_eq!(
+                        pair_state(&case, other),
+                        pair_state(&CubieCube::solved(), other),
+                        "F2L {} moves another slot",
+                        index + 1
+                    );
+                }
+                let class = (0..4)
+                    .map(|auf| pair_state(&after_auf(&case, auf), slot))
+                    .min()
+                    .unwrap();
+                assert!(classes.insert(class), "F2L {} repeats a case", index + 1);
+            }
+            assert_eq!(classes.len(), 41);
+        }
+    }
+
+    #[test]
+    fn test_recognize_f2l_cases() {
+        let mut rng = Rng::new(41);
+        for _ in 0..30 {
+            // Random pairs: table cases performed backwards at random slots and AUFs
+            let mut moves = Vec::new();
+            for _ in 0..4 {
+                let alg = algorithm::parse_algorithm(F2L_ALGORITHMS[rng.below(41)]).unwrap();
+                moves.extend(algorithm::y_rotated(&alg, rng.below(4)));
+                moves.extend(auf_moves(rng.below(4) as u8));
+            }
+            let state = algorithm::case_state(&moves).unwrap();
+            for slot_case in recognize_f2l(&state).unwrap() {
+                let slot = slot_case.slot;
+                let Some(moves) = slot_case.moves() else {
+                    // A piece sits in another slot
+                    let elsewhere = |position: u8, first: u8| {
+                        position >= first && position != first + slot as u8
+                    };
+                    assert!(
+                        elsewhere(slot_case.corner_position, 4)
+                            || elsewhere(slot_case.edge_position, 8)
+                    );
+                    continue;
+                };
+                let mut solved = state;
+                solved.apply_moves(&moves).unwrap();
+                assert_eq!(
+                    pair_state(&solved, slot),
+                    pair_state(&CubieCube::solved(), slot),
+                    "{:?}",
+                    slot_case
+                );
+                assert!((4..8).all(|i| solved.ep[i] == i as u8 && solved.eo[i] == 0));
+            }
+        }
+
+        let sexy = algorithm::parse_algorithm("U R U' R'").unwrap();
+        let cases = recognize_f2l(&algorithm::case_state(&sexy).unwrap()).unwrap();
+        assert_eq!(cases[0].case, Some(F2lCase { number: 1, auf: 0 }));
+        assert_eq!((cases[0].corner_position, cases[0].edge_position), (0, 0));
+        assert_eq!(cases[1].case, Some(F2lCase { number: 0, auf: 0 }));
+
+        let mut broken = CubieCube::solved();
+        broken.apply_move(MoveCode::F as u8).unwrap();
+        assert!(recognize_f2l(&broken).is_err());
+    }
+}
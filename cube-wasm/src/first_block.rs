@@ -0,0 +1,178 @@
+// Roux first block solver: the 1x2x3 block on the left (DL, FL and BL edges, DLF and DBL
+// corners), solved relative to the centers with face moves
+// Each of the 24 orientations (bottom color x left color) is a piece search. All of them
+// are deepened together (`search::search_jointly`), so results come out shortest first.
+use crate::cross;
+use crate::optimized_cube::OptimizedCube;
+use crate::pieces::{Piece, PieceSpace};
+use crate::search::{self, Deadline};
+
+/// Block corners: DLF, DBL
+const BLOCK_CORNERS: [u8; 2] = [5, 6];
+/// Block edges: DL, FL, BL
+const BLOCK_EDGES: [u8; 3] = [6, 9, 10];
+/// Sticker index of the L center
+const LEFT_CENTER: usize = 49;
+
+/// Limits for a first block analysis
+#[derive(Debug, Clone, Copy)]
+pub struct FirstBlockOptions {
+    /// Longest solution searched for
+    pub max_length: usize,
+    /// Stop after this many milliseconds
+    pub timeout_ms: Option<f64>,
+}
+
+impl Default for FirstBlockOptions {
+    fn default() -> Self {
+        FirstBlockOptions {
+            max_length: 14,
+            timeout_ms: Some(5000.0),
+        }
+    }
+}
+
+/// Optimal first block for one orientation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FirstBlockSolution {
+    /// Color of the block's bottom face
+    pub bottom_color: u8,
+    /// Color of the block's left face
+    pub left_color: u8,
+    /// Rotation (move codes) bringing the block orientation to the bottom left
+    pub rotation: Vec<u8>,
+    /// Face moves after the rotation
+    pub moves: Vec<u8>,
+}
+
+/// Solutions found, shortest first
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FirstBlockReport {
+    pub solutions: Vec<FirstBlockSolution>,
+    /// Every orientation got its optimal solution
+    pub complete: bool,
+}
+
+/// Search space for the left block, bounded by each corner with the three edges and by
+/// both corners with the DL edge
+fn first_block_space() -> PieceSpace {
+    let corners = BLOCK_CORNERS.map(Piece::Corner);
+    let edges = BLOCK_EDGES.map(Piece::Edge);
+    let mut pieces = corners.to_vec();
+    pieces.extend_from_slice(&edges);
+    let mut groups: Vec<Vec<Piece>> = corners
+        .iter()
+        .map(|&corner| {
+            let mut group = vec![corner];
+            group.extend_from_slice(&edges);
+            group
+        })
+        .collect();
+    groups.push(vec![corners[0], corners[1], edges[0]]);
+    PieceSpace::new(&pieces, &groups)
+}
+
+/// Optimal first block for all 24 orientations, shortest first, within the option limits
+pub fn solve_first_blocks(
+    cube: &OptimizedCube,
+    options: FirstBlockOptions,
+) -> Result<FirstBlockReport, String> {
+    let space = first_block_space();
+    // (bottom color, left color, rotation) of every search, with its start state
+    let mut orientations = Vec::new();
+    let mut problems = Vec::new();
+    for bottom_color in 0..6 {
+        let to_bottom = cross::rotation_to_bottom(cube, bottom_color)?;
+        for y_turn in cross::Y_TURNS {
+            let mut rotation = to_bottom.clone();
+            rotation.extend(y_turn.iter().map(|&m| m as u8));
+            let mut rotated = cube.clone();
+            rotated.apply_moves(&rotation);
+            let left_color = rotated.stickers()[LEFT_CENTER];
+            let state = cross::rotated_state(cube, &rotation)?;
+            problems.push((&space, space.state(&state)));
+            orientations.push((bottom_color, left_color, rotation));
+        }
+    }
+
+    let deadline = Deadline::from_timeout(options.timeout_ms);
+    let outcome = search::search_jointly(&problems, options.max_length, usize::MAX, deadline);
+    let solutions = outcome
+        .solutions
+        .into_iter()
+        .map(|(index, moves)| {
+            let (bottom_color, left_color, rotation) = &orientations[index];
+            FirstBlockSolution {
+                bottom_color: *bottom_color,
+                left_color: *left_color,
+                rotation: rotation.clone(),
+                moves,
+            }
+        })
+        .collect();
+    Ok(FirstBlockReport {
+        solutions,
+        complete: outcome.complete,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cubie_cube::CubieCube;
+
+    #[test]
+    fn test_first_block_solutions() {
+        let mut cube = OptimizedCube::solved();
+        cube.apply_scramble("R U R' U' F2 D' L").unwrap();
+        let report = solve_first_blocks(&cube, FirstBlockOptions::default()).unwrap();
+        assert!(report.complete);
+        assert_eq!(report.solutions.len(), 24);
+        assert!(report
+            .solutions
+            .windows(2)
+            .all(|pair| pair[0].moves.len() <= pair[1].moves.len()));
+
+        let mut orientations = Vec::new();
+        for solution in &report.solutions {
+            assert_ne!(solution.bottom_color / 2, solution.left_color / 2);
+            orientations.push((solution.bottom_color, solution.left_color));
+            let mut solved = cube.clone();
+            solved.apply_moves(&solution.rotation);
+            solved.apply_moves(&solution.moves);
+            assert_eq!(solved.stickers()[LEFT_CENTER], solution.left_color);
+            let state = CubieCube::from_optimized(&solved).unwrap();
+            for corner in BLOCK_CORNERS.map(usize::from) {
+                assert_eq!((state.cp[corner], state.co[corner]), (corner as u8, 0));
+            }
+            for edge in BLOCK_EDGES.map(usize::from) {
+                assert_eq!((state.ep[edge], state.eo[edge]), (edge as u8, 0));
+            }
+        }
+        orientations.sort();
+        orientations.dedup();
+        assert_eq!(orientations.len(), 24);
+    }
+
+    #[test]
+    fn test_first_block_untouched_and_limits() {
+        let mut cube = OptimizedCube::solved();
+        cube.apply_scramble("R U R' U'").unwrap();
+        let report = solve_first_blocks(&cube, FirstBlockOptions::default()).unwrap();
+        let first = &report.solutions[0];
+        assert_eq!(
+            (first.bottom_color, first.left_color),
+            (OptimizedCube::YELLOW, OptimizedCube::ORANGE)
+        );
+        assert!(first.moves.is_empty());
+
+        // Six blocks avoid every piece R U R' U' moves
+        let options = FirstBlockOptions {
+            max_length: 0,
+            ..FirstBlockOptions::default()
+        };
+        let report = solve_first_blocks(&cube, options).unwrap();
+        assert!(!report.complete);
+        assert_eq!(report.solutions.len(), 6);
+    }
+}
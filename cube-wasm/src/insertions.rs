@@ -0,0 +1,179 @@
+// Fewest-moves insertion finder
+// A skeleton is a partial solution leaving a few pieces unsolved: a 3-cycle of corners
+// or edges, or exactly two twisted corners (three twisted corners take two insertions,
+// which are not searched). At each insertion point the inserted sequence must undo what
+// is left, seen through the skeleton moves around that point. Commutators
+// with exactly that effect are looked up (3-cycles in the commutator tables, twists
+// among pure twist commutators), inserted, and the solution is counted after
+// cancellations, including moves cancelling across a turn of the opposite face.
+use crate::algorithm;
+use crate::commutators::{
+    self, facelets, inverse, is_corner_twist, then, three_cycle, Commutator, PieceKind,
+};
+use std::collections::HashSet;
+
+/// What a skeleton leaves unsolved
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Residue {
+    CornerCycle,
+    EdgeCycle,
+    CornerTwist,
+}
+
+impl Residue {
+    pub fn name(self) -> &'static str {
+        match self {
+            Residue::CornerCycle => "corner 3-cycle",
+            Residue::EdgeCycle => "edge 3-cycle",
+            Residue::CornerTwist => "corner twist",
+        }
+    }
+}
+
+/// A commutator inserted into the skeleton
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Insertion {
+    /// Number of skeleton moves before the insertion
+    pub position: usize,
+    pub commutator: Commutator,
+    /// Whole solution after cancellations
+    pub solution: Vec<u8>,
+    /// Moves cancelled by the insertion, compared to the skeleton plus the commutator
+    pub cancelled: usize,
+}
+
+/// Insertions for a skeleton, shortest solution first
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InsertionSearch {
+    pub residue: Residue,
+    pub insertions: Vec<Insertion>,
+}
+
+/// Kind of residue left by a permutation, if a single insertion can fix it: a 3-cycle,
+/// or a twist of exactly two corners like the twist commutators make
+fn residue(left: &commutators::Facelets) -> Option<Residue> {
+    if three_cycle(left, PieceKind::Corner).is_some() {
+        Some(Residue::CornerCycle)
+    } else if three_cycle(left, PieceKind::Edge).is_some() {
+        Some(Residue::EdgeCycle)
+    } else if is_corner_twist(left) && (0..54).filter(|&i| left[i] != i as u8).count() == 6 {
+        Some(Residue::CornerTwist)
+    } else {
+        None
+    }
+}
+
+/// Insertions of face-move commutators into `skeleton` solving the cube after
+/// `scramble`, shortest solution first, then earliest; at most `limit` are returned
+pub fn find_insertions(
+    scramble: &[u8],
+    skeleton: &[u8],
+    limit: usize,
+) -> Result<InsertionSearch, String> {
+    let mut attempt = scramble.to_vec();
+    attempt.extend_from_slice(skeleton);
+    let left = facelets(&attempt);
+    if left == facelets(&[]) {
+        return Err("Skeleton already solves the scramble".to_string());
+    }
+    let residue = residue(&left).ok_or(
+        "Skeleton must leave a 3-cycle of corners or edges, or exactly two twisted \
+         corners; three twisted corners need two insertions",
+    )?;
+
+    let mut insertions: Vec<Insertion> = Vec::new();
+    let mut seen = HashSet::new();
+    for position in 0..=skeleton.len() {
+        let (before, after) = skeleton.split_at(position);
+        let mut start = scramble.to_vec();
+        start.extend_from_slice(before);
+        // The inserted sequence must undo the start, then the moves after it
+        let needed = then(&inverse(&facelets(&start)), &inverse(&facelets(after)));
+        let found = match residue {
+            Residue::CornerCycle | Residue::EdgeCycle => {
+                let kind = if residue == Residue::CornerCycle {
+                    PieceKind::Corner
+                } else {
+                    PieceKind::Edge
+                };
+                let cycle = three_cycle(&needed, kind).expect("residues stay 3-cycles")[0];
+                commutators::commutators_cycling(kind, cycle, true)
+            }
+            Residue::CornerTwist => commutators::twist_commutators(&needed),
+        };
+        for commutator in found {
+            let inserted = commutator.moves();
+            let mut moves = before.to_vec();
+            moves.extend_from_slice(&inserted);
+            moves.extend_from_slice(after);
+            let solution = algorithm::cancel_moves(&moves);
+            if !seen.insert(solution.clone()) {
+                continue;
+            }
+            insertions.push(Insertion {
+                position,
+                cancelled: skeleton.len() + inserted.len() - solution.len(),
+                commutator,
+                solution,
+            });
+        }
+    }
+    insertions.sort_by_key(|insertion| (insertion.solution.len(), insertion.position));
+    insertions.truncate(limit);
+    Ok(InsertionSearch {
+        residue,
+        insertions,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Scramble solved by `skeleton` followed by `last`
+    fn scramble_for(skeleton: &[u8], last: &str) -> Vec<u8> {
+        let mut solution = skeleton.to_vec();
+        solution.extend(algorithm::parse_algorithm(last).unwrap());
+        algorithm::invert_algorithm(&solution)
+    }
+
+    #[test]
+    fn test_find_insertions() {
+        let skeleton = algorithm::parse_algorithm("R2 F U' B L2 D R' F2 U L").unwrap();
+        for (last, residue) in [
+            ("R U R' D R U' R' D'", Residue::CornerCycle),
+            ("R2 U R U R' U' R' U' R' U R'", Residue::EdgeCycle),
+            (
+                "R' D' R D R' D' R D U D' R' D R D' R' D R U'",
+                Residue::CornerTwist,
+            ),
+        ] {
+            let scramble = scramble_for(&skeleton, last);
+            let search = find_insertions(&scramble, &skeleton, 20).unwrap();
+            assert_eq!(search.residue, residue);
+            assert!(!search.insertions.is_empty(), "{}", last);
+            let best = &search.insertions[0];
+            let last_length = algorithm::parse_algorithm(last).unwrap().len();
+            assert!(best.solution.len() <= skeleton.len() + last_length);
+            for insertion in &search.insertions {
+                let mut moves = scramble.clone();
+                moves.extend_from_slice(&insertion.solution);
+                assert_eq!(facelets(&moves), facelets(&[]), "{}", last);
+            }
+            assert!(search
+                .insertions
+                .windows(2)
+                .all(|pair| pair[0].solution.len() <= pair[1].solution.len()));
+        }
+
+        // Nothing or too much left
+        assert!(find_insertions(&scramble_for(&skeleton, ""), &skeleton, 5).is_err());
+        assert!(find_insertions(&scramble_for(&skeleton, "R U"), &skeleton, 5).is_err());
+        // Three twisted corners: two twist commutators sharing the URF corner
+        let three_twists = "R' D' R D R' D' R D U D' R' D R D' R' D R U' \
+                            R' D' R D R' D' R D U2 D' R' D R D' R' D R U2";
+        let error =
+            find_insertions(&scramble_for(&skeleton, three_twists), &skeleton, 5).unwrap_err();
+        assert!(error.contains("exactly two twisted corners"), "{}", error);
+    }
+}
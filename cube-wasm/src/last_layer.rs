@@ -0,0 +1,378 @@
+// Last-layer case tables and recognition
+// OLL cases are told apart by the orientation of the four U corners and edges, which is
+// all an OLL algorithm depends on; PLL cases by trying each algorithm after every pre-AUF
+// and looking for a final U turn that solves the cube. Recognition uses the table
+// algorithms themselves, so recognition and the tables can never disagree.
+use crate::algorithm;
+use crate::cubie_cube::CubieCube;
+use crate::optimized_cube::MoveCode;
+use crate::subset_scrambler::is_f2l_solved;
+use std::sync::OnceLock;
+
+/// OLL algorithms in standard numbering, case "OLL n" is entry n - 1
+pub const OLL_ALGORITHMS: [&str; 57] = [
+    "R U2 R2 F R F' U2 R' F R F'",
+    "F R U R' U' F' f R U R' U' f'",
+    "f R U R' U' f' U' F R U R' U' F'",
+    "f R U R' U' f' U F R U R' U' F'",
+    "r' U2 R U R' U r",
+    "r U2 R' U' R U' r'",
+    "r U R' U R U2 r'",
+    "r' U' R U' R' U2 r",
+    "R U R' U' R' F R2 U R' U' F'",
+    "R U R' U R' F R F' R U2 R'",
+    "r U R' U R' F R F' R U2 r'",
+    "M' R' U' R U' R' U2 R U' R r'",
+    "F U R U' R2 F' R U R U' R'",
+    "R' F R U R' F' R F U' F'",
+    "r' U' r R' U' R U r' U r",
+    "r U r' R U R' U' r U' r'",
+    "R U R' U R' F R F' U2 R' F R F'",
+    "r U R' U R U2 r2 U' R U' R' U2 r",
+    "r' R U R U R' U' M' R' F R F'",
+    "r U R' U' M2 U R U' R' U' M'",
+    "R U2 R' U' R U R' U' R U' R'",
+    "R U2 R2 U' R2 U' R2 U2 R",
+    "R2 D' R U2 R' D R U2 R",
+    "r U R' U' r' F R F'",
+    "F' r U R' U' r' F R",
+    "R U2 R' U' R U' R'",
+    "R U R' U R U2 R'",
+    "r U R' U' r' R U R U' R'",
+    "R U R' U' R U' R' F' U' F R U R'",
+    "F R' F R2 U' R' U' R U R' F2",
+    "R' U' F U R U' R' F' R",
+    "L U F' U' L' U L F L'",
+    "R U R' U' R' F R F'",
+    "R U R2 U' R' F R U R U' F'",
+    "R U2 R2 F R F' R U2 R'",
+    "L' U' L U' L' U L U L F' L' F",
+    "F R' F' R U R U' R'",
+    "R U R' U R U' R' U' R' F R F'",
+    "L F' L' U' L U F U' L'",
+    "R' F R U R' U' F' U R",
+    "R U R' U R U2 R' F R U R' U' F'",
+    "R' U' R U' R' U2 R F R U R' U' F'",
+    "F' U' L' U L F",
+    "F U R U' R' F'",
+    "F R U R' U' F'",
+    "R' U' R' F R F' U R",
+    "R' U' R' F R F' R' F R F' U R",
+    "F R U R' U' R U R' U' F'",
+    "r U' r2 U r2 U r2 U' r",
+    "r' U r2 U' r2 U' r2 U r'",
+    "F U R U' R' U R U' R' F'",
+    "R U R' U R U' B U' B' R'",
+    "r' U' R U' R' U R U' R' U2 r",
+    "r U R' U R U' R' U R U2 r'",
+    "R' F R U R U' R2 F' R2 U' R' U R U R'",
+    "r' U' r U' R' U R U' R' U R r' U r",
+    "R U R' U' M' U R U' r'",
+];
+
+/// PLL names and algorithms
+pub const PLL_ALGORITHMS: [(&str, &str); 21] = [
+    ("Aa", "x R' U R' D2 R U' R' D2 R2 x'"),
+    ("Ab", "x R2 D2 R U R' D2 R U' R x'"),
+    ("E", "x' R U' R' D R U R' D' R U R' D R U' R' D' x"),
+    ("F", "R' U' F' R U R' U' R' F R2 U' R' U' R U R' U R"),
+    ("Ga", "R2 U R' U R' U' R U' R2 U' D R' U R D'"),
+    ("Gb", "R' U' R U D' R2 U R' U R U' R U' R2 D"),
+    ("Gc", "R2 U' R U' R U R' U R2 U D' R U' R' D"),
+    ("Gd", "R U R' U' D R2 U' R U' R' U R' U R2 D'"),
+    ("H", "M2 U M2 U2 M2 U M2"),
+    ("Ja", "x R2 F R F' R U2 r' U r U2 x'"),
+    ("Jb", "R U R' F' R U R' U' R' F R2 U' R'"),
+    (
+        "Na",
+        "R U R' U R U R' F' R U R' U' R' F R2 U' R' U2 R U' R'",
+    ),
+    ("Nb", "R' U R U' R' F' U' F R U R' F R' F' R U' R"),
+    ("Ra", "R U' R' U' R U R D R' U' R D' R' U2 R'"),
+    ("Rb", "R2 F R U R U' R' F' R U2 R' U2 R"),
+    ("T", "R U R' U' R' F R2 U' R' U' R U R' F'"),
+    ("Ua", "M2 U M U2 M' U M2"),
+    ("Ub", "M2 U' M U2 M' U' M2"),
+    ("V", "R' U R' U' B' R' B2 U' B' U B' R B R"),
+    ("Y", "F R U' R' U' R U R' F' R U R' U' R' F R F'"),
+    ("Z", "M' U M2 U M2 U M' U2 M2 U'"),
+];
+
+/// Orientation of the U corners and U edges, in position order
+type OrientationPattern = [u8; 8];
+
+/// An OLL case and the U turn to do before its algorithm
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OllCase {
+    /// Standard OLL number (1-57), 0 when the last layer is already oriented
+    pub number: u8,
+    /// Quarter turns of U (clockwise) before the algorithm: 0, U, U2 or U'
+    pub auf: u8,
+}
+
+impl OllCase {
+    /// AUF as move codes
+    pub fn auf_moves(&self) -> Vec<u8> {
+        auf_moves(self.auf)
+    }
+
+    /// Table algorithm of the case, None for the skip
+    pub fn algorithm(&self) -> Option<&'static str> {
+        OLL_ALGORITHMS
+            .get((self.number as usize).checked_sub(1)?)
+            .copied()
+    }
+}
+
+/// A PLL case with the U turns before and after its algorithm
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PllCase {
+    /// PLL name ("Ua", "T", ...), "Skip" when only an AUF is left
+    pub name: &'static str,
+    /// Quarter turns of U (clockwise) before the algorithm
+    pub pre_auf: u8,
+    /// Quarter turns of U (clockwise) after the algorithm
+    pub post_auf: u8,
+}
+
+impl PllCase {
+    /// Table algorithm of the case, None for the skip
+    pub fn algorithm(&self) -> Option<&'static str> {
+        PLL_ALGORITHMS
+            .iter()
+            .find(|(name, _)| *name == self.name)
+            .map(|&(_, alg)| alg)
+    }
+
+    /// Full solution: pre-AUF, algorithm and post-AUF as move codes
+    pub fn solution(&self) -> Vec<u8> {
+        let mut moves = auf_moves(self.pre_auf);
+        if let Some(alg) = self.algorithm() {
+            moves.extend(algorithm::parse_algorithm(alg).expect("valid PLL algorithm"));
+        }
+        moves.extend(auf_moves(self.post_auf));
+        moves
+    }
+}
+
+/// Move codes of `turns` quarter turns of U
+pub fn auf_moves(turns: u8) -> Vec<u8> {
+    match turns % 4 {
+        0 => Vec::new(),
+        1 => vec![MoveCode::U as u8],
+        2 => vec![MoveCode::U2 as u8],
+        _ => vec![MoveCode::Up as u8],
+    }
+}
+
+/// Cube after `turns` quarter turns of U
+pub fn after_auf(cube: &CubieCube, turns: u8) -> CubieCube {
+    let mut turned = *cube;
+    for _ in 0..turns {
+        turned.apply_move(MoveCode::U as u8).expect("face move");
+    }
+    turned
+}
+
+fn orientation_pattern(cube: &CubieCube) -> OrientationPattern {
+    let mut pattern = [0u8; 8];
+    pattern[..4].copy_from_slice(&cube.co[..4]);
+    pattern[4..].copy_from_slice(&cube.eo[..4]);
+    pattern
+}
+
+/// Orientation pattern of each OLL case, from the inverse of its algorithm
+fn oll_patterns() -> &'static [OrientationPattern] {
+    static PATTERNS: OnceLock<Vec<OrientationPattern>> = OnceLock::new();
+    PATTERNS.get_or_init(|| {
+        OLL_ALGORITHMS
+            .iter()
+            .map(|alg| {
+                let moves = algorithm::parse_algorithm(alg).expect("valid OLL algorithm");
+                orientation_pattern(&algorithm::case_state(&moves).expect("valid OLL case"))
+            })
+            .collect()
+    })
+}
+
+/// OLL case of a cube with the first two layers solved
+pub fn recognize_oll(cube: &CubieCube) -> Result<OllCase, String> {
+    if !is_f2l_solved(cube) {
+        return Err("First two layers are not solved".to_string());
+    }
+    if orientation_pattern(cube) == [0; 8] {
+        return Ok(OllCase { number: 0, auf: 0 });
+    }
+    for auf in 0..4 {
+        let pattern = orientation_pattern(&after_auf(cube, auf));
+        if let Some(index) = oll_patterns().iter().position(|&case| case == pattern) {
+            return Ok(OllCase {
+                number: index as u8 + 1,
+                auf,
+            });
+        }
+    }
+    Err("Unknown OLL case".to_string())
+}
+
+/// Effect of each PLL algorithm as a cubie cube
+fn pll_effects() -> &'static [CubieCube] {
+    static EFFECTS: OnceLock<Vec<CubieCube>> = OnceLock::new();
+    EFFECTS.get_or_init(|| {
+        PLL_ALGORITHMS
+            .iter()
+            .map(|(_, alg)| {
+                let moves = algorithm::parse_algorithm(alg).expect("valid PLL algorithm");
+                algorithm::state_after(&moves).expect("valid PLL algorithm")
+            })
+            .collect()
+    })
+}
+
+/// Quarter turns of U that solve the cube, if any
+fn solving_auf(cube: &CubieCube) -> Option<u8> {
+    (0..4).find(|&turns| after_auf(cube, turns).is_solved())
+}
+
+/// PLL case of a cube with the first two layers solved and the last layer oriented
+pub fn recognize_pll(cube: &CubieCube) -> Result<PllCase, String> {
+    if !is_f2l_solved(cube) || orientation_pattern(cube) != [0; 8] {
+        return Err("Last layer is not oriented".to_string());
+    }
+    if let Some(post_auf) = solving_auf(cube) {
+        return Ok(PllCase {
+            name: "Skip",
+            pre_auf: 0,
+            post_auf,
+        });
+    }
+    for pre_auf in 0..4 {
+        let turned = after_auf(cube, pre_auf);
+        for (&(name, _), effect) in PLL_ALGORITHMS.iter().zip(pll_effects()) {
+            if let Some(post_auf) = solving_auf(&turned.multiply(effect)) {
+                return Ok(PllCase {
+                    name,
+                    pre_auf,
+                    post_auf,
+                });
+            }
+        }
+    }
+    Err("Unknown PLL case".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Rng;
+    use crate::subset_scrambler::{self, Subset};
+    use std::collections::HashSet;
+
+    /// Orientation pattern up to AUF (smallest of the four)
+    fn pattern_class(cube: &CubieCube) -> OrientationPattern {
+        (0..4)
+            .map(|auf| orientation_pattern(&after_auf(cube, auf)))
+            .min()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_oll_table_covers_every_case_once() {
+        let mut classes = HashSet::new();
+        for (index, alg) in OLL_ALGORITHMS.iter().enumerate() {
+            let moves = algorithm::parse_algorithm(alg).unwrap();
+            let case = algorithm::case_state(&moves).unwrap();
+            assert!(is_f2l_solved(&case), "OLL {} breaks F2L", index + 1);
+            assert_ne!(orientation_pattern(&case), [0; 8], "OLL {}", index + 1);
+            assert!(
+                classes.insert(pattern_class(&case)),
+                "OLL {} repeats a case",
+                index + 1
+            );
+        }
+        assert_eq!(classes.len(), 57);
+    }
+
+    #[test]
+    fn test_recognize_oll_of_random_last_layers() {
+        let mut rng = Rng::new(39);
+        for _ in 0..50 {
+            let state = subset_scrambler::random_subset_state(&Subset::LastLayer.spec(), &mut rng);
+            let case = recognize_oll(&state).unwrap();
+            let mut cube = state.to_optimized();
+            cube.apply_moves(&case.auf_moves());
+            if let Some(alg) = case.algorithm() {
+                cube.apply_moves(&algorithm::parse_algorithm(alg).unwrap());
+            }
+            let solved = CubieCube::from_optimized(&cube).unwrap();
+            assert!(is_f2l_solved(&solved));
+            assert_eq!(orientation_pattern(&solved), [0; 8], "{:?}", case);
+        }
+
+        let sune = algorithm::parse_algorithm("R U R' U R U2 R'").unwrap();
+        let case = recognize_oll(&algorithm::case_state(&sune).unwrap()).unwrap();
+        assert_eq!(case, OllCase { number: 27, auf: 0 });
+        assert_eq!(
+            recognize_oll(&CubieCube::solved()),
+            Ok(OllCase { number: 0, auf: 0 })
+        );
+        let mut broken = CubieCube::solved();
+        broken.apply_move(MoveCode::R as u8).unwrap();
+        assert!(recognize_oll(&broken).is_err());
+    }
+
+    #[test]
+    fn test_pll_table_covers_every_case_once() {
+        for (name, alg) in PLL_ALGORITHMS {
+            let moves = algorithm::parse_algorithm(alg).unwrap();
+            let case = algorithm::case_state(&moves).unwrap();
+            assert!(is_f2l_solved(&case), "{} breaks F2L", name);
+            assert_eq!(orientation_pattern(&case), [0; 8], "{} is not a PLL", name);
+            // Each algorithm is recognised as its own case, never as an earlier one
+            assert_eq!(recognize_pll(&case).unwrap().name, name);
+        }
+        // Whether a case leaves the edges and the corners in place
+        let pieces_in_place = |name: &str| {
+            let (_, alg) = PLL_ALGORITHMS.iter().find(|(n, _)| *n == name).unwrap();
+            let case = algorithm::case_state(&algorithm::parse_algorithm(alg).unwrap()).unwrap();
+            (
+                case.ep == CubieCube::solved().ep,
+                case.cp == CubieCube::solved().cp,
+            )
+        };
+        for name in ["H", "Ua", "Ub", "Z"] {
+            assert_eq!(pieces_in_place(name), (false, true), "{}", name);
+        }
+        for name in ["Aa", "Ab", "E"] {
+            assert_eq!(pieces_in_place(name), (true, false), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_recognize_pll_with_aufs() {
+        let mut rng = Rng::new(40);
+        let mut names = HashSet::new();
+        for _ in 0..300 {
+            let state = subset_scrambler::random_subset_state(&Subset::Pll.spec(), &mut rng);
+            let case = recognize_pll(&state).unwrap();
+            names.insert(case.name);
+            let mut cube = state.to_optimized();
+            cube.apply_moves(&case.solution());
+            assert!(
+                CubieCube::from_optimized(&cube).unwrap().is_solved(),
+                "{:?}",
+                case
+            );
+        }
+        // Every case and the skip turn up among random permutations
+        assert_eq!(names.len(), 22);
+
+        let mut cube =
+            algorithm::case_state(&algorithm::parse_algorithm("M2 U M U2 M' U M2").unwrap())
+                .unwrap();
+        cube.apply_move(MoveCode::U as u8).unwrap();
+        let case = recognize_pll(&cube).unwrap();
+        assert_eq!(case.name, "Ua");
+        assert!(recognize_pll(&algorithm::case_state(&[MoveCode::F as u8]).unwrap()).is_err());
+    }
+}
@@ -23,12 +23,42 @@
 use wasm_bindgen::prelude::*;
 
 // Core cube implementation
+pub mod algorithm;
+pub mod algorithm_sets;
+pub mod analysis;
+pub mod analysis_bindings;
+pub mod beginner;
+pub mod blindfolded;
+pub mod cfop;
+pub mod commutators;
+pub mod cross;
+pub mod cubie_cube;
+pub mod cycles;
+pub mod eoline;
+pub mod equivalence;
+pub mod ergonomics;
+pub mod f2l;
+pub mod first_block;
+pub mod insertions;
+pub mod last_layer;
 pub mod optimized_cube;
+pub mod pieces;
+pub mod rng;
+pub mod scramble_filter;
 pub mod scramble_utils;
+pub mod scrambler;
+pub mod search;
+pub mod subset_scrambler;
+pub mod two_phase;
+pub mod verification;
 pub mod wasm_bindings;
+pub mod xcross;
 
 // Re-export the primary types for easier access
+pub use analysis_bindings::CubeAnalysis;
+pub use cubie_cube::CubieCube;
 pub use optimized_cube::{MoveCode, OptimizedCube};
+pub use rng::Rng;
 pub use scramble_utils::{AlgorithmPatterns, ScrambleUtils};
 pub use wasm_bindings::{CubeColors, MoveUtils, PerfTest, WasmOptimizedCube};
 
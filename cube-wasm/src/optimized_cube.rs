@@ -9,10 +9,33 @@ use js_sys;
 pub struct OptimizedCube {
     stickers: [u8; 54],
 }
+/// Sticker permutation for an x rotation (whole cube turned like R).
+/// Entry `i` holds the index of the sticker that moves into position `i`.
+const X_ROTATION: [u8; 54] = [
+    18, 19, 20, 21, 22, 23, 24, 25, 26, 35, 34, 33, 32, 31, 30, 29, 28, 27, 9, 10, 11, 12, 13, 14,
+    15, 16, 17, 8, 7, 6, 5, 4, 3, 2, 1, 0, 42, 39, 36, 43, 40, 37, 44, 41, 38, 47, 50, 53, 46, 49,
+    52, 45, 48, 51,
+];
+/// Sticker permutation for a y rotation (whole cube turned like U).
+const Y_ROTATION: [u8; 54] = [
+    6, 3, 0, 7, 4, 1, 8, 5, 2, 11, 14, 17, 10, 13, 16, 9, 12, 15, 36, 37, 38, 39, 40, 41, 42, 43,
+    44, 45, 46, 47, 48, 49, 50, 51, 52, 53, 27, 28, 29, 30, 31, 32, 33, 34, 35, 18, 19, 20, 21, 22,
+    23, 24, 25, 26,
+];
+/// Sticker permutation for a z rotation (whole cube turned like F).
+const Z_ROTATION: [u8; 54] = [
+    51, 48, 45, 52, 49, 46, 53, 50, 47, 42, 39, 36, 43, 40, 37, 44, 41, 38, 24, 21, 18, 25, 22, 19,
+    26, 23, 20, 29, 32, 35, 28, 31, 34, 27, 30, 33, 6, 3, 0, 7, 4, 1, 8, 5, 2, 15, 12, 9, 16, 13,
+    10, 17, 14, 11,
+];
 // Move encoding: each move is represented as a single byte enum value
 // 0-5: U, D, F, B, R, L (90° clockwise rotations)
 // 6-11: U', D', F', B', R', L' (90° counter-clockwise rotations)
 // 12-17: U2, D2, F2, B2, R2, L2 (180° double rotations)
+// 18-35: Uw..Lw, Uw'..Lw', Uw2..Lw2 (wide moves, same layout as above)
+// For codes 0-35, `code % 6` is the face being turned
+// 36-44: M, E, S, M', E', S', M2, E2, S2 (slice moves)
+// 45-53: x, y, z, x', y', z', x2, y2, z2 (whole-cube rotations)
 #[repr(u8)]
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum MoveCode {
@@ -34,10 +57,53 @@ pub enum MoveCode {
     B2 = 15,
     R2 = 16,
     L2 = 17,
+    Uw = 18,
+    Dw = 19,
+    Fw = 20,
+    Bw = 21,
+    Rw = 22,
+    Lw = 23,
+    Uwp = 24,
+    Dwp = 25,
+    Fwp = 26,
+    Bwp = 27,
+    Rwp = 28,
+    Lwp = 29,
+    Uw2 = 30,
+    Dw2 = 31,
+    Fw2 = 32,
+    Bw2 = 33,
+    Rw2 = 34,
+    Lw2 = 35,
+    M = 36,
+    E = 37,
+    S = 38,
+    Mp = 39,
+    Ep = 40,
+    Sp = 41,
+    M2 = 42,
+    E2 = 43,
+    S2 = 44,
+    X = 45,
+    Y = 46,
+    Z = 47,
+    Xp = 48,
+    Yp = 49,
+    Zp = 50,
+    X2 = 51,
+    Y2 = 52,
+    Z2 = 53,
 }
 impl MoveCode {
+    /// Number of valid move codes (0-53)
+    pub const COUNT: u8 = 54;
+    /// Number of codes that turn outer layers (face and wide moves, 0-35)
+    /// Only these may appear in a scramble.
+    pub const OUTER_COUNT: u8 = 36;
     /// Parse a move string into a MoveCode enum variant
-    /// Supports standard notation: U, U', U2, etc.
+    /// Supports standard notation: U, U', U2, wide moves Uw, Uw', Uw2, slice moves
+    /// M, E, S and rotations x, y, z
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(move_str: &str) -> Result<Self, String> {
         match move_str {
             "U" => Ok(MoveCode::U),
@@ -58,18 +124,134 @@ impl MoveCode {
             "B2" => Ok(MoveCode::B2),
             "R2" => Ok(MoveCode::R2),
             "L2" => Ok(MoveCode::L2),
+            "Uw" => Ok(MoveCode::Uw),
+            "Dw" => Ok(MoveCode::Dw),
+            "Fw" => Ok(MoveCode::Fw),
+            "Bw" => Ok(MoveCode::Bw),
+            "Rw" => Ok(MoveCode::Rw),
+            "Lw" => Ok(MoveCode::Lw),
+            "Uw'" => Ok(MoveCode::Uwp),
+            "Dw'" => Ok(MoveCode::Dwp),
+            "Fw'" => Ok(MoveCode::Fwp),
+            "Bw'" => Ok(MoveCode::Bwp),
+            "Rw'" => Ok(MoveCode::Rwp),
+            "Lw'" => Ok(MoveCode::Lwp),
+            "Uw2" => Ok(MoveCode::Uw2),
+            "Dw2" => Ok(MoveCode::Dw2),
+            "Fw2" => Ok(MoveCode::Fw2),
+            "Bw2" => Ok(MoveCode::Bw2),
+            "Rw2" => Ok(MoveCode::Rw2),
+            "Lw2" => Ok(MoveCode::Lw2),
+            "M" => Ok(MoveCode::M),
+            "E" => Ok(MoveCode::E),
+            "S" => Ok(MoveCode::S),
+            "M'" => Ok(MoveCode::Mp),
+            "E'" => Ok(MoveCode::Ep),
+            "S'" => Ok(MoveCode::Sp),
+            "M2" => Ok(MoveCode::M2),
+            "E2" => Ok(MoveCode::E2),
+            "S2" => Ok(MoveCode::S2),
+            "x" => Ok(MoveCode::X),
+            "y" => Ok(MoveCode::Y),
+            "z" => Ok(MoveCode::Z),
+            "x'" => Ok(MoveCode::Xp),
+            "y'" => Ok(MoveCode::Yp),
+            "z'" => Ok(MoveCode::Zp),
+            "x2" => Ok(MoveCode::X2),
+            "y2" => Ok(MoveCode::Y2),
+            "z2" => Ok(MoveCode::Z2),
             _ => Err(format!("Invalid move: {}", move_str)),
         }
     }
-    /// Convert a byte value (0-17) to a MoveCode enum variant
+    /// Convert a byte value (0-53) to a MoveCode enum variant
     /// Used for efficient move storage and batch processing
     pub fn from_u8(code: u8) -> Result<Self, String> {
-        if code <= 17 {
-            Ok(unsafe { std::mem::transmute(code) })
+        if code < Self::COUNT {
+            Ok(unsafe { std::mem::transmute::<u8, MoveCode>(code) })
         } else {
             Err(format!("Invalid move code: {}", code))
         }
     }
+    /// Standard notation for this move (e.g. "R'", "Uw2")
+    pub fn notation(self) -> &'static str {
+        match self {
+            MoveCode::U => "U",
+            MoveCode::D => "D",
+            MoveCode::F => "F",
+            MoveCode::B => "B",
+            MoveCode::R => "R",
+            MoveCode::L => "L",
+            MoveCode::Up => "U'",
+            MoveCode::Dp => "D'",
+            MoveCode::Fp => "F'",
+            MoveCode::Bp => "B'",
+            MoveCode::Rp => "R'",
+            MoveCode::Lp => "L'",
+            MoveCode::U2 => "U2",
+            MoveCode::D2 => "D2",
+            MoveCode::F2 => "F2",
+            MoveCode::B2 => "B2",
+            MoveCode::R2 => "R2",
+            MoveCode::L2 => "L2",
+            MoveCode::Uw => "Uw",
+            MoveCode::Dw => "Dw",
+            MoveCode::Fw => "Fw",
+            MoveCode::Bw => "Bw",
+            MoveCode::Rw => "Rw",
+            MoveCode::Lw => "Lw",
+            MoveCode::Uwp => "Uw'",
+            MoveCode::Dwp => "Dw'",
+            MoveCode::Fwp => "Fw'",
+            MoveCode::Bwp => "Bw'",
+            MoveCode::Rwp => "Rw'",
+            MoveCode::Lwp => "Lw'",
+            MoveCode::Uw2 => "Uw2",
+            MoveCode::Dw2 => "Dw2",
+            MoveCode::Fw2 => "Fw2",
+            MoveCode::Bw2 => "Bw2",
+            MoveCode::Rw2 => "Rw2",
+            MoveCode::Lw2 => "Lw2",
+            MoveCode::M => "M",
+            MoveCode::E => "E",
+            MoveCode::S => "S",
+            MoveCode::Mp => "M'",
+            MoveCode::Ep => "E'",
+            MoveCode::Sp => "S'",
+            MoveCode::M2 => "M2",
+            MoveCode::E2 => "E2",
+            MoveCode::S2 => "S2",
+            MoveCode::X => "x",
+            MoveCode::Y => "y",
+            MoveCode::Z => "z",
+            MoveCode::Xp => "x'",
+            MoveCode::Yp => "y'",
+            MoveCode::Zp => "z'",
+            MoveCode::X2 => "x2",
+            MoveCode::Y2 => "y2",
+            MoveCode::Z2 => "z2",
+        }
+    }
+    /// Move that undoes this one (R -> R', Uw' -> Uw, M2 -> M2, x -> x')
+    pub fn inverse(self) -> MoveCode {
+        let code = self as u8;
+        // Blocks of one direction are 6 codes wide for face and wide moves, 3 otherwise
+        let (first, width) = if code < Self::OUTER_COUNT {
+            (0, 6)
+        } else {
+            (Self::OUTER_COUNT, 3)
+        };
+        let inverse = match (code - first) / width % 3 {
+            0 => code + width,
+            1 => code - width,
+            _ => code,
+        };
+        MoveCode::from_u8(inverse).expect("inverse of a valid move code")
+    }
+}
+impl Default for OptimizedCube {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 impl OptimizedCube {
     /// Color constants for cube faces (matches standard color scheme)
@@ -108,6 +290,7 @@ impl OptimizedCube {
     const R: usize = 4;
     const L: usize = 5;
     /// Create a cube in solved state with each face showing its own color
+    #[allow(clippy::needless_range_loop)]
     pub fn solved() -> OptimizedCube {
         let mut stickers = [0u8; 54];
         // Fill each face with its color
@@ -165,6 +348,72 @@ impl OptimizedCube {
             MoveCode::B2 => self.apply_b_double(),
             MoveCode::R2 => self.apply_r_double(),
             MoveCode::L2 => self.apply_l_double(),
+            // Wide moves turn the opposite face and rotate the whole cube,
+            // e.g. Rw = L x and Uw = D y
+            MoveCode::Uw => self.apply_wide(MoveCode::D, &Y_ROTATION, 1),
+            MoveCode::Dw => self.apply_wide(MoveCode::U, &Y_ROTATION, 3),
+            MoveCode::Fw => self.apply_wide(MoveCode::B, &Z_ROTATION, 1),
+            MoveCode::Bw => self.apply_wide(MoveCode::F, &Z_ROTATION, 3),
+            MoveCode::Rw => self.apply_wide(MoveCode::L, &X_ROTATION, 1),
+            MoveCode::Lw => self.apply_wide(MoveCode::R, &X_ROTATION, 3),
+            MoveCode::Uwp => self.apply_wide(MoveCode::Dp, &Y_ROTATION, 3),
+            MoveCode::Dwp => self.apply_wide(MoveCode::Up, &Y_ROTATION, 1),
+            MoveCode::Fwp => self.apply_wide(MoveCode::Bp, &Z_ROTATION, 3),
+            MoveCode::Bwp => self.apply_wide(MoveCode::Fp, &Z_ROTATION, 1),
+            MoveCode::Rwp => self.apply_wide(MoveCode::Lp, &X_ROTATION, 3),
+            MoveCode::Lwp => self.apply_wide(MoveCode::Rp, &X_ROTATION, 1),
+            MoveCode::Uw2 => self.apply_wide(MoveCode::D2, &Y_ROTATION, 2),
+            MoveCode::Dw2 => self.apply_wide(MoveCode::U2, &Y_ROTATION, 2),
+            MoveCode::Fw2 => self.apply_wide(MoveCode::B2, &Z_ROTATION, 2),
+            MoveCode::Bw2 => self.apply_wide(MoveCode::F2, &Z_ROTATION, 2),
+            MoveCode::Rw2 => self.apply_wide(MoveCode::L2, &X_ROTATION, 2),
+            MoveCode::Lw2 => self.apply_wide(MoveCode::R2, &X_ROTATION, 2),
+            // Slice moves turn both outer layers and rotate them back,
+            // e.g. M = R L' x' and E = U D' y'
+            MoveCode::M => self.apply_slice(MoveCode::R, MoveCode::Lp, &X_ROTATION, 3),
+            MoveCode::E => self.apply_slice(MoveCode::U, MoveCode::Dp, &Y_ROTATION, 3),
+            MoveCode::S => self.apply_slice(MoveCode::Fp, MoveCode::B, &Z_ROTATION, 1),
+            MoveCode::Mp => self.apply_slice(MoveCode::Rp, MoveCode::L, &X_ROTATION, 1),
+            MoveCode::Ep => self.apply_slice(MoveCode::Up, MoveCode::D, &Y_ROTATION, 1),
+            MoveCode::Sp => self.apply_slice(MoveCode::F, MoveCode::Bp, &Z_ROTATION, 3),
+            MoveCode::M2 => self.apply_slice(MoveCode::R2, MoveCode::L2, &X_ROTATION, 2),
+            MoveCode::E2 => self.apply_slice(MoveCode::U2, MoveCode::D2, &Y_ROTATION, 2),
+            MoveCode::S2 => self.apply_slice(MoveCode::F2, MoveCode::B2, &Z_ROTATION, 2),
+            MoveCode::X => self.apply_rotation(&X_ROTATION, 1),
+            MoveCode::Y => self.apply_rotation(&Y_ROTATION, 1),
+            MoveCode::Z => self.apply_rotation(&Z_ROTATION, 1),
+            MoveCode::Xp => self.apply_rotation(&X_ROTATION, 3),
+            MoveCode::Yp => self.apply_rotation(&Y_ROTATION, 3),
+            MoveCode::Zp => self.apply_rotation(&Z_ROTATION, 3),
+            MoveCode::X2 => self.apply_rotation(&X_ROTATION, 2),
+            MoveCode::Y2 => self.apply_rotation(&Y_ROTATION, 2),
+            MoveCode::Z2 => self.apply_rotation(&Z_ROTATION, 2),
+        }
+    }
+    /// Apply a wide move as an outer face turn followed by a whole-cube rotation
+    fn apply_wide(&mut self, face_move: MoveCode, rotation: &[u8; 54], quarter_turns: usize) {
+        self.apply_move_code(face_move);
+        self.apply_rotation(rotation, quarter_turns);
+    }
+    /// Apply a slice move as two opposite face turns followed by a whole-cube rotation
+    fn apply_slice(
+        &mut self,
+        first: MoveCode,
+        second: MoveCode,
+        rotation: &[u8; 54],
+        quarter_turns: usize,
+    ) {
+        self.apply_move_code(first);
+        self.apply_move_code(second);
+        self.apply_rotation(rotation, quarter_turns);
+    }
+    /// Rotate the whole cube by applying a sticker permutation `quarter_turns` times
+    fn apply_rotation(&mut self, rotation: &[u8; 54], quarter_turns: usize) {
+        for _ in 0..quarter_turns {
+            let previous = self.stickers;
+            for (sticker, &source) in self.stickers.iter_mut().zip(rotation.iter()) {
+                *sticker = previous[source as usize];
+            }
         }
     }
     /// Apply U move: rotate upper face 90° clockwise
@@ -753,8 +1002,8 @@ impl OptimizedCube {
         previous_move: Option<u8>,
         before_previous_move: Option<u8>,
     ) -> bool {
-        // Check if move code is valid (0-17)
-        if move_code > 17 {
+        // Check if move code is valid (0-35, no slice moves or rotations)
+        if move_code >= MoveCode::OUTER_COUNT {
             return false;
         }
         let current_face = move_code % 6;
@@ -778,14 +1027,13 @@ impl OptimizedCube {
         }
         true
     }
-    /// Generate a random scramble of the specified length
-    /// Follows WCA rules: no consecutive moves on same face, no 3 moves on same axis
-    pub fn generate_random_scramble(length: usize) -> Vec<u8> {
-        let mut moves = Vec::with_capacity(length);
+    /// Advance the shared scramble generator and return the next raw value
+    /// The seed is initialized from a random source on first use
+    fn next_random() -> u64 {
         static mut SEED: u64 = 0;
         static mut INITIALIZED: bool = false;
-        // Initialize seed with random value on first use
         unsafe {
+            // Initialize seed with random value on first use
             if !INITIALIZED {
                 #[cfg(target_arch = "wasm32")]
                 {
@@ -807,31 +1055,55 @@ impl OptimizedCube {
                 }
                 INITIALIZED = true;
             }
+            SEED = SEED.wrapping_mul(1103515245).wrapping_add(12345);
+            SEED >> 16
         }
+    }
+    /// Generate a random scramble of the specified length
+    /// Follows WCA rules: no consecutive moves on same face, no 3 moves on same axis
+    pub fn generate_random_scramble(length: usize) -> Vec<u8> {
+        let mut moves = Vec::with_capacity(length);
         let mut attempts = 0;
         const MAX_ATTEMPTS: usize = 1000; // Prevent infinite loops
         while moves.len() < length && attempts < MAX_ATTEMPTS {
-            unsafe {
-                SEED = SEED.wrapping_mul(1103515245).wrapping_add(12345);
-                let move_code = ((SEED >> 16) % 18) as u8;
-                let previous_move = if moves.len() > 0 {
-                    Some(moves[moves.len() - 1])
-                } else {
-                    None
-                };
-                let before_previous_move = if moves.len() > 1 {
-                    Some(moves[moves.len() - 2])
-                } else {
-                    None
-                };
-                if Self::is_valid_move(move_code, previous_move, before_previous_move) {
-                    moves.push(move_code);
-                }
+            let move_code = (Self::next_random() % 18) as u8;
+            let previous_move = if !moves.is_empty() {
+                Some(moves[moves.len() - 1])
+            } else {
+                None
+            };
+            let before_previous_move = if moves.len() > 1 {
+                Some(moves[moves.len() - 2])
+            } else {
+                None
+            };
+            if Self::is_valid_move(move_code, previous_move, before_previous_move) {
+                moves.push(move_code);
             }
             attempts += 1;
         }
         moves
     }
+    /// Wide moves that bring a random face to U (WCA blindfolded orientation)
+    const BLD_UP_FACE_MOVES: [&'static [MoveCode]; 6] = [
+        &[],
+        &[MoveCode::Rw],
+        &[MoveCode::Rw2],
+        &[MoveCode::Rwp],
+        &[MoveCode::Fw],
+        &[MoveCode::Fwp],
+    ];
+    /// Wide moves that then bring a random side face to F
+    const BLD_FRONT_FACE_MOVES: [&'static [MoveCode]; 4] =
+        [&[], &[MoveCode::Uw], &[MoveCode::Uw2], &[MoveCode::Uwp]];
+    /// Wide moves for one of the 24 orientations (up face 0-5, front face 0-3)
+    pub(crate) fn bld_orientation_suffix(up_index: usize, front_index: usize) -> Vec<u8> {
+        Self::BLD_UP_FACE_MOVES[up_index]
+            .iter()
+            .chain(Self::BLD_FRONT_FACE_MOVES[front_index].iter())
+            .map(|&move_code| move_code as u8)
+            .collect()
+    }
     /// Validate a scramble sequence according to WCA rules
     pub fn validate_scramble(moves: &[u8]) -> bool {
         for i in 0..moves.len() {
@@ -860,27 +1132,8 @@ impl OptimizedCube {
     pub fn moves_to_string(moves: &[u8]) -> String {
         moves
             .iter()
-            .filter_map(|&move_code| match move_code {
-                0 => Some("U"),
-                1 => Some("D"),
-                2 => Some("F"),
-                3 => Some("B"),
-                4 => Some("R"),
-                5 => Some("L"),
-                6 => Some("U'"),
-                7 => Some("D'"),
-                8 => Some("F'"),
-                9 => Some("B'"),
-                10 => Some("R'"),
-                11 => Some("L'"),
-                12 => Some("U2"),
-                13 => Some("D2"),
-                14 => Some("F2"),
-                15 => Some("B2"),
-                16 => Some("R2"),
-                17 => Some("L2"),
-                _ => None,
-            })
+            .filter_map(|&move_code| MoveCode::from_u8(move_code).ok())
+            .map(MoveCode::notation)
             .collect::<Vec<&str>>()
             .join(" ")
     }
@@ -952,11 +1205,11 @@ mod tests {
     }
     #[test]
     fn test_move_code_conversion() {
-        for i in 0u8..18u8 {
+        for i in 0u8..54u8 {
             let move_code = MoveCode::from_u8(i).unwrap();
             assert_eq!(move_code as u8, i);
         }
-        assert!(MoveCode::from_u8(18).is_err());
+        assert!(MoveCode::from_u8(54).is_err());
         assert!(MoveCode::from_u8(255).is_err());
     }
     #[test]
@@ -2138,6 +2391,7 @@ mod tests {
         corners_correct && edges_correct && center_same
     }
 
+    #[allow(clippy::manual_memcpy, clippy::match_ref_pats)]
     fn test_all_faces_clockwise() {
         println!("\n--- ALL FACES CLOCKWISE ROTATION TEST ---");
 
@@ -2189,6 +2443,7 @@ mod tests {
         }
     }
 
+    #[allow(clippy::manual_memcpy, clippy::match_ref_pats)]
     fn test_all_faces_counterclockwise() {
         println!("\n--- ALL FACES COUNTERCLOCKWISE ROTATION TEST ---");
 
@@ -2287,6 +2542,7 @@ mod tests {
         }
     }
 
+    #[allow(clippy::manual_memcpy, clippy::match_ref_pats)]
     fn test_all_faces_double() {
         println!("\n--- ALL FACES DOUBLE ROTATION TEST ---");
 
@@ -2950,7 +3206,7 @@ mod tests {
         assert!(!OptimizedCube::validate_scramble(&invalid_axis));
 
         // Invalid move code
-        let invalid_code = vec![18]; // Invalid move code
+        let invalid_code = vec![36]; // Invalid move code
         assert!(!OptimizedCube::validate_scramble(&invalid_code));
     }
 
@@ -3046,4 +3302,132 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_wide_move_parsing() {
+        assert_eq!(MoveCode::from_str("Rw").unwrap(), MoveCode::Rw);
+        assert_eq!(MoveCode::from_str("Uw'").unwrap(), MoveCode::Uwp);
+        assert_eq!(MoveCode::from_str("Fw2").unwrap(), MoveCode::Fw2);
+        for code in 0..MoveCode::COUNT {
+            let move_code = MoveCode::from_u8(code).unwrap();
+            assert_eq!(MoveCode::from_str(move_code.notation()).unwrap(), move_code);
+        }
+    }
+
+    #[test]
+    fn test_rotations_match_face_moves() {
+        // Turning a face after a rotation equals turning the face that moved there
+        let relations = [
+            (&X_ROTATION, MoveCode::U, MoveCode::F),
+            (&X_ROTATION, MoveCode::F, MoveCode::D),
+            (&Y_ROTATION, MoveCode::F, MoveCode::R),
+            (&Y_ROTATION, MoveCode::R, MoveCode::B),
+            (&Z_ROTATION, MoveCode::U, MoveCode::L),
+            (&Z_ROTATION, MoveCode::R, MoveCode::U),
+        ];
+        let scramble = OptimizedCube::parse_scramble("R U F' L2 D B' R2 U'").unwrap();
+        for (rotation, after, before) in relations {
+            let mut rotated_first = OptimizedCube::solved();
+            rotated_first.apply_moves(&scramble);
+            rotated_first.apply_rotation(rotation, 1);
+            rotated_first.apply_move_code(after);
+
+            let mut turned_first = OptimizedCube::solved();
+            turned_first.apply_moves(&scramble);
+            turned_first.apply_move_code(before);
+            turned_first.apply_rotation(rotation, 1);
+
+            assert_eq!(rotated_first, turned_first);
+        }
+    }
+
+    #[test]
+    fn test_wide_moves() {
+        // Rw brings the F center to U and keeps the L layer in place
+        let mut cube = OptimizedCube::solved();
+        cube.apply_move("Rw").unwrap();
+        assert_eq!(cube.stickers[4], OptimizedCube::GREEN);
+        assert_eq!(cube.stickers[22], OptimizedCube::YELLOW);
+        assert!(cube.get_face(5).iter().all(|&c| c == OptimizedCube::ORANGE));
+
+        // Every wide move has order 4 and its prime undoes it
+        for face in 0..6u8 {
+            let quarter = MoveCode::from_u8(18 + face).unwrap();
+            let prime = MoveCode::from_u8(24 + face).unwrap();
+            let double = MoveCode::from_u8(30 + face).unwrap();
+
+            let mut cube = OptimizedCube::solved();
+            cube.apply_move_code(quarter);
+            assert!(!cube.is_solved());
+            cube.apply_move_code(prime);
+            assert!(cube.is_solved());
+
+            let mut twice = OptimizedCube::solved();
+            twice.apply_move_code(quarter);
+            twice.apply_move_code(quarter);
+            let mut once = OptimizedCube::solved();
+            once.apply_move_code(double);
+            assert_eq!(twice, once);
+        }
+
+        // Uw is the same as D followed by a y rotation
+        let mut wide = OptimizedCube::solved();
+        wide.apply_scramble("R F Uw").unwrap();
+        let mut expected = OptimizedCube::solved();
+        expected.apply_scramble("R F D").unwrap();
+        expected.apply_rotation(&Y_ROTATION, 1);
+        assert_eq!(wide, expected);
+    }
+
+    #[test]
+    fn test_wide_suffix_validation() {
+        // The wide suffix follows the same face and axis rules
+        assert!(!OptimizedCube::validate_scramble(&[4, 22])); // R Rw
+        assert!(!OptimizedCube::validate_scramble(&[4, 5, 22])); // R L Rw
+        assert!(OptimizedCube::validate_scramble(&[4, 0, 22, 18])); // R U Rw Uw
+    }
+
+    #[test]
+    fn test_slice_moves_and_rotations() {
+        // Each wide move is the face move plus the adjacent slice
+        let identities = [
+            ("Rw", "R M'"),
+            ("Lw", "L M"),
+            ("Uw", "U E'"),
+            ("Dw", "D E"),
+            ("Fw", "F S"),
+            ("Bw", "B S'"),
+            ("x", "R M' L'"),
+            ("y", "U E' D'"),
+            ("z", "F S B'"),
+            ("M2", "M M"),
+            ("y2", "y y"),
+        ];
+        for (left, right) in identities {
+            let mut expected = OptimizedCube::solved();
+            expected.apply_scramble("R U F' L2 D B'").unwrap();
+            let mut actual = expected.clone();
+            expected.apply_scramble(left).unwrap();
+            actual.apply_scramble(right).unwrap();
+            assert_eq!(expected, actual, "{} != {}", left, right);
+        }
+
+        // Every move followed by its inverse is the identity
+        for code in 0..MoveCode::COUNT {
+            let move_code = MoveCode::from_u8(code).unwrap();
+            let mut cube = OptimizedCube::solved();
+            cube.apply_scramble("R U F'").unwrap();
+            let before = cube.clone();
+            cube.apply_move_code(move_code);
+            cube.apply_move_code(move_code.inverse());
+            assert_eq!(cube, before, "{}", move_code.notation());
+        }
+        assert_eq!(MoveCode::Uwp.inverse(), MoveCode::Uw);
+        assert_eq!(MoveCode::S.inverse(), MoveCode::Sp);
+        assert_eq!(MoveCode::Z2.inverse(), MoveCode::Z2);
+
+        // Slice moves and rotations are not allowed in scrambles
+        assert!(!OptimizedCube::validate_scramble(&[MoveCode::M as u8]));
+        assert!(!OptimizedCube::validate_scramble(&[MoveCode::Y as u8]));
+    }
 }
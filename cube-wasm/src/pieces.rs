@@ -0,0 +1,344 @@
+// Piece-level search spaces for the step solvers
+// Every corner (8 positions x 3 twists) and every edge (12 positions x 2 flips) has exactly
+// 24 states, so a group of up to four pieces fits a 24^4 distance table. Tables are built by
+// breadth-first search on first use and cached by piece list.
+use crate::cubie_cube::CubieCube;
+use crate::search::SearchSpace;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::sync::{Arc, Mutex, OnceLock};
+
+const N_MOVES: usize = 18;
+/// States of a single corner or edge
+pub const N_PIECE_STATES: usize = 24;
+/// Most pieces a search space can track
+pub const MAX_PIECES: usize = 12;
+/// Most pieces in one distance table
+pub const MAX_TABLE_PIECES: usize = 4;
+const UNVISITED: u8 = u8::MAX;
+
+/// A corner or edge cubie, by its home position index
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Piece {
+    Corner(u8),
+    Edge(u8),
+}
+
+struct PieceMoves {
+    corner: [[u8; N_MOVES]; N_PIECE_STATES],
+    edge: [[u8; N_MOVES]; N_PIECE_STATES],
+}
+
+static PIECE_MOVES: OnceLock<PieceMoves> = OnceLock::new();
+
+fn piece_moves() -> &'static PieceMoves {
+    PIECE_MOVES.get_or_init(|| {
+        let mut corner = [[0u8; N_MOVES]; N_PIECE_STATES];
+        let mut edge = [[0u8; N_MOVES]; N_PIECE_STATES];
+        let move_cubes = (0..N_MOVES as u8).map(|m| CubieCube::move_cube(m).expect("face move"));
+        // A move takes the cubie at position move.cp[i] (or ep[i]) to position i
+        for (m, move_cube) in move_cubes.enumerate() {
+            for position in 0..8 {
+                let source = move_cube.cp[position] as usize;
+                for twist in 0..3 {
+                    let new_twist = (twist + move_cube.co[position] as usize) % 3;
+                    corner[source * 3 + twist][m] = (position * 3 + new_twist) as u8;
+                }
+            }
+            for position in 0..12 {
+                let source = move_cube.ep[position] as usize;
+                for flip in 0..2 {
+                    let new_flip = flip ^ move_cube.eo[position] as usize;
+                    edge[source * 2 + flip][m] = (position * 2 + new_flip) as u8;
+                }
+            }
+        }
+        PieceMoves { corner, edge }
+    })
+}
+
+impl Piece {
+    /// Position and orientation of this piece as a value in 0..24
+    pub fn state(self, cube: &CubieCube) -> u8 {
+        match self {
+            Piece::Corner(piece) => {
+                let position = cube.cp.iter().position(|&p| p == piece).expect("placed");
+                (position * 3) as u8 + cube.co[position]
+            }
+            Piece::Edge(piece) => {
+                let position = cube.ep.iter().position(|&p| p == piece).expect("placed");
+                (position * 2) as u8 + cube.eo[position]
+            }
+        }
+    }
+
+    /// State of this piece on a solved cube
+    pub fn solved_state(self) -> u8 {
+        match self {
+            Piece::Corner(piece) => piece * 3,
+            Piece::Edge(piece) => piece * 2,
+        }
+    }
+
+    /// State after face move `m`
+    pub fn apply(self, state: u8, m: u8) -> u8 {
+        let moves = piece_moves();
+        match self {
+            Piece::Corner(_) => moves.corner[state as usize][m as usize],
+            Piece::Edge(_) => moves.edge[state as usize][m as usize],
+        }
+    }
+}
+
+/// Exact move counts to solve a group of up to four pieces
+pub struct PieceTable {
+    distance: Vec<u8>,
+}
+
+static TABLE_CACHE: OnceLock<Mutex<HashMap<Vec<Piece>, Arc<PieceTable>>>> = OnceLock::new();
+
+impl PieceTable {
+    /// Table for a piece group, built on first request
+    pub fn get(pieces: &[Piece]) -> Arc<PieceTable> {
+        assert!(pieces.len() <= MAX_TABLE_PIECES, "piece group too large");
+        let cache = TABLE_CACHE.get_or_init(|| Mutex::new(HashMap::new()));
+        let mut cache = cache.lock().expect("table cache poisoned");
+        cache
+            .entry(pieces.to_vec())
+            .or_insert_with(|| Arc::new(PieceTable::build(pieces)))
+            .clone()
+    }
+
+    fn build(pieces: &[Piece]) -> PieceTable {
+        let size = N_PIECE_STATES.pow(pieces.len() as u32);
+        let mut distance = vec![UNVISITED; size];
+        let count = pieces.len();
+        let mut states = [0u8; MAX_TABLE_PIECES];
+        for (state, piece) in states.iter_mut().zip(pieces) {
+            *state = piece.solved_state();
+        }
+        let solved = table_index(&states[..count]);
+        distance[solved] = 0;
+        let mut frontier = vec![solved];
+        let mut depth = 0;
+        let mut moved = [0u8; MAX_TABLE_PIECES];
+        while !frontier.is_empty() {
+            let mut next = Vec::new();
+            for &index in &frontier {
+                table_unindex(index, &mut states[..count]);
+                for m in 0..N_MOVES as u8 {
+                    for ((target, &state), piece) in moved.iter_mut().zip(&states).zip(pieces) {
+                        *target = piece.apply(state, m);
+                    }
+                    let target = table_index(&moved[..count]);
+                    if distance[target] == UNVISITED {
+                        distance[target] = depth + 1;
+                        next.push(target);
+                    }
+                }
+            }
+            frontier = next;
+            depth += 1;
+        }
+        PieceTable { distance }
+    }
+
+    /// Moves needed to solve the group from the given piece states (in table order)
+    pub fn distance(&self, states: &[u8]) -> u8 {
+        self.distance[table_index(states)]
+    }
+}
+
+/// Table index of a group's piece states
+fn table_index(states: &[u8]) -> usize {
+    states
+        .iter()
+        .fold(0, |index, &state| index * N_PIECE_STATES + state as usize)
+}
+
+/// Piece states of a table index
+fn table_unindex(mut index: usize, states: &mut [u8]) {
+    for state in states.iter_mut().rev() {
+        *state = (index % N_PIECE_STATES) as u8;
+        index /= N_PIECE_STATES;
+    }
+}
+
+/// Cheapest way to bring up to four pieces to a `goal` by chaining move sequences
+/// Dijkstra over the pieces' states, counting moves. Sequences that keep other pieces
+/// intact (triggers, algorithms) make this a method-style solver for a single step.
+/// Returns the chained sequences, or None if the goal cannot be reached.
+pub fn sequence_solution(
+    pieces: &[Piece],
+    cube: &CubieCube,
+    sequences: &[Vec<u8>],
+    goal: impl Fn(&[u8]) -> bool,
+) -> Option<Vec<u8>> {
+    assert!(pieces.len() <= MAX_TABLE_PIECES, "piece group too large");
+    let count = pieces.len();
+    let mut states = [0u8; MAX_TABLE_PIECES];
+    for (state, piece) in states.iter_mut().zip(pieces) {
+        *state = piece.state(cube);
+    }
+    let start = table_index(&states[..count]);
+    let size = N_PIECE_STATES.pow(count as u32);
+    let mut cost = vec![usize::MAX; size];
+    // Previous state and the sequence leading from it
+    let mut previous = vec![(0usize, 0usize); size];
+    let mut queue = BinaryHeap::new();
+    cost[start] = 0;
+    queue.push(Reverse((0, start)));
+    let mut reached = None;
+    while let Some(Reverse((moves, index))) = queue.pop() {
+        if moves > cost[index] {
+            continue;
+        }
+        table_unindex(index, &mut states[..count]);
+        if goal(&states[..count]) {
+            reached = Some(index);
+            break;
+        }
+        for (sequence_index, sequence) in sequences.iter().enumerate() {
+            let mut moved = states;
+            for (state, piece) in moved.iter_mut().zip(pieces) {
+                *state = sequence.iter().fold(*state, |s, &m| piece.apply(s, m));
+            }
+            let next = table_index(&moved[..count]);
+            let next_moves = moves + sequence.len();
+            if next_moves < cost[next] {
+                cost[next] = next_moves;
+                previous[next] = (index, sequence_index);
+                queue.push(Reverse((next_moves, next)));
+            }
+        }
+    }
+    let mut index = reached?;
+    let mut path = Vec::new();
+    while index != start {
+        let (from, sequence_index) = previous[index];
+        path.push(sequence_index);
+        index = from;
+    }
+    Some(
+        path.iter()
+            .rev()
+            .flat_map(|&i| sequences[i].iter().copied())
+            .collect(),
+    )
+}
+
+/// Search space solving a set of pieces, bounded by distance tables over piece groups
+pub struct PieceSpace {
+    pieces: Vec<Piece>,
+    /// Each table with the indices (into `pieces`) of the pieces it covers
+    tables: Vec<(Vec<usize>, Arc<PieceTable>)>,
+}
+
+/// States of the tracked pieces, in the order they were given
+pub type PieceState = [u8; MAX_PIECES];
+
+impl PieceSpace {
+    /// Space for solving `pieces`, using one table per group
+    /// Every piece must belong to at least one group.
+    pub fn new(pieces: &[Piece], groups: &[Vec<Piece>]) -> PieceSpace {
+        assert!(pieces.len() <= MAX_PIECES, "too many pieces");
+        let tables = groups
+            .iter()
+            .map(|group| {
+                let indices = group
+                    .iter()
+                    .map(|piece| {
+                        pieces
+                            .iter()
+                            .position(|p| p == piece)
+                            .expect("grouped piece is tracked")
+                    })
+                    .collect::<Vec<usize>>();
+                (indices, PieceTable::get(group))
+            })
+            .collect::<Vec<_>>();
+        debug_assert!((0..pieces.len()).all(|i| tables.iter().any(|(group, _)| group.contains(&i))));
+        PieceSpace {
+            pieces: pieces.to_vec(),
+            tables,
+        }
+    }
+
+    /// Tracked piece states of a cube
+    pub fn state(&self, cube: &CubieCube) -> PieceState {
+        let mut state = [0u8; MAX_PIECES];
+        for (value, piece) in state.iter_mut().zip(&self.pieces) {
+            *value = piece.state(cube);
+        }
+        state
+    }
+}
+
+impl SearchSpace for PieceSpace {
+    type State = PieceState;
+
+    fn apply(&self, state: &PieceState, m: u8) -> PieceState {
+        let mut next = *state;
+        for (value, piece) in next.iter_mut().zip(&self.pieces) {
+            *value = piece.apply(*value, m);
+        }
+        next
+    }
+
+    fn lower_bound(&self, state: &PieceState) -> u8 {
+        let mut states = [0u8; MAX_TABLE_PIECES];
+        self.tables
+            .iter()
+            .map(|(indices, table)| {
+                for (value, &index) in states.iter_mut().zip(indices) {
+                    *value = state[index];
+                }
+                table.distance(&states[..indices.len()])
+            })
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::optimized_cube::OptimizedCube;
+    use crate::search::{self, Deadline};
+
+    #[test]
+    fn test_piece_moves_match_cubie_moves() {
+        let scramble = OptimizedCube::parse_scramble("R U2 F' L D B2 R'").unwrap();
+        let cube = CubieCube::from_moves(&scramble).unwrap();
+        for piece in [
+            Piece::Corner(0),
+            Piece::Corner(6),
+            Piece::Edge(3),
+            Piece::Edge(9),
+        ] {
+            let state = scramble
+                .iter()
+                .fold(piece.solved_state(), |state, &m| piece.apply(state, m));
+            assert_eq!(state, piece.state(&cube));
+        }
+    }
+
+    #[test]
+    fn test_piece_space_search_is_optimal() {
+        // The DR and DF edges with the DFR corner, bounded by a single exact table
+        let pieces = [Piece::Edge(4), Piece::Edge(5), Piece::Corner(4)];
+        let space = PieceSpace::new(&pieces, &[pieces.to_vec()]);
+        let scramble = OptimizedCube::parse_scramble("R U F").unwrap();
+        let start = space.state(&CubieCube::from_moves(&scramble).unwrap());
+        let outcome = search::search(&space, start, 10, 5, Deadline::none());
+        assert!(!outcome.timed_out);
+        assert!(!outcome.solutions.is_empty());
+        let length = space.lower_bound(&start) as usize;
+        for solution in &outcome.solutions {
+            assert_eq!(solution.len(), length);
+            let mut cube = CubieCube::from_moves(&scramble).unwrap();
+            cube.apply_moves(solution).unwrap();
+            assert_eq!(space.lower_bound(&space.state(&cube)), 0);
+        }
+    }
+}
@@ -0,0 +1,68 @@
+// Small seedable pseudo-random generator (SplitMix64)
+// Used by the random-state scramblers so that scrambles can be reproduced from a seed
+#[cfg(target_arch = "wasm32")]
+use js_sys;
+
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    /// Create a generator from a fixed seed (same seed gives the same sequence)
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    /// Create a generator seeded from a random source
+    pub fn from_entropy() -> Rng {
+        #[cfg(target_arch = "wasm32")]
+        let seed = (js_sys::Math::random() * (u64::MAX as f64)) as u64;
+        #[cfg(not(target_arch = "wasm32"))]
+        let seed = {
+            use std::time::{SystemTime, UNIX_EPOCH};
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_nanos() as u64
+        };
+        Rng::new(seed)
+    }
+
+    /// Independent generator for item `index` of a seeded batch
+    /// Item `index` only depends on the seed, not on how many items are generated.
+    pub fn for_index(seed: u64, index: u64) -> Rng {
+        let base = Rng::new(seed).next_u64();
+        Rng::new(Rng::new(base ^ index).next_u64())
+    }
+
+    /// Next raw 64-bit value
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform value in `0..bound` (bound must be non-zero)
+    pub fn below(&mut self, bound: usize) -> usize {
+        // Rejection sampling avoids modulo bias
+        let bound = bound as u64;
+        let zone = u64::MAX - u64::MAX % bound;
+        loop {
+            let value = self.next_u64();
+            if value < zone {
+                return (value % bound) as usize;
+            }
+        }
+    }
+
+    /// Shuffle a slice in place (Fisher-Yates)
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.below(i + 1);
+            items.swap(i, j);
+        }
+    }
+}
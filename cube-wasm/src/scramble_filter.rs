@@ -0,0 +1,369 @@
+// Filtered random-state scrambles
+// Random states are drawn until every filter accepts one, then only that state is solved,
+// so rejecting states is cheap. Used for "easy cross" or "bad EO" training scrambles,
+// and for blindfolded practice ("parity", "at most 16 edge targets").
+use crate::analysis;
+use crate::blindfolded::{self, BldStats, MemoOptions, MemoSettings};
+use crate::cross;
+use crate::cubie_cube::CubieCube;
+use crate::optimized_cube::OptimizedCube;
+use crate::rng::Rng;
+use crate::scrambler;
+use crate::two_phase::SolveOptions;
+use serde::Deserialize;
+
+/// Random states tried before giving up on a set of filters
+pub const MAX_FILTER_ATTEMPTS: usize = 200_000;
+
+/// A condition a scrambled state must meet
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScrambleFilter {
+    /// Optimal cross on `color` is at most this many moves
+    MaxCrossLength { color: u8, moves: u8 },
+    /// Optimal cross on `color` is at least this many moves
+    MinCrossLength { color: u8, moves: u8 },
+    /// Number of misoriented edges (F/B axis) is within `min..=max`
+    MisorientedEdges { min: u8, max: u8 },
+    /// Whether an F2L pair for the `color` cross is already formed outside its slot
+    FreePair { color: u8, present: bool },
+    /// Blindfolded corner targets (UFR buffer, cycle breaks included) within `min..=max`
+    CornerTargets { min: u8, max: u8 },
+    /// Blindfolded edge targets (UF buffer, cycle breaks included) within `min..=max`
+    EdgeTargets { min: u8, max: u8 },
+    /// Corner and edge cycle breaks within `min..=max`
+    CycleBreaks { min: u8, max: u8 },
+    /// Corners twisted in place within `min..=max`
+    TwistedCorners { min: u8, max: u8 },
+    /// Edges flipped in place within `min..=max`
+    FlippedEdges { min: u8, max: u8 },
+    /// Whether the blindfolded solve needs a parity algorithm
+    Parity { present: bool },
+    /// Whether the corner buffer starts solved
+    CornerBufferSolved { present: bool },
+    /// Whether the edge buffer starts solved
+    EdgeBufferSolved { present: bool },
+}
+
+impl ScrambleFilter {
+    /// Whether a state (in the standard orientation) passes this filter, with
+    /// blindfolded figures traced with `settings`
+    pub fn accepts(&self, state: &CubieCube, settings: &MemoSettings) -> bool {
+        accepts_all(std::slice::from_ref(self), state, settings)
+    }
+
+    /// Whether a state passes this filter, None for blindfolded filters
+    fn accepts_state(&self, state: &CubieCube) -> Option<bool> {
+        let cube = state.to_optimized();
+        let accepted = match *self {
+            ScrambleFilter::MaxCrossLength { color, moves } => {
+                cross::cross_length_for_color(&cube, color).is_ok_and(|length| length <= moves)
+            }
+            ScrambleFilter::MinCrossLength { color, moves } => {
+                cross::cross_length_for_color(&cube, color).is_ok_and(|length| length >= moves)
+            }
+            ScrambleFilter::MisorientedEdges { min, max } => {
+                (min as usize..=max as usize).contains(&analysis::misoriented_edges(state))
+            }
+            ScrambleFilter::FreePair { color, present } => cross::with_color_down(&cube, color)
+                .is_ok_and(|oriented| (analysis::free_pairs(&oriented) > 0) == present),
+            _ => return None,
+        };
+        Some(accepted)
+    }
+
+    /// Whether blindfolded figures pass this filter; other filters pass
+    fn accepts_bld(&self, stats: &BldStats) -> bool {
+        let within =
+            |count: usize, min: u8, max: u8| (min as usize..=max as usize).contains(&count);
+        match *self {
+            ScrambleFilter::CornerTargets { min, max } => within(stats.corner_targets, min, max),
+            ScrambleFilter::EdgeTargets { min, max } => within(stats.edge_targets, min, max),
+            ScrambleFilter::CycleBreaks { min, max } => within(
+                stats.corner_cycle_breaks + stats.edge_cycle_breaks,
+                min,
+                max,
+            ),
+            ScrambleFilter::TwistedCorners { min, max } => within(stats.twisted_corners, min, max),
+            ScrambleFilter::FlippedEdges { min, max } => within(stats.flipped_edges, min, max),
+            ScrambleFilter::Parity { present } => stats.parity == present,
+            ScrambleFilter::CornerBufferSolved { present } => stats.corner_buffer_solved == present,
+            ScrambleFilter::EdgeBufferSolved { present } => stats.edge_buffer_solved == present,
+            _ => true,
+        }
+    }
+}
+
+/// Whether a state passes every filter; the blindfolded memo is traced at most once,
+/// with `settings`, and only if a blindfolded filter needs it
+pub fn accepts_all(filters: &[ScrambleFilter], state: &CubieCube, settings: &MemoSettings) -> bool {
+    let mut stats: Option<Option<BldStats>> = None;
+    filters.iter().all(|filter| {
+        filter.accepts_state(state).unwrap_or_else(|| {
+            let stats = stats.get_or_insert_with(|| {
+                blindfolded::memo(&state.to_optimized(), settings)
+                    .ok()
+                    .map(|memo| memo.stats())
+            });
+            stats
+                .as_ref()
+                .is_some_and(|stats| filter.accepts_bld(stats))
+        })
+    })
+}
+
+/// Filter settings from JavaScript, e.g. `{ crossColor: 0, maxCrossLength: 4 }`
+/// Unset fields do not filter; the cross color defaults to white. Blindfolded figures
+/// are traced with the `memo` options (as for `blind_memo`, e.g. `{ edgeBuffer: "DF" }`).
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct FilterOptions {
+    pub cross_color: Option<u8>,
+    pub max_cross_length: Option<u8>,
+    pub min_cross_length: Option<u8>,
+    pub min_misoriented_edges: Option<u8>,
+    pub max_misoriented_edges: Option<u8>,
+    pub free_pair: Option<bool>,
+    pub min_corner_targets: Option<u8>,
+    pub max_corner_targets: Option<u8>,
+    pub min_edge_targets: Option<u8>,
+    pub max_edge_targets: Option<u8>,
+    pub min_cycle_breaks: Option<u8>,
+    pub max_cycle_breaks: Option<u8>,
+    pub min_twisted_corners: Option<u8>,
+    pub max_twisted_corners: Option<u8>,
+    pub min_flipped_edges: Option<u8>,
+    pub max_flipped_edges: Option<u8>,
+    pub parity: Option<bool>,
+    pub corner_buffer_solved: Option<bool>,
+    pub edge_buffer_solved: Option<bool>,
+    pub memo: MemoOptions,
+}
+
+impl FilterOptions {
+    /// Filters described by these options
+    pub fn filters(&self) -> Vec<ScrambleFilter> {
+        let color = self.cross_color.unwrap_or(OptimizedCube::WHITE);
+        let mut filters = Vec::new();
+        if let Some(moves) = self.max_cross_length {
+            filters.push(ScrambleFilter::MaxCrossLength { color, moves });
+        }
+        if let Some(moves) = self.min_cross_length {
+            filters.push(ScrambleFilter::MinCrossLength { color, moves });
+        }
+        if self.min_misoriented_edges.is_some() || self.max_misoriented_edges.is_some() {
+            filters.push(ScrambleFilter::MisorientedEdges {
+                min: self.min_misoriented_edges.unwrap_or(0),
+                max: self.max_misoriented_edges.unwrap_or(12),
+            });
+        }
+        if let Some(present) = self.free_pair {
+            filters.push(ScrambleFilter::FreePair { color, present });
+        }
+        let range = |min: Option<u8>, max: Option<u8>| {
+            (min.is_some() || max.is_some()).then(|| (min.unwrap_or(0), max.unwrap_or(u8::MAX)))
+        };
+        if let Some((min, max)) = range(self.min_corner_targets, self.max_corner_targets) {
+            filters.push(ScrambleFilter::CornerTargets { min, max });
+        }
+        if let Some((min, max)) = range(self.min_edge_targets, self.max_edge_targets) {
+            filters.push(ScrambleFilter::EdgeTargets { min, max });
+        }
+        if let Some((min, max)) = range(self.min_cycle_breaks, self.max_cycle_breaks) {
+            filters.push(ScrambleFilter::CycleBreaks { min, max });
+        }
+        if let Some((min, max)) = range(self.min_twisted_corners, self.max_twisted_corners) {
+            filters.push(ScrambleFilter::TwistedCorners { min, max });
+        }
+        if let Some((min, max)) = range(self.min_flipped_edges, self.max_flipped_edges) {
+            filters.push(ScrambleFilter::FlippedEdges { min, max });
+        }
+        if let Some(present) = self.parity {
+            filters.push(ScrambleFilter::Parity { present });
+        }
+        if let Some(present) = self.corner_buffer_solved {
+            filters.push(ScrambleFilter::CornerBufferSolved { present });
+        }
+        if let Some(present) = self.edge_buffer_solved {
+            filters.push(ScrambleFilter::EdgeBufferSolved { present });
+        }
+        filters
+    }
+
+    /// Memo settings for the blindfolded filters
+    pub fn memo_settings(&self) -> Result<MemoSettings, String> {
+        self.memo.settings()
+    }
+}
+
+/// Generate a random-state scramble whose state passes every filter
+/// Fails if no state passed within `MAX_FILTER_ATTEMPTS` draws.
+pub fn filtered_scramble(
+    filters: &[ScrambleFilter],
+    settings: &MemoSettings,
+    rng: &mut Rng,
+) -> Result<Vec<u8>, String> {
+    filtered_state_scramble(filters, settings, rng, &[], SolveOptions::default())
+}
+
+/// Generate a blindfolded scramble whose state passes every filter
+/// The wide moves orienting the cube at the end also move pieces relative to the
+/// centers, so the filters see the state after them.
+pub fn filtered_bld_scramble(
+    filters: &[ScrambleFilter],
+    settings: &MemoSettings,
+    rng: &mut Rng,
+) -> Result<Vec<u8>, String> {
+    let (suffix, options) = scrambler::bld_orientation(rng);
+    filtered_state_scramble(filters, settings, rng, &suffix, options)
+}
+
+/// Scramble for a random state that passes the filters once `suffix` is applied,
+/// followed by `suffix`
+fn filtered_state_scramble(
+    filters: &[ScrambleFilter],
+    settings: &MemoSettings,
+    rng: &mut Rng,
+    suffix: &[u8],
+    options: SolveOptions,
+) -> Result<Vec<u8>, String> {
+    for _ in 0..MAX_FILTER_ATTEMPTS {
+        let state = CubieCube::random(rng);
+        let mut scrambled = state.to_optimized();
+        scrambled.apply_moves(suffix);
+        let seen = CubieCube::from_optimized(&scrambled)?;
+        if !accepts_all(filters, &seen, settings) {
+            continue;
+        }
+        if let Some(mut scramble) = scrambler::scramble_for_state(&state, options) {
+            scramble.extend_from_slice(suffix);
+            return Ok(scramble);
+        }
+    }
+    Err("No scramble matched the filters".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithm;
+
+    #[test]
+    fn test_filtered_scramble_meets_filters() {
+        let mut rng = Rng::new(31);
+        let filters = [
+            ScrambleFilter::MaxCrossLength {
+                color: OptimizedCube::WHITE,
+                moves: 4,
+            },
+            ScrambleFilter::MisorientedEdges { min: 8, max: 12 },
+        ];
+        let settings = MemoSettings::default();
+        let scramble = filtered_scramble(&filters, &settings, &mut rng).unwrap();
+        assert!(OptimizedCube::validate_scramble(&scramble));
+        let state = CubieCube::from_moves(&scramble).unwrap();
+        assert!(accepts_all(&filters, &state, &settings));
+        assert!(analysis::misoriented_edges(&state) >= 8);
+
+        // Impossible filters give up instead of looping forever
+        let impossible = [ScrambleFilter::MisorientedEdges { min: 13, max: 13 }];
+        assert!(filtered_scramble(&impossible, &settings, &mut rng).is_err());
+    }
+
+    #[test]
+    fn test_filtered_bld_scramble() {
+        let mut rng = Rng::new(47);
+        let filters = [
+            ScrambleFilter::Parity { present: true },
+            ScrambleFilter::EdgeTargets { min: 0, max: 12 },
+            ScrambleFilter::TwistedCorners { min: 1, max: 8 },
+            ScrambleFilter::EdgeBufferSolved { present: true },
+        ];
+        // DF edge buffer
+        let settings = MemoOptions {
+            edge_buffer: Some("DF".to_string()),
+            ..MemoOptions::default()
+        }
+        .settings()
+        .unwrap();
+        let scramble = filtered_bld_scramble(&filters, &settings, &mut rng).unwrap();
+        assert!(scramble.iter().any(|&m| m >= 18));
+        let mut cube = OptimizedCube::solved();
+        cube.apply_moves(&scramble);
+        let stats = blindfolded::memo(&cube, &settings).unwrap().stats();
+        assert!(stats.parity && stats.edge_targets <= 12 && stats.twisted_corners >= 1);
+        let state = CubieCube::from_optimized(&cube).unwrap();
+        assert_eq!((state.ep[5], state.eo[5]), (5, 0));
+        assert!(accepts_all(&filters, &state, &settings));
+    }
+
+    #[test]
+    fn test_filter_options() {
+        let options = FilterOptions {
+            max_cross_length: Some(5),
+            min_misoriented_edges: Some(6),
+            free_pair: Some(true),
+            ..FilterOptions::default()
+        };
+        assert_eq!(
+            options.filters(),
+            vec![
+                ScrambleFilter::MaxCrossLength { color: 0, moves: 5 },
+                ScrambleFilter::MisorientedEdges { min: 6, max: 12 },
+                ScrambleFilter::FreePair {
+                    color: 0,
+                    present: true
+                },
+            ]
+        );
+        assert!(FilterOptions::default().filters().is_empty());
+
+        let bld = FilterOptions {
+            max_edge_targets: Some(16),
+            parity: Some(false),
+            ..FilterOptions::default()
+        };
+        assert_eq!(
+            bld.filters(),
+            vec![
+                ScrambleFilter::EdgeTargets { min: 0, max: 16 },
+                ScrambleFilter::Parity { present: false },
+            ]
+        );
+
+        let buffers = FilterOptions {
+            corner_buffer_solved: Some(true),
+            edge_buffer_solved: Some(false),
+            ..FilterOptions::default()
+        };
+        assert_eq!(
+            buffers.filters(),
+            vec![
+                ScrambleFilter::CornerBufferSolved { present: true },
+                ScrambleFilter::EdgeBufferSolved { present: false },
+            ]
+        );
+
+        let solved = CubieCube::solved();
+        let settings = MemoSettings::default();
+        assert!(ScrambleFilter::FreePair {
+            color: 3,
+            present: false
+        }
+        .accepts(&solved, &settings));
+        assert!(!ScrambleFilter::MinCrossLength { color: 1, moves: 1 }.accepts(&solved, &settings));
+        assert!(ScrambleFilter::CornerBufferSolved { present: true }.accepts(&solved, &settings));
+        assert!(ScrambleFilter::EdgeBufferSolved { present: true }.accepts(&solved, &settings));
+        assert!(!ScrambleFilter::CornerTargets { min: 1, max: 20 }.accepts(&solved, &settings));
+
+        // Only the corner buffer solved: U moves the UF edge but not the DFR corner
+        let settings = MemoOptions {
+            corner_buffer: Some("DFR".to_string()),
+            ..MemoOptions::default()
+        }
+        .settings()
+        .unwrap();
+        let turned = CubieCube::from_moves(&algorithm::parse_algorithm("U").unwrap()).unwrap();
+        assert!(ScrambleFilter::CornerBufferSolved { present: true }.accepts(&turned, &settings));
+        assert!(ScrambleFilter::EdgeBufferSolved { present: false }.accepts(&turned, &settings));
+    }
+}
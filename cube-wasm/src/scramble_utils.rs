@@ -1,4 +1,19 @@
+use crate::algorithm;
+use crate::algorithm_sets::AlgorithmSet;
+use crate::commutators::{self, FoundCommutator};
+use crate::cubie_cube::CubieCube;
+use crate::cycles::{self, CycleStructure, PieceCycle};
+use crate::equivalence::{self, EffectClass, Equivalence};
+use crate::ergonomics::{self, ErgonomicsOptions, ErgonomicsReport, MoveAnnotation};
+use crate::f2l::F2L_ALGORITHMS;
+use crate::last_layer::{auf_moves, OLL_ALGORITHMS, PLL_ALGORITHMS};
 use crate::optimized_cube::OptimizedCube;
+use crate::rng::Rng;
+use crate::scramble_filter::{self, FilterOptions};
+use crate::scrambler::{self, ScrambleEvent};
+use crate::subset_scrambler::{self, Subset};
+use crate::verification::{self, PieceDiff, Verification, VerifyOptions};
+use serde::Serialize;
 use wasm_bindgen::prelude::*;
 
 /// Scramble generation utilities for JavaScript
@@ -35,6 +50,80 @@ impl ScrambleUtils {
         OptimizedCube::moves_to_string(&moves)
     }
 
+    /// Generate a blindfolded scramble (random state plus random orientation)
+    #[wasm_bindgen]
+    pub fn generate_bld_scramble() -> String {
+        let moves = scrambler::bld_scramble(&mut Rng::from_entropy());
+        OptimizedCube::moves_to_string(&moves)
+    }
+
+    /// Generate a random-state scramble (solution of a uniformly random cube state)
+    #[wasm_bindgen]
+    pub fn generate_random_state_scramble() -> String {
+        let moves = scrambler::random_state_scramble(&mut Rng::from_entropy());
+        OptimizedCube::moves_to_string(&moves)
+    }
+
+    /// Generate a Fewest Moves scramble (random state padded with R' U' F)
+    #[wasm_bindgen]
+    pub fn generate_fmc_scramble() -> String {
+        let moves = scrambler::fmc_scramble(&mut Rng::from_entropy());
+        OptimizedCube::moves_to_string(&moves)
+    }
+
+    /// Generate a numbered batch of scrambles for an event ("333", "333oh", "333bf",
+    /// "333mbf", "333fm") from a single seed. Entry `i` is scramble number `i + 1`
+    /// and is reproducible from the same seed regardless of `count`.
+    #[wasm_bindgen]
+    pub fn generate_batch(event: &str, count: usize, seed: u32) -> Result<Vec<String>, JsValue> {
+        let event = ScrambleEvent::from_id(event).map_err(|e| JsValue::from_str(&e))?;
+        Ok(scrambler::generate_batch(event, count, seed as u64)
+            .iter()
+            .map(|moves| OptimizedCube::moves_to_string(moves))
+            .collect())
+    }
+
+    /// Generate a random-state scramble that passes filters, given as an object such as
+    /// `{ crossColor: 0, maxCrossLength: 4, minMisorientedEdges: 8, freePair: true }`
+    #[wasm_bindgen]
+    pub fn generate_filtered_scramble(filters: JsValue) -> Result<String, JsValue> {
+        let options: FilterOptions = serde_wasm_bindgen::from_value(filters)?;
+        let settings = options.memo_settings().map_err(|e| JsValue::from_str(&e))?;
+        let moves = scramble_filter::filtered_scramble(
+            &options.filters(),
+            &settings,
+            &mut Rng::from_entropy(),
+        )
+        .map_err(|e| JsValue::from_str(&e))?;
+        Ok(OptimizedCube::moves_to_string(&moves))
+    }
+
+    /// Generate a blindfolded scramble whose state passes filters, e.g. `{ parity: true,
+    /// maxEdgeTargets: 16, minTwistedCorners: 1, memo: { edgeBuffer: "DF" } }`;
+    /// blindfolded figures use the UFR and UF buffers unless `memo` sets others. Other
+    /// `generate_filtered_scramble` filters work too.
+    #[wasm_bindgen]
+    pub fn generate_filtered_bld_scramble(filters: JsValue) -> Result<String, JsValue> {
+        let options: FilterOptions = serde_wasm_bindgen::from_value(filters)?;
+        let settings = options.memo_settings().map_err(|e| JsValue::from_str(&e))?;
+        let moves = scramble_filter::filtered_bld_scramble(
+            &options.filters(),
+            &settings,
+            &mut Rng::from_entropy(),
+        )
+        .map_err(|e| JsValue::from_str(&e))?;
+        Ok(OptimizedCube::moves_to_string(&moves))
+    }
+
+    /// Validate a Fewest Moves scramble string (R' U' F padding, no cancellations)
+    #[wasm_bindgen]
+    pub fn validate_fmc_scramble(scramble: &str) -> bool {
+        match OptimizedCube::parse_scramble(scramble) {
+            Ok(moves) => scrambler::validate_fmc_scramble(&moves),
+            Err(_) => false,
+        }
+    }
+
     /// Validate a scramble string according to WCA rules
     #[wasm_bindgen]
     pub fn validate_scramble(scramble: &str) -> bool {
@@ -46,6 +135,7 @@ impl ScrambleUtils {
 
     /// Parse a scramble string into individual moves
     #[wasm_bindgen]
+    #[allow(clippy::trim_split_whitespace)]
     pub fn parse_scramble(scramble: &str) -> Vec<String> {
         scramble
             .trim()
@@ -62,46 +152,42 @@ impl ScrambleUtils {
         moves.join(" ")
     }
 
+    /// Generate a training subset scramble: a random state of the subset
+    /// ("LL", "OLL", "PLL", "ZBLL", "COLL", "2GLL", "FB", "F2B", "EOLine", "EO", "2x2x3")
+    #[wasm_bindgen]
+    pub fn generate_subset_scramble(subset: &str) -> Result<String, JsValue> {
+        let subset = Subset::from_name(subset).map_err(|e| JsValue::from_str(&e))?;
+        let moves = subset_scrambler::subset_scramble(subset, &mut Rng::from_entropy());
+        Ok(OptimizedCube::moves_to_string(&moves))
+    }
+
+    /// Generate a scramble for one case with a random U turn before and after it.
+    /// The case is a case id ("PLL Ua", see `AlgorithmPatterns::case_algorithm`) or an
+    /// algorithm whose inverse defines the state ("M2 U M U2 M' U M2").
+    #[wasm_bindgen]
+    pub fn generate_case_scramble(case: &str) -> Result<String, JsValue> {
+        let state = case_state(case).map_err(|e| JsValue::from_str(&e))?;
+        let scramble = subset_scrambler::case_scramble(&state, &mut Rng::from_entropy())
+            .map_err(|e| JsValue::from_str(&e))?;
+        Ok(OptimizedCube::moves_to_string(&scramble))
+    }
+
     /// Generate algorithm practice scramble
+    /// Last-layer subsets ("OLL", "PLL", ...) produce real subset states,
+    /// other types wrap a known algorithm in random moves
     #[wasm_bindgen]
     pub fn generate_algorithm_practice(algorithm_type: &str) -> String {
+        if let Ok(subset) = Subset::from_name(algorithm_type) {
+            let moves = subset_scrambler::subset_scramble(subset, &mut Rng::from_entropy());
+            return OptimizedCube::moves_to_string(&moves);
+        }
+
         let patterns = match algorithm_type {
-            "OLL" => vec![
-                "R U R' U R U2 R'",
-                "R U R' U' R' F R F'",
-                "F R U R' U' F'",
-                "R U R' U R U' R' U' R' F R F'",
-                "F (R U R' U') (R U R' U') (R U R' U') F'",
-                "F' (L' U' L U) (L' U' L U) (L' U' L U) F",
-                "R U2 R' U' R U' R'",
-                "R' U' R U' R' U2 R",
-                "R U2 R2 U' R2 U' R2 U2 R",
-            ],
-            "PLL" => vec![
-                "R U R' F' R U R' U' R' F R2 U' R'",
-                "R U R' U' R' F R2 U' R' U' R U R' F'",
-                "R' U R' U' R' U' R' U R U R2",
-                "R U R' U R U R' F' R U R' U' R' F R2 U' R' U2 R U' R'",
-                "R2 U R' U R' U' R U' R2 U' D R' U R D'",
-                "R' U' F' R U R' U' R' F R2 U' R' U' R U R' U R",
-                "R' U R U' R' F' U' F R U R' F R' F' R U' R",
-                "R U' L' U R' U' L U R U' L' U R' U' L",
-            ],
-            "F2L" => vec![
-                "R U' R' U R U R'",
-                "R U R' U' R U R'",
-                "R U' R' U2 R U' R'",
-                "R U2 R' U' R U R'",
-                "U' R U R' U R U R'",
-                "U R U' R' U' R U R'",
-                "R' F R F' U R U' R'",
-                "U' F' U F U R U' R'",
-            ],
-            _ => vec!["R U R' U R U2 R'"], // Default to simple OLL
+            "F2L" => &F2L_ALGORITHMS[..],
+            _ => &OLL_ALGORITHMS[26..27], // Default to simple OLL (Sune)
         };
 
-        let random_pattern =
-            patterns[js_sys::Math::random() as usize * patterns.len() % patterns.len()];
+        let random_pattern = patterns[Rng::from_entropy().below(patterns.len())];
 
         // Add some random moves before and after
         let prefix_moves = OptimizedCube::generate_random_scramble(8);
@@ -120,46 +206,436 @@ pub struct AlgorithmPatterns;
 
 #[wasm_bindgen]
 impl AlgorithmPatterns {
+    /// The 57 OLL algorithms in standard numbering (the algorithms of `set("OLL")`)
     #[wasm_bindgen(getter)]
     pub fn oll() -> Vec<String> {
-        vec![
-            "R U R' U R U2 R'".to_string(),
-            "R U R' U' R' F R F'".to_string(),
-            "F R U R' U' F'".to_string(),
-            "R U R' U R U' R' U' R' F R F'".to_string(),
-            "F (R U R' U') (R U R' U') (R U R' U') F'".to_string(),
-            "F' (L' U' L U) (L' U' L U) (L' U' L U) F".to_string(),
-            "R U2 R' U' R U' R'".to_string(),
-            "R' U' R U' R' U2 R".to_string(),
-            "R U2 R2 U' R2 U' R2 U2 R".to_string(),
-        ]
+        set_algorithms("OLL")
     }
 
+    /// The 21 PLL algorithms, named in `set("PLL")`
     #[wasm_bindgen(getter)]
     pub fn pll() -> Vec<String> {
-        vec![
-            "R U R' F' R U R' U' R' F R2 U' R'".to_string(),
-            "R U R' U' R' F R2 U' R' U' R U R' F'".to_string(),
-            "R' U R' U' R' U' R' U R U R2".to_string(),
-            "R U R' U R U R' F' R U R' U' R' F R2 U' R' U2 R U' R'".to_string(),
-            "R2 U R' U R' U' R U' R2 U' D R' U R D'".to_string(),
-            "R' U' F' R U R' U' R' F R2 U' R' U' R U R' U R".to_string(),
-            "R' U R U' R' F' U' F R U R' F R' F' R U' R".to_string(),
-            "R U' L' U R' U' L U R U' L' U R' U' L".to_string(),
-        ]
+        set_algorithms("PLL")
     }
 
+    /// The 41 F2L algorithms for the FR slot in standard numbering (as in `set("F2L")`)
     #[wasm_bindgen(getter)]
     pub fn f2l() -> Vec<String> {
-        vec![
-            "R U' R' U R U R'".to_string(),
-            "R U R' U' R U R'".to_string(),
-            "R U' R' U2 R U' R'".to_string(),
-            "R U2 R' U' R U R'".to_string(),
-            "U' R U R' U R U R'".to_string(),
-            "U R U' R' U' R U R'".to_string(),
-            "R' F R F' U R U' R'".to_string(),
-            "U' F' U F U R U' R'".to_string(),
-        ]
+        set_algorithms("F2L")
+    }
+
+    /// Check an algorithm against a case (a case id or an algorithm, as for
+    /// `ScrambleUtils::generate_case_scramble`). U turns before and after the algorithm
+    /// and a y rotation first are allowed unless turned off:
+    /// `{ solves, preservesF2l, rotation: "y'", preAuf: "U", postAuf: "",
+    /// wrongPieces: [{ position: "UF", piece: "UB", orientation: 0 }, ...] }`
+    #[wasm_bindgen]
+    pub fn verify_algorithm(
+        case: &str,
+        alg: &str,
+        allow_auf: Option<bool>,
+        allow_rotation: Option<bool>,
+    ) -> Result<JsValue, JsValue> {
+        let state = case_state(case).map_err(|e| JsValue::from_str(&e))?;
+        let moves = algorithm::parse_algorithm(alg).map_err(|e| JsValue::from_str(&e))?;
+        let options = VerifyOptions {
+            allow_auf: allow_auf.unwrap_or(true),
+            allow_rotation: allow_rotation.unwrap_or(true),
+        };
+        let result = verification::verify_algorithm(&state, &moves, options)
+            .map_err(|e| JsValue::from_str(&e))?;
+        Ok(serde_wasm_bindgen::to_value(&VerificationResult::from(
+            &result,
+        ))?)
+    }
+
+    /// Whether two algorithms have the same effect up to AUF, and up to the orientation
+    /// the second is performed in when `modulo_rotation` is set:
+    /// `{ equivalent, rotation: "y", preAuf: "U", postAuf: "U'" }`, where the second
+    /// algorithm performed after the rotation and pre-AUF, then the inverse rotation and
+    /// post-AUF, has the effect of the first
+    #[wasm_bindgen]
+    pub fn compare_algorithms(
+        first: &str,
+        second: &str,
+        modulo_rotation: Option<bool>,
+    ) -> Result<JsValue, JsValue> {
+        let first = algorithm::parse_algorithm(first).map_err(|e| JsValue::from_str(&e))?;
+        let second = algorithm::parse_algorithm(second).map_err(|e| JsValue::from_str(&e))?;
+        let found = equivalence::equivalence(&first, &second, modulo_rotation.unwrap_or(false))
+            .map_err(|e| JsValue::from_str(&e))?;
+        Ok(serde_wasm_bindgen::to_value(&EquivalenceResult::from(
+            found.as_ref(),
+        ))?)
+    }
+
+    /// What an algorithm does: `{ kind: "PLL", auf: "U", permutedCorners: ["URF", "UBR"],
+    /// permutedEdges, twistedCorners, flippedEdges }`, read after the U turn that moves
+    /// the fewest pieces. Kinds: Identity, AUF, EPLL, CPLL, PLL, Pure OLL, ELL, CLL, COLL,
+    /// ZBLL, 1LLL, LSE, CMLL, F2L, Other.
+    #[wasm_bindgen]
+    pub fn classify_algorithm(alg: &str) -> Result<JsValue, JsValue> {
+        let moves = algorithm::parse_algorithm(alg).map_err(|e| JsValue::from_str(&e))?;
+        let class = equivalence::classify(&moves).map_err(|e| JsValue::from_str(&e))?;
+        Ok(serde_wasm_bindgen::to_value(&EffectClassResult::from(
+            &class,
+        ))?)
+    }
+
+    /// Order and cycle structure of a move sequence: `{ order: 6, cornerCycles:
+    /// [{ positions: ["URF", "UBR"], orientation: 1 }], edgeCycles, twistedCorners: 0, flippedEdges: 0 }`
+    /// Each piece of a cycle moves to the next position; a twisted or flipped piece in
+    /// place is a cycle of one. Orientation is given per cycle, and the counts are of
+    /// positions holding a twisted corner or flipped edge.
+    #[wasm_bindgen]
+    pub fn analyse_cycles(alg: &str) -> Result<JsValue, JsValue> {
+        let moves = algorithm::parse_algorithm(alg).map_err(|e| JsValue::from_str(&e))?;
+        let structure = cycles::cycle_structure(&moves).map_err(|e| JsValue::from_str(&e))?;
+        Ok(serde_wasm_bindgen::to_value(&CycleStructureResult::from(
+            &structure,
+        ))?)
+    }
+
+    /// Commutators cycling three stickers for 3-style: the buffer sticker goes to
+    /// `first`, `first` to `second` and `second` to the buffer. Stickers are named from
+    /// their own face, e.g. "UFR" or "RDF" for corners and "UF" or "FU" for edges.
+    /// Returns up to `limit` (default 10) `[{ notation: "[D: [R U R', D]]", moves,
+    /// moveCount, ergonomics }]`, shortest first, then easiest.
+    #[wasm_bindgen]
+    pub fn find_commutators(
+        buffer: &str,
+        first: &str,
+        second: &str,
+        limit: Option<usize>,
+    ) -> Result<JsValue, JsValue> {
+        let found = commutators::find_commutators(buffer, first, second, limit.unwrap_or(10))
+            .map_err(|e| JsValue::from_str(&e))?;
+        let results: Vec<CommutatorResult> = found.iter().map(CommutatorResult::from).collect();
+        Ok(serde_wasm_bindgen::to_value(&results)?)
+    }
+
+    /// Ease of executing a move sequence: `{ moves: [{ notation: "R", hand: "right",
+    /// technique: "wrist", regrip: false, cost: 1 }], regrips, wristTurns, awkwardMoves,
+    /// rotations, ruRatio, score }`
+    /// Lower scores are easier. Options are optional, e.g. `{ leftHanded: true,
+    /// weights: { regrip: 4, awkward: 2 } }`; unset weights keep their defaults.
+    #[wasm_bindgen]
+    pub fn analyse_ergonomics(alg: &str, options: JsValue) -> Result<JsValue, JsValue> {
+        let options: ErgonomicsOptions = if options.is_undefined() || options.is_null() {
+            ErgonomicsOptions::default()
+        } else {
+            serde_wasm_bindgen::from_value(options)?
+        };
+        let moves = algorithm::parse_algorithm(alg).map_err(|e| JsValue::from_str(&e))?;
+        let report = ergonomics::analyse(&moves, &options).map_err(|e| JsValue::from_str(&e))?;
+        Ok(serde_wasm_bindgen::to_value(&ErgonomicsResult::from(
+            &report,
+        ))?)
+    }
+
+    /// Cases of an algorithm set ("OLL", "PLL", "F2L", "COLL", "CMLL", "ZBLL", "OLLCP",
+    /// case-insensitive) as `[{ id: "H1", caseId: "COLL H1", algorithm }, ...]`
+    /// OLL and F2L ids are case numbers, PLL ids are names.
+    #[wasm_bindgen]
+    pub fn set(name: &str) -> Result<JsValue, JsValue> {
+        let cases = set_cases(name)
+            .ok_or_else(|| JsValue::from_str(&format!("Unknown algorithm set: {}", name)))?;
+        let set = name.to_ascii_uppercase();
+        let entries: Vec<SetCaseEntry> = cases
+            .into_iter()
+            .map(|(id, alg)| SetCaseEntry {
+                case_id: format!("{} {}", set, id),
+                id,
+                algorithm: alg.to_string(),
+            })
+            .collect();
+        Ok(serde_wasm_bindgen::to_value(&entries)?)
+    }
+
+    /// Algorithm for a case id such as "PLL Ua", "PLL 3", "oll-1" or "ZBLL AS12"
+    /// (case-insensitive; numbers are 1-based and work for every set)
+    #[wasm_bindgen]
+    pub fn case_algorithm(case_id: &str) -> Option<String> {
+        let (set, id) = case_id.trim().split_once([' ', '-'])?;
+        let cases = set_cases(set)?;
+        let id = id.trim();
+        if let Some((_, alg)) = cases.iter().find(|(case, _)| case.eq_ignore_ascii_case(id)) {
+            return Some(alg.to_string());
+        }
+        let number: usize = id.parse().ok()?;
+        cases
+            .get(number.checked_sub(1)?)
+            .map(|(_, alg)| alg.to_string())
+    }
+}
+
+/// One case of an algorithm set, as returned to JavaScript
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SetCaseEntry {
+    id: String,
+    case_id: String,
+    algorithm: String,
+}
+
+/// Algorithms of a set in table order
+fn set_algorithms(set: &str) -> Vec<String> {
+    set_cases(set)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(_, alg)| alg.to_string())
+        .collect()
+}
+
+/// (case id, algorithm) of every case of a set, None for an unknown set
+fn set_cases(set: &str) -> Option<Vec<(String, &'static str)>> {
+    let numbered = |algorithms: &[&'static str]| {
+        algorithms
+            .iter()
+            .enumerate()
+            .map(|(index, &alg)| ((index + 1).to_string(), alg))
+            .collect()
+    };
+    match set.to_ascii_uppercase().as_str() {
+        "OLL" => Some(numbered(&OLL_ALGORITHMS)),
+        "PLL" => Some(
+            PLL_ALGORITHMS
+                .iter()
+                .map(|&(name, alg)| (name.to_string(), alg))
+                .collect(),
+        ),
+        "F2L" => Some(numbered(&F2L_ALGORITHMS)),
+        name => AlgorithmSet::from_name(name).ok().map(|set| {
+            set.cases()
+                .iter()
+                .map(|&(id, alg)| (id.to_string(), alg))
+                .collect()
+        }),
+    }
+}
+
+/// Wrong piece of a verification, as returned to JavaScript
+#[derive(Serialize)]
+struct PieceDiffResult {
+    position: &'static str,
+    piece: &'static str,
+    orientation: u8,
+}
+
+impl From<&PieceDiff> for PieceDiffResult {
+    fn from(diff: &PieceDiff) -> Self {
+        PieceDiffResult {
+            position: diff.position,
+            piece: diff.piece,
+            orientation: diff.orientation,
+        }
+    }
+}
+
+/// Algorithm verification, as returned to JavaScript
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct VerificationResult {
+    solves: bool,
+    preserves_f2l: bool,
+    rotation: String,
+    pre_auf: String,
+    post_auf: String,
+    wrong_pieces: Vec<PieceDiffResult>,
+}
+
+impl From<&Verification> for VerificationResult {
+    fn from(result: &Verification) -> Self {
+        VerificationResult {
+            solves: result.solves,
+            preserves_f2l: result.preserves_f2l,
+            rotation: OptimizedCube::moves_to_string(&result.rotation),
+            pre_auf: OptimizedCube::moves_to_string(&auf_moves(result.pre_auf)),
+            post_auf: OptimizedCube::moves_to_string(&auf_moves(result.post_auf)),
+            wrong_pieces: result
+                .wrong_pieces
+                .iter()
+                .map(PieceDiffResult::from)
+                .collect(),
+        }
+    }
+}
+
+/// Algorithm comparison, as returned to JavaScript
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct EquivalenceResult {
+    equivalent: bool,
+    rotation: String,
+    pre_auf: String,
+    post_auf: String,
+}
+
+impl From<Option<&Equivalence>> for EquivalenceResult {
+    fn from(found: Option<&Equivalence>) -> Self {
+        EquivalenceResult {
+            equivalent: found.is_some(),
+            rotation: found
+                .map(|found| OptimizedCube::moves_to_string(&found.rotation))
+                .unwrap_or_default(),
+            pre_auf: found
+                .map(|found| OptimizedCube::moves_to_string(&auf_moves(found.pre_auf)))
+                .unwrap_or_default(),
+            post_auf: found
+                .map(|found| OptimizedCube::moves_to_string(&auf_moves(found.post_auf)))
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// Algorithm classification, as returned to JavaScript
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct EffectClassResult {
+    kind: &'static str,
+    auf: String,
+    permuted_corners: Vec<&'static str>,
+    permuted_edges: Vec<&'static str>,
+    twisted_corners: Vec<&'static str>,
+    flipped_edges: Vec<&'static str>,
+}
+
+impl From<&EffectClass> for EffectClassResult {
+    fn from(class: &EffectClass) -> Self {
+        EffectClassResult {
+            kind: class.kind.name(),
+            auf: OptimizedCube::moves_to_string(&auf_moves(class.auf)),
+            permuted_corners: class.permuted_corners.clone(),
+            permuted_edges: class.permuted_edges.clone(),
+            twisted_corners: class.twisted_corners.clone(),
+            flipped_edges: class.flipped_edges.clone(),
+        }
     }
 }
+
+/// Cycle of pieces, as returned to JavaScript
+#[derive(Serialize)]
+struct PieceCycleResult {
+    positions: Vec<&'static str>,
+    orientation: u8,
+}
+
+impl From<&PieceCycle> for PieceCycleResult {
+    fn from(cycle: &PieceCycle) -> Self {
+        PieceCycleResult {
+            positions: cycle.positions.clone(),
+            orientation: cycle.orientation,
+        }
+    }
+}
+
+/// Cycle structure, as returned to JavaScript
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CycleStructureResult {
+    order: usize,
+    corner_cycles: Vec<PieceCycleResult>,
+    edge_cycles: Vec<PieceCycleResult>,
+    twisted_corners: usize,
+    flipped_edges: usize,
+}
+
+impl From<&CycleStructure> for CycleStructureResult {
+    fn from(structure: &CycleStructure) -> Self {
+        CycleStructureResult {
+            order: structure.order,
+            corner_cycles: structure
+                .corner_cycles
+                .iter()
+                .map(PieceCycleResult::from)
+                .collect(),
+            edge_cycles: structure
+                .edge_cycles
+                .iter()
+                .map(PieceCycleResult::from)
+                .collect(),
+            twisted_corners: structure.twisted_corners,
+            flipped_edges: structure.flipped_edges,
+        }
+    }
+}
+
+/// Commutator found for three stickers, as returned to JavaScript
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CommutatorResult {
+    notation: String,
+    moves: String,
+    move_count: usize,
+    ergonomics: f64,
+}
+
+impl From<&FoundCommutator> for CommutatorResult {
+    fn from(found: &FoundCommutator) -> Self {
+        CommutatorResult {
+            notation: found.commutator.notation(),
+            moves: OptimizedCube::moves_to_string(&found.commutator.moves()),
+            move_count: found.move_count,
+            ergonomics: found.ergonomics,
+        }
+    }
+}
+
+/// Annotated move, as returned to JavaScript
+#[derive(Serialize)]
+struct MoveAnnotationResult {
+    notation: &'static str,
+    hand: &'static str,
+    technique: &'static str,
+    regrip: bool,
+    cost: f64,
+}
+
+impl From<&MoveAnnotation> for MoveAnnotationResult {
+    fn from(annotation: &MoveAnnotation) -> Self {
+        MoveAnnotationResult {
+            notation: annotation.notation,
+            hand: annotation.hand.name(),
+            technique: annotation.technique.name(),
+            regrip: annotation.regrip,
+            cost: annotation.cost,
+        }
+    }
+}
+
+/// Ergonomics report, as returned to JavaScript
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ErgonomicsResult {
+    moves: Vec<MoveAnnotationResult>,
+    regrips: usize,
+    wrist_turns: usize,
+    awkward_moves: usize,
+    rotations: usize,
+    ru_ratio: f64,
+    score: f64,
+}
+
+impl From<&ErgonomicsReport> for ErgonomicsResult {
+    fn from(report: &ErgonomicsReport) -> Self {
+        ErgonomicsResult {
+            moves: report
+                .moves
+                .iter()
+                .map(MoveAnnotationResult::from)
+                .collect(),
+            regrips: report.regrips,
+            wrist_turns: report.wrist_turns,
+            awkward_moves: report.awkward_moves,
+            rotations: report.rotations,
+            ru_ratio: report.ru_ratio,
+            score: report.score,
+        }
+    }
+}
+
+/// State of a case given as a case id ("PLL Ua") or as an algorithm solving it
+fn case_state(case: &str) -> Result<CubieCube, String> {
+    let algorithm = AlgorithmPatterns::case_algorithm(case).unwrap_or_else(|| case.to_string());
+    algorithm::case_state(&algorithm::parse_algorithm(&algorithm)?)
+}
@@ -0,0 +1,205 @@
+// Random-state scramble generation
+// A uniformly random cube state is solved with the two-phase solver and the
+// inverted solution is used as the scramble, as in WCA competition scramblers.
+use crate::cubie_cube::CubieCube;
+use crate::optimized_cube::{MoveCode, OptimizedCube};
+use crate::rng::Rng;
+use crate::two_phase::{self, SolveOptions};
+
+/// R' U' F, the padding at both ends of a WCA Fewest Moves scramble
+pub const FMC_PADDING: [u8; 3] = [MoveCode::Rp as u8, MoveCode::Up as u8, MoveCode::F as u8];
+
+/// Axis codes as used by the solver (0=UD, 1=FB, 2=RL)
+const FB_AXIS: u8 = 1;
+const RL_AXIS: u8 = 2;
+
+/// Scramble (face move codes) that takes a solved cube to `state`
+/// Returns None if the solver gave up within the given options.
+pub fn scramble_for_state(state: &CubieCube, options: SolveOptions) -> Option<Vec<u8>> {
+    two_phase::solve(state, options).map(|solution| two_phase::invert_moves(&solution))
+}
+
+/// Generate a random-state scramble
+pub fn random_state_scramble(rng: &mut Rng) -> Vec<u8> {
+    loop {
+        let state = CubieCube::random(rng);
+        if let Some(scramble) = scramble_for_state(&state, SolveOptions::default()) {
+            return scramble;
+        }
+    }
+}
+
+/// Generate a WCA-style Fewest Moves scramble: R' U' F, a random-state scramble, R' U' F
+/// The middle part never starts on the F/B axis nor ends on the R/L axis, so nothing
+/// cancels into the padding.
+pub fn fmc_scramble(rng: &mut Rng) -> Vec<u8> {
+    // The scramble is the inverted solution, so its first move restriction applies to
+    // the solution's last move and vice versa
+    let options = SolveOptions {
+        first_axis_excluded: Some(RL_AXIS),
+        last_axis_excluded: Some(FB_AXIS),
+        ..SolveOptions::default()
+    };
+    loop {
+        let state = CubieCube::random(rng);
+        if let Some(middle) = scramble_for_state(&state, options) {
+            let mut scramble = FMC_PADDING.to_vec();
+            scramble.extend_from_slice(&middle);
+            scramble.extend_from_slice(&FMC_PADDING);
+            return scramble;
+        }
+    }
+}
+
+/// Generate a WCA-style blindfolded scramble: a random-state scramble followed by
+/// wide moves that randomise the orientation (one of 24)
+pub fn bld_scramble(rng: &mut Rng) -> Vec<u8> {
+    let (suffix, options) = bld_orientation(rng);
+    loop {
+        let state = CubieCube::random(rng);
+        if let Some(mut scramble) = scramble_for_state(&state, options) {
+            scramble.extend_from_slice(&suffix);
+            return scramble;
+        }
+    }
+}
+
+/// Random orientation suffix of a blindfolded scramble, with the solver options for the
+/// random-state part before it
+pub fn bld_orientation(rng: &mut Rng) -> (Vec<u8>, SolveOptions) {
+    let suffix = OptimizedCube::bld_orientation_suffix(rng.below(6), rng.below(4));
+    // The scramble must not end on the axis of the first wide move,
+    // so its inverted solution must not start on it
+    let options = SolveOptions {
+        first_axis_excluded: suffix.first().map(|&m| m % 6 / 2),
+        ..SolveOptions::default()
+    };
+    (suffix, options)
+}
+
+/// Events supported by the batch scramble generator (WCA event ids)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScrambleEvent {
+    /// 3x3x3 and one-handed: random state
+    ThreeByThree,
+    /// 3x3x3 blindfolded and multi-blind: random state plus random orientation
+    Blindfolded,
+    /// 3x3x3 fewest moves: random state with R' U' F padding
+    FewestMoves,
+}
+
+impl ScrambleEvent {
+    /// Parse a WCA event id ("333", "333oh", "333bf", "333mbf", "333fm")
+    pub fn from_id(event: &str) -> Result<Self, String> {
+        match event {
+            "333" | "333oh" => Ok(ScrambleEvent::ThreeByThree),
+            "333bf" | "333mbf" => Ok(ScrambleEvent::Blindfolded),
+            "333fm" => Ok(ScrambleEvent::FewestMoves),
+            _ => Err(format!("Unsupported event: {}", event)),
+        }
+    }
+
+    /// Generate one scramble for this event
+    pub fn scramble(self, rng: &mut Rng) -> Vec<u8> {
+        match self {
+            ScrambleEvent::ThreeByThree => random_state_scramble(rng),
+            ScrambleEvent::Blindfolded => bld_scramble(rng),
+            ScrambleEvent::FewestMoves => fmc_scramble(rng),
+        }
+    }
+}
+
+/// Generate `count` independent scrambles from a single seed
+/// Scramble number `i` (1-based) is entry `i - 1` and is the same for a given seed
+/// whatever the batch size, so a multi-blind attempt can be regenerated exactly.
+pub fn generate_batch(event: ScrambleEvent, count: usize, seed: u64) -> Vec<Vec<u8>> {
+    (0..count)
+        .map(|index| event.scramble(&mut Rng::for_index(seed, index as u64)))
+        .collect()
+}
+
+/// Check the WCA Fewest Moves scramble format
+/// The scramble must start and end with R' U' F, use only face moves and follow the
+/// same face and axis rules as `OptimizedCube::validate_scramble`, so no move cancels
+/// into the padding.
+pub fn validate_fmc_scramble(moves: &[u8]) -> bool {
+    moves.len() > 2 * FMC_PADDING.len()
+        && moves.starts_with(&FMC_PADDING)
+        && moves.ends_with(&FMC_PADDING)
+        && moves.iter().all(|&m| m < 18)
+        && OptimizedCube::validate_scramble(moves)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_random_state_scramble_reaches_state() {
+        let mut rng = Rng::new(42);
+        let state = CubieCube::random(&mut rng);
+        let scramble = scramble_for_state(&state, SolveOptions::default()).unwrap();
+        assert_eq!(CubieCube::from_moves(&scramble).unwrap(), state);
+        assert!(OptimizedCube::validate_scramble(&scramble));
+
+        let scramble = random_state_scramble(&mut rng);
+        assert!(scramble.len() <= 22);
+        assert!(OptimizedCube::validate_scramble(&scramble));
+    }
+
+    #[test]
+    fn test_fmc_scramble_format() {
+        let mut rng = Rng::new(333);
+        for _ in 0..5 {
+            let scramble = fmc_scramble(&mut rng);
+            assert!(validate_fmc_scramble(&scramble));
+            let text = OptimizedCube::moves_to_string(&scramble);
+            assert!(text.starts_with("R' U' F "));
+            assert!(text.ends_with(" R' U' F"));
+        }
+    }
+
+    #[test]
+    fn test_fmc_validation_rejects_cancellations() {
+        let parse = |s: &str| OptimizedCube::parse_scramble(s).unwrap();
+        assert!(validate_fmc_scramble(&parse("R' U' F D R2 U' R' U' F")));
+        // F cancels with the padding
+        assert!(!validate_fmc_scramble(&parse("R' U' F F2 D R2 U' R' U' F")));
+        // F B F cancels across the B
+        assert!(!validate_fmc_scramble(&parse("R' U' F B F D R' U' F")));
+        // R ... R' cancels across the L at the end
+        assert!(!validate_fmc_scramble(&parse("R' U' F D R L R' U' F")));
+        // Missing padding
+        assert!(!validate_fmc_scramble(&parse("R' U' F D R2 U'")));
+    }
+
+    #[test]
+    fn test_bld_scramble_orientation() {
+        let mut rng = Rng::new(5);
+        for _ in 0..10 {
+            let scramble = bld_scramble(&mut rng);
+            assert!(OptimizedCube::validate_scramble(&scramble));
+            let face_moves = scramble.iter().take_while(|&&m| m < 18).count();
+            assert!(face_moves <= 22);
+            assert!(scramble.len() - face_moves <= 2);
+            assert!(scramble[face_moves..].iter().all(|&m| m >= 18));
+        }
+    }
+
+    #[test]
+    fn test_batch_is_reproducible() {
+        let event = ScrambleEvent::from_id("333mbf").unwrap();
+        let batch = generate_batch(event, 4, 1234);
+        assert_eq!(batch.len(), 4);
+        assert_eq!(batch, generate_batch(event, 4, 1234));
+        // Scrambles are independent of the batch size
+        assert_eq!(generate_batch(event, 2, 1234), batch[..2].to_vec());
+        assert_ne!(generate_batch(event, 4, 4321), batch);
+        assert_ne!(batch[0], batch[1]);
+        assert!(batch.iter().all(|s| OptimizedCube::validate_scramble(s)));
+
+        let fmc = generate_batch(ScrambleEvent::FewestMoves, 2, 9);
+        assert!(fmc.iter().all(|s| validate_fmc_scramble(s)));
+        assert!(ScrambleEvent::from_id("222").is_err());
+    }
+}
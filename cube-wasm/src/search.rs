@@ -0,0 +1,196 @@
+// Iterative-deepening A* over face moves, shared by the step solvers (XCross, EOLine, blocks)
+// A search space supplies the move function and an admissible lower bound that is 0 exactly
+// on solved states. Solutions are canonical: no face repeats and opposite faces come in a
+// fixed order, so every optimal solution is found once.
+use crate::two_phase::can_follow;
+
+const N_MOVES: u8 = 18;
+/// Nodes visited between two deadline checks
+const DEADLINE_CHECK_INTERVAL: u64 = 4096;
+
+/// States and moves of a step to solve
+pub trait SearchSpace {
+    type State: Copy;
+    /// State after face move `m` (0-17)
+    fn apply(&self, state: &Self::State, m: u8) -> Self::State;
+    /// Admissible estimate of the moves left, 0 only when solved
+    fn lower_bound(&self, state: &Self::State) -> u8;
+}
+
+/// Wall-clock limit for a search (uses `Date.now()` on wasm)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Deadline {
+    end_ms: Option<f64>,
+}
+
+impl Deadline {
+    /// No time limit
+    pub fn none() -> Deadline {
+        Deadline { end_ms: None }
+    }
+
+    /// Expires `timeout_ms` milliseconds from now
+    pub fn after_ms(timeout_ms: f64) -> Deadline {
+        Deadline {
+            end_ms: Some(now_ms() + timeout_ms),
+        }
+    }
+
+    /// Expires after `timeout_ms` if given, never otherwise
+    pub fn from_timeout(timeout_ms: Option<f64>) -> Deadline {
+        timeout_ms.map_or_else(Deadline::none, Deadline::after_ms)
+    }
+
+    pub fn expired(&self) -> bool {
+        self.end_ms.is_some_and(|end| now_ms() >= end)
+    }
+}
+
+/// Current time in milliseconds
+fn now_ms() -> f64 {
+    #[cfg(target_arch = "wasm32")]
+    let now = js_sys::Date::now();
+    #[cfg(not(target_arch = "wasm32"))]
+    let now = {
+        use std::time::{SystemTime, UNIX_EPOCH};
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs_f64()
+            * 1000.0
+    };
+    now
+}
+
+/// Solutions found by a search
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SearchOutcome {
+    /// Optimal solutions (all the same length), at most the requested number
+    pub solutions: Vec<Vec<u8>>,
+    /// The deadline passed before the search finished
+    pub timed_out: bool,
+}
+
+/// Find optimal solutions of at most `max_length` moves
+pub fn search<S: SearchSpace>(
+    space: &S,
+    start: S::State,
+    max_length: usize,
+    max_solutions: usize,
+    deadline: Deadline,
+) -> SearchOutcome {
+    let mut outcome = SearchOutcome::default();
+    let first_depth = space.lower_bound(&start) as usize;
+    for depth in first_depth..=max_length {
+        outcome = search_depth(space, start, depth, max_solutions, deadline);
+        if !outcome.solutions.is_empty() || outcome.timed_out {
+            break;
+        }
+    }
+    outcome
+}
+
+/// Optimal solutions of several searches deepened together
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct JointOutcome {
+    /// (search index, optimal solution), shortest first
+    pub solutions: Vec<(usize, Vec<u8>)>,
+    /// Every search got its optimal solution
+    pub complete: bool,
+}
+
+/// Solve every (space, start) problem optimally, deepening all of them together so results
+/// come out shortest first and a deadline or `max_results` still leaves the best ones
+pub fn search_jointly<S: SearchSpace>(
+    problems: &[(&S, S::State)],
+    max_length: usize,
+    max_results: usize,
+    deadline: Deadline,
+) -> JointOutcome {
+    let mut solved = vec![false; problems.len()];
+    let mut outcome = JointOutcome::default();
+    'deepening: for depth in 0..=max_length {
+        for (index, &(space, start)) in problems.iter().enumerate() {
+            if solved[index] || space.lower_bound(&start) as usize > depth {
+                continue;
+            }
+            let found = search_depth(space, start, depth, 1, deadline);
+            if found.timed_out {
+                break 'deepening;
+            }
+            if let Some(moves) = found.solutions.into_iter().next() {
+                outcome.solutions.push((index, moves));
+                solved[index] = true;
+                if outcome.solutions.len() >= max_results {
+                    break 'deepening;
+                }
+            }
+        }
+        if solved.iter().all(|&done| done) {
+            break;
+        }
+    }
+    outcome.complete = solved.iter().all(|&done| done);
+    outcome
+}
+
+/// Find solutions of exactly `depth` moves (none if the state needs more or fewer)
+pub fn search_depth<S: SearchSpace>(
+    space: &S,
+    start: S::State,
+    depth: usize,
+    max_solutions: usize,
+    deadline: Deadline,
+) -> SearchOutcome {
+    let mut search = Search {
+        space,
+        max_solutions,
+        deadline,
+        nodes: 0,
+        path: Vec::with_capacity(depth),
+        outcome: SearchOutcome::default(),
+    };
+    search.visit(start, depth);
+    search.outcome
+}
+
+struct Search<'a, S: SearchSpace> {
+    space: &'a S,
+    max_solutions: usize,
+    deadline: Deadline,
+    nodes: u64,
+    path: Vec<u8>,
+    outcome: SearchOutcome,
+}
+
+impl<S: SearchSpace> Search<'_, S> {
+    /// Depth-first search with `remaining` moves left; returns true to stop
+    fn visit(&mut self, state: S::State, remaining: usize) -> bool {
+        let bound = self.space.lower_bound(&state) as usize;
+        if bound > remaining {
+            return false;
+        }
+        if remaining == 0 {
+            // bound is 0 here, so the state is solved
+            self.outcome.solutions.push(self.path.clone());
+            return self.outcome.solutions.len() >= self.max_solutions;
+        }
+        self.nodes += 1;
+        if self.nodes.is_multiple_of(DEADLINE_CHECK_INTERVAL) && self.deadline.expired() {
+            self.outcome.timed_out = true;
+            return true;
+        }
+        for m in 0..N_MOVES {
+            if !can_follow(self.path.last().copied(), m) {
+                continue;
+            }
+            self.path.push(m);
+            let stop = self.visit(self.space.apply(&state, m), remaining - 1);
+            self.path.pop();
+            if stop {
+                return true;
+            }
+        }
+        false
+    }
+}
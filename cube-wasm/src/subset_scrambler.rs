@@ -0,0 +1,404 @@
+// Subset scrambles for training a single step of a method
+// A random state is drawn in which only the chosen pieces are scrambled (for example the
+// last layer with the first two layers solved), then solved with the two-phase solver so
+// the scramble looks like any other scramble instead of revealing an algorithm.
+use crate::cubie_cube::CubieCube;
+use crate::rng::Rng;
+use crate::scrambler;
+use crate::two_phase::SolveOptions;
+
+/// U layer positions, identical for corners (URF, UFL, ULB, UBR) and edges (UR, UF, UL, UB)
+const LAST_LAYER: &[u8] = &[0, 1, 2, 3];
+const ALL_CORNERS: &[u8] = &[0, 1, 2, 3, 4, 5, 6, 7];
+const ALL_EDGES: &[u8] = &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11];
+/// Corners outside the left 1x2x3 block (DLF, DBL solved), also used for the Petrus 2x2x3
+const OUTSIDE_LEFT_BLOCK_CORNERS: &[u8] = &[0, 1, 2, 3, 4, 7];
+/// Edges outside the left 1x2x3 block (DL, FL, BL solved)
+const OUTSIDE_LEFT_BLOCK_EDGES: &[u8] = &[0, 1, 2, 3, 4, 5, 7, 8, 11];
+/// Edges outside both Roux blocks: the U layer plus DF and DB
+const OUTSIDE_BLOCKS_EDGES: &[u8] = &[0, 1, 2, 3, 5, 7];
+/// Edges outside the EOLine (DF, DB solved)
+const OUTSIDE_LINE_EDGES: &[u8] = &[0, 1, 2, 3, 4, 6, 8, 9, 10, 11];
+/// Edges outside the Petrus 2x2x3 block (DL, DF, DB, FL, BL solved)
+const OUTSIDE_2X2X3_EDGES: &[u8] = &[0, 1, 2, 3, 4, 8, 11];
+/// Face move code of U, used for AUF
+const U_MOVE: u8 = 0;
+
+/// How the pieces of one group are permuted among their positions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Permutation {
+    /// Uniformly random permutation
+    Random,
+    /// Solved up to a U turn shared by every group using it (last layer only)
+    AufShift,
+}
+
+/// Description of a family of states: which pieces move and how
+#[derive(Debug, Clone, Copy)]
+pub struct SubsetSpec {
+    /// Corner positions whose pieces are scrambled among themselves; others stay solved
+    pub corners: &'static [u8],
+    /// Edge positions whose pieces are scrambled among themselves; others stay solved
+    pub edges: &'static [u8],
+    pub corner_permutation: Permutation,
+    pub edge_permutation: Permutation,
+    /// Randomly twist the scrambled corners
+    pub twist_corners: bool,
+    /// Randomly flip the scrambled edges
+    pub flip_edges: bool,
+    /// Add a random U turn in front of the case
+    pub pre_auf: bool,
+}
+
+/// Named training subsets
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Subset {
+    /// Full last layer: F2L solved, last layer random
+    LastLayer,
+    /// Orientation only: last layer permuted up to AUF
+    Oll,
+    /// Permutation only: last layer oriented
+    Pll,
+    /// Edges oriented, everything else in the last layer random
+    Zbll,
+    /// Edges oriented and permuted up to AUF, corners random
+    Coll,
+    /// Edges oriented, corners permuted up to AUF (the <R, U> last layer)
+    TwoGll,
+    /// Roux first block (left 1x2x3) solved, everything else random
+    FirstBlock,
+    /// Both Roux blocks solved, corners and edges of the U layer and M slice random
+    FirstTwoBlocks,
+    /// ZZ EOLine: all edges oriented, DF and DB solved
+    EoLine,
+    /// All edges oriented, everything else random (ZZ F2L practice)
+    EdgesOriented,
+    /// Petrus 2x2x3 block (back left) solved, everything else random
+    Block2x2x3,
+}
+
+impl Subset {
+    /// Parse a subset name, case-insensitive: "LL", "OLL", "PLL", "ZBLL", "COLL", "2GLL",
+    /// "FB" (Roux first block), "F2B" (Roux CMLL), "EOLine", "EO", "2x2x3" (Petrus)
+    pub fn from_name(name: &str) -> Result<Self, String> {
+        match name.to_ascii_uppercase().as_str() {
+            "LL" => Ok(Subset::LastLayer),
+            "OLL" => Ok(Subset::Oll),
+            "PLL" => Ok(Subset::Pll),
+            "ZBLL" => Ok(Subset::Zbll),
+            "COLL" => Ok(Subset::Coll),
+            "2GLL" => Ok(Subset::TwoGll),
+            "FB" => Ok(Subset::FirstBlock),
+            "F2B" => Ok(Subset::FirstTwoBlocks),
+            "EOLINE" => Ok(Subset::EoLine),
+            "EO" => Ok(Subset::EdgesOriented),
+            "2X2X3" => Ok(Subset::Block2x2x3),
+            _ => Err(format!("Unknown subset: {}", name)),
+        }
+    }
+
+    /// State description for this subset
+    pub fn spec(self) -> SubsetSpec {
+        let last_layer =
+            |corner_permutation, edge_permutation, twist_corners, flip_edges| SubsetSpec {
+                corners: LAST_LAYER,
+                edges: LAST_LAYER,
+                corner_permutation,
+                edge_permutation,
+                twist_corners,
+                flip_edges,
+                pre_auf: true,
+            };
+        let block = |corners, edges, flip_edges| SubsetSpec {
+            corners,
+            edges,
+            corner_permutation: Random,
+            edge_permutation: Random,
+            twist_corners: true,
+            flip_edges,
+            pre_auf: false,
+        };
+        use Permutation::{AufShift, Random};
+        match self {
+            Subset::LastLayer => last_layer(Random, Random, true, true),
+            Subset::Oll => last_layer(AufShift, AufShift, true, true),
+            Subset::Pll => last_layer(Random, Random, false, false),
+            Subset::Zbll => last_layer(Random, Random, true, false),
+            Subset::Coll => last_layer(Random, AufShift, true, false),
+            Subset::TwoGll => last_layer(AufShift, Random, true, false),
+            Subset::FirstBlock => block(OUTSIDE_LEFT_BLOCK_CORNERS, OUTSIDE_LEFT_BLOCK_EDGES, true),
+            Subset::FirstTwoBlocks => block(LAST_LAYER, OUTSIDE_BLOCKS_EDGES, true),
+            Subset::EoLine => block(ALL_CORNERS, OUTSIDE_LINE_EDGES, false),
+            Subset::EdgesOriented => block(ALL_CORNERS, ALL_EDGES, false),
+            Subset::Block2x2x3 => block(OUTSIDE_LEFT_BLOCK_CORNERS, OUTSIDE_2X2X3_EDGES, true),
+        }
+    }
+}
+
+/// Draw a random state matching a subset description
+pub fn random_subset_state(spec: &SubsetSpec, rng: &mut Rng) -> CubieCube {
+    let mut cube = CubieCube::solved();
+    let shift = rng.below(4);
+    place_pieces(
+        &mut cube.cp,
+        spec.corners,
+        spec.corner_permutation,
+        shift,
+        rng,
+    );
+    place_pieces(&mut cube.ep, spec.edges, spec.edge_permutation, shift, rng);
+
+    // Fix parity on a group that is allowed to take any permutation
+    if cube.corner_parity() != cube.edge_parity() {
+        if spec.edge_permutation == Permutation::Random && spec.edges.len() > 1 {
+            cube.ep.swap(spec.edges[0] as usize, spec.edges[1] as usize);
+        } else {
+            assert!(
+                spec.corner_permutation == Permutation::Random && spec.corners.len() > 1,
+                "subset cannot reach a valid parity"
+            );
+            cube.cp
+                .swap(spec.corners[0] as usize, spec.corners[1] as usize);
+        }
+    }
+
+    if spec.twist_corners {
+        randomize_orientation(&mut cube.co, spec.corners, 3, rng);
+    }
+    if spec.flip_edges {
+        randomize_orientation(&mut cube.eo, spec.edges, 2, rng);
+    }
+    if spec.pre_auf {
+        for _ in 0..rng.below(4) {
+            cube.apply_move(U_MOVE).expect("face move");
+        }
+    }
+    cube
+}
+
+/// Permute the pieces at `positions` among themselves
+fn place_pieces(
+    pieces: &mut [u8],
+    positions: &[u8],
+    permutation: Permutation,
+    shift: usize,
+    rng: &mut Rng,
+) {
+    let mut chosen: Vec<u8> = positions.to_vec();
+    match permutation {
+        Permutation::Random => rng.shuffle(&mut chosen),
+        // A U turn moves the piece from position i - 1 into position i
+        Permutation::AufShift => chosen.rotate_right(shift),
+    }
+    for (&position, &piece) in positions.iter().zip(&chosen) {
+        pieces[position as usize] = piece;
+    }
+}
+
+/// Random orientations at `positions` that keep the total orientation valid
+fn randomize_orientation(orientation: &mut [u8], positions: &[u8], modulus: u8, rng: &mut Rng) {
+    if let Some((&last, rest)) = positions.split_last() {
+        let mut total = 0;
+        for &position in rest {
+            let value = rng.below(modulus as usize) as u8;
+            orientation[position as usize] = value;
+            total += value;
+        }
+        orientation[last as usize] = (modulus - total % modulus) % modulus;
+    }
+}
+
+/// Generate a scramble for a subset (face move codes)
+pub fn subset_scramble(subset: Subset, rng: &mut Rng) -> Vec<u8> {
+    let spec = subset.spec();
+    loop {
+        let state = random_subset_state(&spec, rng);
+        if let Some(scramble) = scrambler::scramble_for_state(&state, SolveOptions::default()) {
+            return scramble;
+        }
+    }
+}
+
+/// Generate a scramble for one specific case (for example the state an algorithm
+/// solves), with a random U turn before and after it
+/// The case is solved with the two-phase solver, so the scramble is not simply the
+/// inverted algorithm.
+pub fn case_scramble(case: &CubieCube, rng: &mut Rng) -> Result<Vec<u8>, String> {
+    case.verify()?;
+    let auf = |turns: usize| {
+        let mut cube = CubieCube::solved();
+        for _ in 0..turns {
+            cube.apply_move(U_MOVE).expect("face move");
+        }
+        cube
+    };
+    let pre_auf = auf(rng.below(4));
+    let post_auf = auf(rng.below(4));
+    let state = pre_auf.multiply(case).multiply(&post_auf);
+    scrambler::scramble_for_state(&state, SolveOptions::default())
+        .ok_or_else(|| "No scramble found for this case".to_string())
+}
+
+/// U-turn shift (0-3) that the four last-layer entries of a permutation are, if any
+fn auf_shift(pieces: &[u8]) -> Option<usize> {
+    (0..4).find(|&shift| (0..4).all(|i| pieces[i] as usize == (i + 4 - shift) % 4))
+}
+
+/// Whether the first two layers are solved (all non-U pieces home and oriented)
+pub fn is_f2l_solved(cube: &CubieCube) -> bool {
+    (4..8).all(|i| cube.cp[i] == i as u8 && cube.co[i] == 0)
+        && (4..12).all(|i| cube.ep[i] == i as u8 && cube.eo[i] == 0)
+}
+
+/// Whether the cube is in the given subset
+pub fn is_in_subset(cube: &CubieCube, subset: Subset) -> bool {
+    let spec = subset.spec();
+    let outside_solved = |pieces: &[u8], orientation: &[u8], scrambled: &[u8]| {
+        (0..pieces.len() as u8)
+            .filter(|position| !scrambled.contains(position))
+            .all(|position| {
+                pieces[position as usize] == position && orientation[position as usize] == 0
+            })
+    };
+    outside_solved(&cube.cp, &cube.co, spec.corners)
+        && outside_solved(&cube.ep, &cube.eo, spec.edges)
+        && (spec.twist_corners || cube.co.iter().all(|&o| o == 0))
+        && (spec.flip_edges || cube.eo.iter().all(|&o| o == 0))
+        && (spec.corner_permutation == Permutation::Random || auf_shift(&cube.cp).is_some())
+        && (spec.edge_permutation == Permutation::Random || auf_shift(&cube.ep).is_some())
+        && (spec.corner_permutation != Permutation::AufShift
+            || spec.edge_permutation != Permutation::AufShift
+            || auf_shift(&cube.cp) == auf_shift(&cube.ep))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithm;
+    use crate::optimized_cube::{MoveCode, OptimizedCube};
+
+    const ALL: [Subset; 11] = [
+        Subset::LastLayer,
+        Subset::Oll,
+        Subset::Pll,
+        Subset::Zbll,
+        Subset::Coll,
+        Subset::TwoGll,
+        Subset::FirstBlock,
+        Subset::FirstTwoBlocks,
+        Subset::EoLine,
+        Subset::EdgesOriented,
+        Subset::Block2x2x3,
+    ];
+
+    #[test]
+    fn test_subset_states_are_valid() {
+        let mut rng = Rng::new(29);
+        for subset in ALL {
+            for _ in 0..50 {
+                let state = random_subset_state(&subset.spec(), &mut rng);
+                assert!(state.verify().is_ok(), "{:?}", subset);
+                assert!(is_in_subset(&state, subset), "{:?}", subset);
+            }
+        }
+    }
+
+    #[test]
+    fn test_subset_scrambles_reach_subset() {
+        let mut rng = Rng::new(30);
+        for subset in ALL {
+            let scramble = subset_scramble(subset, &mut rng);
+            assert!(OptimizedCube::validate_scramble(&scramble));
+            let state = CubieCube::from_moves(&scramble).unwrap();
+            assert!(is_in_subset(&state, subset), "{:?}", subset);
+        }
+    }
+
+    #[test]
+    fn test_subset_membership() {
+        // Sune twists corners and cycles edges with <R, U> moves only
+        let sune = OptimizedCube::parse_scramble("R U R' U R U2 R'").unwrap();
+        let state = CubieCube::from_moves(&sune).unwrap();
+        assert!(is_in_subset(&state, Subset::TwoGll));
+        assert!(is_in_subset(&state, Subset::Zbll));
+        assert!(is_in_subset(&state, Subset::LastLayer));
+        assert!(!is_in_subset(&state, Subset::Oll));
+        assert!(!is_in_subset(&state, Subset::Coll));
+        assert!(!is_in_subset(&state, Subset::Pll));
+
+        // Two twisted corners with everything else solved is an OLL case
+        let mut twisted = CubieCube::solved();
+        twisted.co[0] = 1;
+        twisted.co[1] = 2;
+        assert!(is_in_subset(&twisted, Subset::Oll));
+        assert!(is_in_subset(&twisted, Subset::Coll));
+
+        // Corners and edges shifted by different U turns are not an OLL case
+        let mut shifted = CubieCube::solved();
+        shifted.cp[..4].copy_from_slice(&[3, 0, 1, 2]);
+        shifted.ep[..4].copy_from_slice(&[2, 3, 0, 1]);
+        assert!(!is_in_subset(&shifted, Subset::Oll));
+
+        // A T perm is a PLL but not an OLL
+        let t_perm = OptimizedCube::parse_scramble("R U R' U' R' F R2 U' R' U' R U R' F'").unwrap();
+        let state = CubieCube::from_moves(&t_perm).unwrap();
+        assert!(is_in_subset(&state, Subset::Pll));
+        assert!(!is_in_subset(&state, Subset::Oll));
+
+        // <R, U> states keep the left block, the line and edge orientation
+        let sune =
+            CubieCube::from_moves(&OptimizedCube::parse_scramble("R U R' U R U2 R'").unwrap())
+                .unwrap();
+        assert!(is_in_subset(&sune, Subset::FirstTwoBlocks));
+        assert!(is_in_subset(&sune, Subset::FirstBlock));
+        assert!(is_in_subset(&sune, Subset::Block2x2x3));
+        assert!(is_in_subset(&sune, Subset::EoLine));
+        let f_move = CubieCube::from_moves(&[MoveCode::F as u8]).unwrap();
+        assert!(!is_in_subset(&f_move, Subset::EdgesOriented));
+        assert!(!is_in_subset(&f_move, Subset::FirstBlock));
+        let r_move = CubieCube::from_moves(&[MoveCode::R as u8]).unwrap();
+        assert!(is_in_subset(&r_move, Subset::FirstBlock));
+        assert!(is_in_subset(&r_move, Subset::Block2x2x3));
+        assert!(is_in_subset(&r_move, Subset::EoLine));
+        assert!(!is_in_subset(&r_move, Subset::FirstTwoBlocks));
+        let d_move = CubieCube::from_moves(&[MoveCode::D as u8]).unwrap();
+        assert!(!is_in_subset(&d_move, Subset::EoLine));
+        assert!(is_in_subset(&d_move, Subset::EdgesOriented));
+
+        assert_eq!(Subset::from_name("zbll").unwrap(), Subset::Zbll);
+        assert_eq!(Subset::from_name("EOLine").unwrap(), Subset::EoLine);
+        assert!(Subset::from_name("VLS").is_err());
+    }
+
+    #[test]
+    fn test_case_scramble_reaches_case_up_to_auf() {
+        let mut rng = Rng::new(31);
+        let ua = algorithm::parse_algorithm("M2 U M U2 M' U M2").unwrap();
+        let case = algorithm::case_state(&ua).unwrap();
+        let mut seen = Vec::new();
+        for _ in 0..8 {
+            let scramble = case_scramble(&case, &mut rng).unwrap();
+            assert!(OptimizedCube::validate_scramble(&scramble));
+            let state = CubieCube::from_moves(&scramble).unwrap();
+            assert!(is_in_subset(&state, Subset::Pll));
+
+            // Some U turn before and after the algorithm solves the scramble
+            let solvable = (0..4).any(|pre| {
+                (0..4).any(|post| {
+                    let mut cube = state.to_optimized();
+                    (0..pre).for_each(|_| cube.apply_move_code(MoveCode::U));
+                    for &code in &ua {
+                        cube.apply_move_code(MoveCode::from_u8(code).unwrap());
+                    }
+                    (0..post).for_each(|_| cube.apply_move_code(MoveCode::U));
+                    CubieCube::from_optimized(&cube).unwrap().is_solved()
+                })
+            });
+            assert!(solvable);
+            seen.push(scramble);
+        }
+        // Random AUFs give different scrambles
+        seen.dedup();
+        assert!(seen.len() > 1);
+    }
+}
@@ -0,0 +1,393 @@
+// Two-phase (Kociemba) solver over the cubie representation
+// Phase 1 brings the cube into <U, D, R2, L2, F2, B2> (oriented pieces, slice edges in the slice),
+// phase 2 solves it using only those moves. Tables are built lazily on first use.
+// Solutions use face move codes (0-17) and never contain two moves on the same face
+// or three moves on the same axis, so they always pass `OptimizedCube::validate_scramble`.
+use crate::cubie_cube::CubieCube;
+use std::sync::OnceLock;
+
+const N_TWIST: usize = 2187;
+const N_FLIP: usize = 2048;
+const N_SLICE: usize = 495;
+const N_PERM_8: usize = 40320;
+const N_SLICE_PERM: usize = 24;
+const N_MOVES: usize = 18;
+/// Moves that keep the cube inside the phase 2 group: U, D, U', D', U2, D2, F2, B2, R2, L2
+const PHASE2_MOVES: [u8; 10] = [0, 1, 6, 7, 12, 13, 14, 15, 16, 17];
+const UNVISITED: u8 = u8::MAX;
+
+struct Tables {
+    twist_move: Vec<[u16; N_MOVES]>,
+    flip_move: Vec<[u16; N_MOVES]>,
+    slice_move: Vec<[u16; N_MOVES]>,
+    corner_perm_move: Vec<[u16; N_MOVES]>,
+    ud_edge_perm_move: Vec<[u16; N_MOVES]>,
+    slice_perm_move: Vec<[u16; N_MOVES]>,
+    twist_slice_prune: Vec<u8>,
+    flip_slice_prune: Vec<u8>,
+    corner_slice_prune: Vec<u8>,
+    edge_slice_prune: Vec<u8>,
+}
+
+static TABLES: OnceLock<Tables> = OnceLock::new();
+
+fn tables() -> &'static Tables {
+    TABLES.get_or_init(Tables::build)
+}
+
+impl Tables {
+    fn build() -> Tables {
+        let move_cubes: Vec<CubieCube> = (0..N_MOVES as u8)
+            .map(|m| CubieCube::move_cube(m).expect("face move"))
+            .collect();
+
+        let twist_move =
+            build_move_table(N_TWIST, &move_cubes, |c, i| c.set_twist(i), |c| c.twist());
+        let flip_move = build_move_table(N_FLIP, &move_cubes, |c, i| c.set_flip(i), |c| c.flip());
+        let slice_move =
+            build_move_table(N_SLICE, &move_cubes, |c, i| c.set_slice(i), |c| c.slice());
+        let corner_perm_move = build_move_table(
+            N_PERM_8,
+            &move_cubes,
+            |c, i| c.set_corner_permutation(i),
+            |c| c.corner_permutation(),
+        );
+        // The edge permutation coordinates are only meaningful for phase 2 moves
+        let phase2_cubes: Vec<CubieCube> = (0..N_MOVES as u8)
+            .map(|m| {
+                if PHASE2_MOVES.contains(&m) {
+                    move_cubes[m as usize]
+                } else {
+                    CubieCube::solved()
+                }
+            })
+            .collect();
+        let ud_edge_perm_move = build_move_table(
+            N_PERM_8,
+            &phase2_cubes,
+            |c, i| c.set_ud_edge_permutation(i),
+            |c| c.ud_edge_permutation(),
+        );
+        let slice_perm_move = build_move_table(
+            N_SLICE_PERM,
+            &phase2_cubes,
+            |c, i| c.set_slice_permutation(i),
+            |c| c.slice_permutation(),
+        );
+
+        let all_moves: Vec<u8> = (0..N_MOVES as u8).collect();
+        let twist_slice_prune = build_prune_table(&twist_move, &slice_move, N_SLICE, &all_moves);
+        let flip_slice_prune = build_prune_table(&flip_move, &slice_move, N_SLICE, &all_moves);
+        let corner_slice_prune = build_prune_table(
+            &corner_perm_move,
+            &slice_perm_move,
+            N_SLICE_PERM,
+            &PHASE2_MOVES,
+        );
+        let edge_slice_prune = build_prune_table(
+            &ud_edge_perm_move,
+            &slice_perm_move,
+            N_SLICE_PERM,
+            &PHASE2_MOVES,
+        );
+
+        Tables {
+            twist_move,
+            flip_move,
+            slice_move,
+            corner_perm_move,
+            ud_edge_perm_move,
+            slice_perm_move,
+            twist_slice_prune,
+            flip_slice_prune,
+            corner_slice_prune,
+            edge_slice_prune,
+        }
+    }
+}
+
+/// Tabulate how every move changes one coordinate
+fn build_move_table(
+    size: usize,
+    move_cubes: &[CubieCube],
+    set: impl Fn(&mut CubieCube, u16),
+    get: impl Fn(&CubieCube) -> u16,
+) -> Vec<[u16; N_MOVES]> {
+    let mut table = vec![[0u16; N_MOVES]; size];
+    let mut cube = CubieCube::solved();
+    for (index, row) in table.iter_mut().enumerate() {
+        set(&mut cube, index as u16);
+        for (entry, move_cube) in row.iter_mut().zip(move_cubes) {
+            *entry = get(&cube.multiply(move_cube));
+        }
+    }
+    table
+}
+
+/// Breadth-first distances to solved over the product of two coordinates
+fn build_prune_table(
+    major_move: &[[u16; N_MOVES]],
+    minor_move: &[[u16; N_MOVES]],
+    minor_size: usize,
+    moves: &[u8],
+) -> Vec<u8> {
+    let mut table = vec![UNVISITED; major_move.len() * minor_size];
+    table[0] = 0;
+    let mut frontier = vec![0usize];
+    let mut depth = 0;
+    while !frontier.is_empty() {
+        let mut next = Vec::new();
+        for &index in &frontier {
+            let (major, minor) = (index / minor_size, index % minor_size);
+            for &m in moves {
+                let target = major_move[major][m as usize] as usize * minor_size
+                    + minor_move[minor][m as usize] as usize;
+                if table[target] == UNVISITED {
+                    table[target] = depth + 1;
+                    next.push(target);
+                }
+            }
+        }
+        frontier = next;
+        depth += 1;
+    }
+    table
+}
+
+/// Whether `next` may follow `previous` in a solution
+/// Forbids repeating a face and fixes the order of commuting opposite faces
+pub(crate) fn can_follow(previous: Option<u8>, next: u8) -> bool {
+    match previous {
+        None => true,
+        Some(previous) => {
+            let (previous_face, face) = (previous % 6, next % 6);
+            previous_face != face && !(previous_face / 2 == face / 2 && face < previous_face)
+        }
+    }
+}
+
+/// Search limits and restrictions for a two-phase solve
+#[derive(Debug, Clone, Copy)]
+pub struct SolveOptions {
+    /// Longest acceptable solution
+    pub max_length: usize,
+    /// Axis (0=UD, 1=FB, 2=RL) the first move must not turn
+    pub first_axis_excluded: Option<u8>,
+    /// Axis the last move must not turn
+    pub last_axis_excluded: Option<u8>,
+    /// Give up after visiting this many search nodes
+    pub node_limit: usize,
+}
+
+impl Default for SolveOptions {
+    fn default() -> Self {
+        SolveOptions {
+            max_length: 22,
+            first_axis_excluded: None,
+            last_axis_excluded: None,
+            node_limit: 20_000_000,
+        }
+    }
+}
+
+struct Search<'a> {
+    tables: &'a Tables,
+    cube: CubieCube,
+    options: SolveOptions,
+    moves: Vec<u8>,
+    nodes: usize,
+}
+
+impl Search<'_> {
+    fn phase1(&mut self, twist: usize, flip: usize, slice: usize, depth: usize) -> bool {
+        self.nodes += 1;
+        if depth == 0 {
+            // Ending phase 1 with a phase 2 move would only duplicate a shorter search
+            let ends_in_phase2 = self.moves.last().is_some_and(|m| PHASE2_MOVES.contains(m));
+            return twist == 0 && flip == 0 && slice == 0 && !ends_in_phase2 && self.start_phase2();
+        }
+        let t = self.tables;
+        let bound = t.twist_slice_prune[twist * N_SLICE + slice]
+            .max(t.flip_slice_prune[flip * N_SLICE + slice]) as usize;
+        if bound > depth || self.nodes > self.options.node_limit {
+            return false;
+        }
+        for m in 0..N_MOVES as u8 {
+            if !self.allowed(m) {
+                continue;
+            }
+            let mi = m as usize;
+            self.moves.push(m);
+            if self.phase1(
+                t.twist_move[twist][mi] as usize,
+                t.flip_move[flip][mi] as usize,
+                t.slice_move[slice][mi] as usize,
+                depth - 1,
+            ) {
+                return true;
+            }
+            self.moves.pop();
+        }
+        false
+    }
+
+    fn start_phase2(&mut self) -> bool {
+        let mut cube = self.cube;
+        cube.apply_moves(&self.moves).expect("face moves");
+        let corners = cube.corner_permutation() as usize;
+        let edges = cube.ud_edge_permutation() as usize;
+        let slice = cube.slice_permutation() as usize;
+        let remaining = self.options.max_length - self.moves.len();
+        let t = self.tables;
+        let bound = t.corner_slice_prune[corners * N_SLICE_PERM + slice]
+            .max(t.edge_slice_prune[edges * N_SLICE_PERM + slice]) as usize;
+        (bound..=remaining).any(|depth| self.phase2(corners, edges, slice, depth))
+    }
+
+    fn phase2(&mut self, corners: usize, edges: usize, slice: usize, depth: usize) -> bool {
+        self.nodes += 1;
+        if depth == 0 {
+            return corners == 0 && edges == 0 && slice == 0 && self.last_move_allowed();
+        }
+        let t = self.tables;
+        let bound = t.corner_slice_prune[corners * N_SLICE_PERM + slice]
+            .max(t.edge_slice_prune[edges * N_SLICE_PERM + slice]) as usize;
+        if bound > depth || self.nodes > self.options.node_limit {
+            return false;
+        }
+        for &m in &PHASE2_MOVES {
+            if !self.allowed(m) {
+                continue;
+            }
+            let mi = m as usize;
+            self.moves.push(m);
+            if self.phase2(
+                t.corner_perm_move[corners][mi] as usize,
+                t.ud_edge_perm_move[edges][mi] as usize,
+                t.slice_perm_move[slice][mi] as usize,
+                depth - 1,
+            ) {
+                return true;
+            }
+            self.moves.pop();
+        }
+        false
+    }
+
+    fn allowed(&self, m: u8) -> bool {
+        if self.moves.is_empty() && self.options.first_axis_excluded == Some(m % 6 / 2) {
+            return false;
+        }
+        can_follow(self.moves.last().copied(), m)
+    }
+
+    fn last_move_allowed(&self) -> bool {
+        match (self.moves.last(), self.options.last_axis_excluded) {
+            (Some(&m), Some(axis)) => m % 6 / 2 != axis,
+            _ => true,
+        }
+    }
+}
+
+/// Find a solution (sequence of face move codes) for a cube state
+/// Returns None if no solution within `options.max_length` was found before the node limit.
+pub fn solve(cube: &CubieCube, options: SolveOptions) -> Option<Vec<u8>> {
+    if cube.verify().is_err() {
+        return None;
+    }
+    let tables = tables();
+    let mut search = Search {
+        tables,
+        cube: *cube,
+        options,
+        moves: Vec::new(),
+        nodes: 0,
+    };
+    let twist = cube.twist() as usize;
+    let flip = cube.flip() as usize;
+    let slice = cube.slice() as usize;
+    for depth in 0..=options.max_length {
+        if search.phase1(twist, flip, slice, depth) {
+            return Some(search.moves);
+        }
+        if search.nodes > options.node_limit {
+            break;
+        }
+    }
+    None
+}
+
+/// Invert a face move sequence (reverse order, invert each move)
+pub fn invert_moves(moves: &[u8]) -> Vec<u8> {
+    moves
+        .iter()
+        .rev()
+        .map(|&m| match m / 6 {
+            0 => m + 6,
+            1 => m - 6,
+            _ => m,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::optimized_cube::OptimizedCube;
+    use crate::rng::Rng;
+
+    #[test]
+    fn test_solves_random_states() {
+        let mut rng = Rng::new(2025);
+        for _ in 0..10 {
+            let cube = CubieCube::random(&mut rng);
+            let solution = solve(&cube, SolveOptions::default()).expect("solution");
+            assert!(solution.len() <= 22);
+            assert!(OptimizedCube::validate_scramble(&solution));
+
+            let mut check = cube;
+            check.apply_moves(&solution).unwrap();
+            assert!(check.is_solved());
+        }
+    }
+
+    #[test]
+    fn test_solves_short_scrambles() {
+        assert_eq!(
+            solve(&CubieCube::solved(), SolveOptions::default()),
+            Some(vec![])
+        );
+        let cube = CubieCube::from_moves(&[4, 0, 10]).unwrap(); // R U R'
+        let solution = solve(&cube, SolveOptions::default()).unwrap();
+        let mut check = cube;
+        check.apply_moves(&solution).unwrap();
+        assert!(check.is_solved());
+    }
+
+    #[test]
+    fn test_axis_restrictions() {
+        let mut rng = Rng::new(11);
+        let options = SolveOptions {
+            first_axis_excluded: Some(2),
+            last_axis_excluded: Some(1),
+            ..SolveOptions::default()
+        };
+        for _ in 0..5 {
+            let cube = CubieCube::random(&mut rng);
+            let solution = solve(&cube, options).unwrap();
+            assert_ne!(solution[0] % 6 / 2, 2);
+            assert_ne!(solution[solution.len() - 1] % 6 / 2, 1);
+        }
+    }
+
+    #[test]
+    fn test_invert_moves() {
+        let moves = OptimizedCube::parse_scramble("R U' F2 D").unwrap();
+        let inverse = invert_moves(&moves);
+        assert_eq!(OptimizedCube::moves_to_string(&inverse), "D' F2 U R'");
+        let mut cube = OptimizedCube::solved();
+        cube.apply_moves(&moves);
+        cube.apply_moves(&inverse);
+        assert!(cube.is_solved());
+    }
+}
@@ -0,0 +1,179 @@
+// Algorithm verification: whether a submitted algorithm solves a case
+// The algorithm is applied to the case state, optionally from any side (a y rotation
+// first) and with U turns before and after it. Every combination is tried and the one
+// leaving the fewest pieces wrong is reported, with those pieces listed.
+use crate::cross::Y_TURNS;
+use crate::cubie_cube::{CubieCube, CORNER_NAMES, EDGE_NAMES};
+use crate::last_layer::auf_moves;
+use crate::subset_scrambler::is_f2l_solved;
+
+/// Freedom allowed when checking an algorithm against a case
+#[derive(Debug, Clone, Copy)]
+pub struct VerifyOptions {
+    /// Allow U turns before and after the algorithm
+    pub allow_auf: bool,
+    /// Allow performing the algorithm from another side (a y rotation first)
+    pub allow_rotation: bool,
+}
+
+impl Default for VerifyOptions {
+    fn default() -> Self {
+        VerifyOptions {
+            allow_auf: true,
+            allow_rotation: true,
+        }
+    }
+}
+
+/// A position holding the wrong piece, or the right piece misoriented
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PieceDiff {
+    /// Position name, e.g. "URF" or "UF"
+    pub position: &'static str,
+    /// Piece found there
+    pub piece: &'static str,
+    /// Twist (corners) or flip (edges) of that piece
+    pub orientation: u8,
+}
+
+/// Result of applying an algorithm to a case
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Verification {
+    /// The whole cube ends solved
+    pub solves: bool,
+    /// The first two layers end solved
+    pub preserves_f2l: bool,
+    /// Rotation (move codes) done before the algorithm
+    pub rotation: Vec<u8>,
+    /// Quarter turns of U before the algorithm
+    pub pre_auf: u8,
+    /// Quarter turns of U after the algorithm
+    pub post_auf: u8,
+    /// Pieces left wrong, corners first
+    pub wrong_pieces: Vec<PieceDiff>,
+}
+
+/// Pieces that are not solved, corners first
+pub fn piece_diff(cube: &CubieCube) -> Vec<PieceDiff> {
+    let corners = (0..8)
+        .filter(|&i| cube.cp[i] != i as u8 || cube.co[i] != 0)
+        .map(|i| PieceDiff {
+            position: CORNER_NAMES[i],
+            piece: CORNER_NAMES[cube.cp[i] as usize],
+            orientation: cube.co[i],
+        });
+    let edges = (0..12)
+        .filter(|&i| cube.ep[i] != i as u8 || cube.eo[i] != 0)
+        .map(|i| PieceDiff {
+            position: EDGE_NAMES[i],
+            piece: EDGE_NAMES[cube.ep[i] as usize],
+            orientation: cube.eo[i],
+        });
+    corners.chain(edges).collect()
+}
+
+/// Apply `moves` to the case state with the freedom allowed by the options
+/// The combination leaving the fewest pieces wrong is reported; ties go to the one with
+/// the smaller rotation, then the smaller AUFs.
+pub fn verify_algorithm(
+    case: &CubieCube,
+    moves: &[u8],
+    options: VerifyOptions,
+) -> Result<Verification, String> {
+    case.verify()?;
+    let rotations = if options.allow_rotation { 4 } else { 1 };
+    let aufs = if options.allow_auf { 4 } else { 1 };
+    let start = case.to_optimized();
+    let mut best: Option<Verification> = None;
+    for y_turn in &Y_TURNS[..rotations] {
+        let rotation: Vec<u8> = y_turn.iter().map(|&m| m as u8).collect();
+        for pre_auf in 0..aufs {
+            let mut cube = start.clone();
+            cube.apply_moves(&rotation);
+            cube.apply_moves(&auf_moves(pre_auf));
+            cube.apply_moves(moves);
+            for post_auf in 0..aufs {
+                let mut finished = cube.clone();
+                finished.apply_moves(&auf_moves(post_auf));
+                let state = CubieCube::from_optimized(&finished)?;
+                let wrong_pieces = piece_diff(&state);
+                if best
+                    .as_ref()
+                    .is_some_and(|best| best.wrong_pieces.len() <= wrong_pieces.len())
+                {
+                    continue;
+                }
+                best = Some(Verification {
+                    solves: wrong_pieces.is_empty(),
+                    preserves_f2l: is_f2l_solved(&state),
+                    rotation: rotation.clone(),
+                    pre_auf,
+                    post_auf,
+                    wrong_pieces,
+                });
+            }
+        }
+    }
+    Ok(best.expect("at least one combination"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithm;
+    use crate::f2l::F2L_ALGORITHMS;
+    use crate::optimized_cube::MoveCode;
+
+    fn case_of(alg: &str) -> CubieCube {
+        algorithm::case_state(&algorithm::parse_algorithm(alg).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_verify_with_auf() {
+        // Two Ua permutations from different angles
+        let case = case_of("U2 M2 U M U2 M' U M2");
+        let alg = algorithm::parse_algorithm("R U' R U R U R U' R' U' R2").unwrap();
+        let result = verify_algorithm(&case, &alg, VerifyOptions::default()).unwrap();
+        assert!(result.solves && result.preserves_f2l);
+        assert!(result.wrong_pieces.is_empty());
+        assert_ne!((result.pre_auf, result.post_auf), (0, 0));
+
+        let strict = VerifyOptions {
+            allow_auf: false,
+            allow_rotation: false,
+        };
+        let result = verify_algorithm(&case, &alg, strict).unwrap();
+        assert!(!result.solves);
+
+        // Sune on the Antisune case orients nothing but keeps F2L
+        let case = case_of("R U2 R' U' R U' R'");
+        let sune = algorithm::parse_algorithm("R U R' U R U2 R'").unwrap();
+        let result = verify_algorithm(&case, &sune, VerifyOptions::default()).unwrap();
+        assert!(!result.solves && result.preserves_f2l);
+        assert!(result
+            .wrong_pieces
+            .iter()
+            .all(|diff| diff.position.starts_with('U')));
+    }
+
+    #[test]
+    fn test_verify_with_rotation() {
+        // The FR algorithm of F2L 4 solves the FL case after a y
+        let alg = algorithm::parse_algorithm(F2L_ALGORITHMS[3]).unwrap();
+        let case = algorithm::case_state(&algorithm::y_rotated(&alg, 1)).unwrap();
+        let result = verify_algorithm(&case, &alg, VerifyOptions::default()).unwrap();
+        assert!(result.solves);
+        assert_eq!(result.rotation, vec![MoveCode::Yp as u8]);
+
+        let no_rotation = VerifyOptions {
+            allow_rotation: false,
+            ..VerifyOptions::default()
+        };
+        let result = verify_algorithm(&case, &alg, no_rotation).unwrap();
+        assert!(!result.solves && !result.preserves_f2l);
+        assert!(result
+            .wrong_pieces
+            .iter()
+            .any(|diff| diff.position == "FL" || diff.position == "DLF"));
+    }
+}
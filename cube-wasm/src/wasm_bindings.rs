@@ -1,4 +1,6 @@
 use crate::optimized_cube::{MoveCode, OptimizedCube};
+use crate::rng::Rng;
+use crate::scrambler;
 use wasm_bindgen::prelude::*;
 
 // Export the optimized cube to JavaScript with zero-copy capabilities
@@ -7,6 +9,12 @@ pub struct WasmOptimizedCube {
     cube: OptimizedCube,
 }
 
+impl Default for WasmOptimizedCube {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[wasm_bindgen]
 impl WasmOptimizedCube {
     #[wasm_bindgen(constructor)]
@@ -31,6 +39,7 @@ impl WasmOptimizedCube {
 
     // Get the length of the sticker array (always 54)
     #[wasm_bindgen]
+    #[allow(clippy::len_without_is_empty)]
     pub fn len(&self) -> usize {
         54
     }
@@ -93,6 +102,12 @@ impl WasmOptimizedCube {
         OptimizedCube::generate_long_scramble()
     }
 
+    // Generate blindfolded scramble (random state plus random orientation)
+    #[wasm_bindgen]
+    pub fn generate_bld_scramble() -> Vec<u8> {
+        scrambler::bld_scramble(&mut Rng::from_entropy())
+    }
+
     // Validate scramble according to WCA rules
     #[wasm_bindgen]
     pub fn validate_scramble(moves: &[u8]) -> bool {
@@ -139,6 +154,7 @@ impl WasmOptimizedCube {
 
     // Clone the cube
     #[wasm_bindgen]
+    #[allow(clippy::should_implement_trait)]
     pub fn clone(&self) -> WasmOptimizedCube {
         WasmOptimizedCube {
             cube: self.cube.clone(),
@@ -164,29 +180,7 @@ impl MoveUtils {
     #[wasm_bindgen]
     pub fn code_to_move(code: u8) -> Result<String, JsValue> {
         let move_code = MoveCode::from_u8(code).map_err(|e| JsValue::from_str(&e))?;
-
-        let move_str = match move_code {
-            MoveCode::U => "U",
-            MoveCode::D => "D",
-            MoveCode::F => "F",
-            MoveCode::B => "B",
-            MoveCode::R => "R",
-            MoveCode::L => "L",
-            MoveCode::Up => "U'",
-            MoveCode::Dp => "D'",
-            MoveCode::Fp => "F'",
-            MoveCode::Bp => "B'",
-            MoveCode::Rp => "R'",
-            MoveCode::Lp => "L'",
-            MoveCode::U2 => "U2",
-            MoveCode::D2 => "D2",
-            MoveCode::F2 => "F2",
-            MoveCode::B2 => "B2",
-            MoveCode::R2 => "R2",
-            MoveCode::L2 => "L2",
-        };
-
-        Ok(move_str.to_string())
+        Ok(move_code.notation().to_string())
     }
 
     // Parse scramble to move codes
@@ -0,0 +1,210 @@
+// Extended cross solver: XCross (cross + one F2L pair) and XXCross (cross + two pairs)
+// Each color and slot choice is a piece search bounded by the cross table and by tables
+// pairing the slot's corner and edge with two cross edges. All choices are deepened
+// together (`search::search_jointly`), so results come out shortest first.
+use crate::cross;
+use crate::optimized_cube::OptimizedCube;
+use crate::pieces::{Piece, PieceSpace};
+use crate::search::{self, Deadline};
+
+/// F2L slot names, seen with the cross color at the bottom
+pub const SLOT_NAMES: [&str; 4] = ["FR", "FL", "BL", "BR"];
+/// D cross edges: DR, DF, DL, DB
+const CROSS_EDGES: [u8; 4] = [4, 5, 6, 7];
+/// Corner of each slot: DFR, DLF, DBL, DRB
+const SLOT_CORNERS: [u8; 4] = [4, 5, 6, 7];
+/// Edge of each slot: FR, FL, BL, BR
+const SLOT_EDGES: [u8; 4] = [8, 9, 10, 11];
+/// Cross edges next to each slot
+const SLOT_CROSS_EDGES: [[u8; 2]; 4] = [[4, 5], [5, 6], [6, 7], [7, 4]];
+
+/// Limits for an extended cross analysis
+#[derive(Debug, Clone, Copy)]
+pub struct ExtendedCrossOptions {
+    /// Longest solution searched for
+    pub max_length: usize,
+    /// Stop once this many solutions are found
+    pub max_results: usize,
+    /// Stop after this many milliseconds
+    pub timeout_ms: Option<f64>,
+}
+
+impl Default for ExtendedCrossOptions {
+    fn default() -> Self {
+        ExtendedCrossOptions {
+            max_length: 14,
+            max_results: usize::MAX,
+            timeout_ms: Some(5000.0),
+        }
+    }
+}
+
+/// Optimal extended cross for one color and slot choice
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtendedCrossSolution {
+    pub color: u8,
+    /// Solved slots (indices into `SLOT_NAMES`)
+    pub slots: Vec<usize>,
+    /// Rotation (move codes) bringing the color to the bottom
+    pub rotation: Vec<u8>,
+    /// Face moves after the rotation
+    pub moves: Vec<u8>,
+}
+
+/// Solutions found, shortest first
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExtendedCrossReport {
+    pub solutions: Vec<ExtendedCrossSolution>,
+    /// Every color and slot choice got its optimal solution
+    pub complete: bool,
+}
+
+/// Search space for the D cross plus the given slots
+fn extended_cross_space(slots: &[usize]) -> PieceSpace {
+    let cross_edges: Vec<Piece> = CROSS_EDGES.iter().map(|&e| Piece::Edge(e)).collect();
+    let mut pieces = cross_edges.clone();
+    let mut groups = vec![cross_edges];
+    for &slot in slots {
+        let pair = [
+            Piece::Corner(SLOT_CORNERS[slot]),
+            Piece::Edge(SLOT_EDGES[slot]),
+        ];
+        pieces.extend_from_slice(&pair);
+        // The pair with the two cross edges next to it, and with the other two
+        let (near, far): (Vec<u8>, Vec<u8>) = CROSS_EDGES
+            .iter()
+            .partition(|edge| SLOT_CROSS_EDGES[slot].contains(edge));
+        for edges in [near, far] {
+            let mut group = pair.to_vec();
+            group.extend(edges.iter().map(|&e| Piece::Edge(e)));
+            groups.push(group);
+        }
+    }
+    PieceSpace::new(&pieces, &groups)
+}
+
+/// Slot choices with `pair_count` slots, in index order
+fn slot_choices(pair_count: usize) -> Vec<Vec<usize>> {
+    match pair_count {
+        1 => (0..4).map(|slot| vec![slot]).collect(),
+        2 => (0..4)
+            .flat_map(|first| (first + 1..4).map(move |second| vec![first, second]))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Optimal XCross (`pair_count` 1) or XXCross (`pair_count` 2) for every color and slot
+/// choice, shortest first, within the option limits
+pub fn solve_extended_cross(
+    cube: &OptimizedCube,
+    pair_count: usize,
+    options: ExtendedCrossOptions,
+) -> Result<ExtendedCrossReport, String> {
+    let choices = slot_choices(pair_count);
+    if choices.is_empty() {
+        return Err(format!("Unsupported number of pairs: {}", pair_count));
+    }
+    let spaces: Vec<PieceSpace> = choices
+        .iter()
+        .map(|slots| extended_cross_space(slots))
+        .collect();
+
+    // (color, choice, rotation) of every search, with the space and start state
+    let mut searches = Vec::new();
+    let mut problems = Vec::new();
+    for color in 0..6 {
+        let rotation = cross::rotation_to_bottom(cube, color)?;
+        let state = cross::rotated_state(cube, &rotation)?;
+        for (choice, space) in spaces.iter().enumerate() {
+            searches.push((color, choice, rotation.clone()));
+            problems.push((space, space.state(&state)));
+        }
+    }
+
+    let deadline = Deadline::from_timeout(options.timeout_ms);
+    let outcome =
+        search::search_jointly(&problems, options.max_length, options.max_results, deadline);
+    let solutions = outcome
+        .solutions
+        .into_iter()
+        .map(|(index, moves)| {
+            let (color, choice, rotation) = &searches[index];
+            ExtendedCrossSolution {
+                color: *color,
+                slots: choices[*choice].clone(),
+                rotation: rotation.clone(),
+                moves,
+            }
+        })
+        .collect();
+    Ok(ExtendedCrossReport {
+        solutions,
+        complete: outcome.complete,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cubie_cube::CubieCube;
+
+    #[test]
+    fn test_xcross_solutions_solve_cross_and_pair() {
+        let mut cube = OptimizedCube::solved();
+        cube.apply_scramble("R U R' F2 D L' B U2").unwrap();
+        let report = solve_extended_cross(&cube, 1, ExtendedCrossOptions::default()).unwrap();
+        assert!(report.complete);
+        assert_eq!(report.solutions.len(), 24);
+        assert!(report
+            .solutions
+            .windows(2)
+            .all(|pair| pair[0].moves.len() <= pair[1].moves.len()));
+
+        for solution in &report.solutions {
+            let mut solved = cube.clone();
+            solved.apply_moves(&solution.rotation);
+            solved.apply_moves(&solution.moves);
+            let state = CubieCube::from_optimized(&solved).unwrap();
+            let slot = solution.slots[0];
+            let corner = SLOT_CORNERS[slot] as usize;
+            let edge = SLOT_EDGES[slot] as usize;
+            assert_eq!(cross::cross_length(&state), 0);
+            assert_eq!((state.cp[corner], state.co[corner]), (corner as u8, 0));
+            assert_eq!((state.ep[edge], state.eo[edge]), (edge as u8, 0));
+            // Never shorter than the cross alone
+            let cross_length = cross::cross_length_for_color(&cube, solution.color).unwrap();
+            assert!(solution.moves.len() >= cross_length as usize);
+        }
+    }
+
+    #[test]
+    fn test_extended_cross_limits() {
+        let mut cube = OptimizedCube::solved();
+        cube.apply_scramble("R U R' U'").unwrap();
+        // Yellow cross with the BL and FL pairs is untouched by R and U
+        let options = ExtendedCrossOptions {
+            max_results: 3,
+            ..ExtendedCrossOptions::default()
+        };
+        let report = solve_extended_cross(&cube, 2, options).unwrap();
+        assert_eq!(report.solutions.len(), 3);
+        assert!(!report.complete);
+        assert_eq!(report.solutions[0].color, OptimizedCube::YELLOW);
+        assert_eq!(report.solutions[0].slots, vec![1, 2]);
+        assert!(report.solutions[0].moves.is_empty());
+
+        let options = ExtendedCrossOptions {
+            timeout_ms: Some(0.0),
+            max_length: 20,
+            ..ExtendedCrossOptions::default()
+        };
+        let mut scrambled = OptimizedCube::solved();
+        scrambled
+            .apply_scramble("D2 F' R U2 B L' D R2 F U' L2 B2 R")
+            .unwrap();
+        let report = solve_extended_cross(&scrambled, 2, options).unwrap();
+        assert!(!report.complete);
+        assert!(solve_extended_cross(&cube, 3, options).is_err());
+    }
+}